@@ -120,6 +120,7 @@ async function loadQueue() {
                     <div class="queue-item-content">
                         <div class="queue-item-agent">${escapeHtml(agentName)}</div>
                         <div class="queue-item-time">${formatTimestamp(item.created_at)}</div>
+                        ${item.decision ? `<div class="queue-item-time" title="${escapeHtml(item.decision)}">${escapeHtml(item.decision)}</div>` : ''}
                     </div>
                     <span class="status-badge ${statusClass}">${item.status}</span>
                 </div>
@@ -228,6 +228,8 @@ let userSelectedIcon = false; // Track if user manually selected an icon
 
 // App data (for edit mode)
 let appData = null;
+let agentConfigData = null; // Loaded agent config, preserved on save for fields the form doesn't edit
+let workspaceApps = []; // Apps a workspace can start
 let isEditMode = false;
 
 // Debounce timer for URL input
@@ -518,6 +520,8 @@ function updateFieldsVisibility() {
         document.getElementById('agent-input-website-group').style.display = 'none';
         document.getElementById('agent-scrape-mode-label').style.display = 'none';
         document.getElementById('agent-scrape-mode-group').style.display = 'none';
+        document.getElementById('agent-conditions-label').style.display = 'none';
+        document.getElementById('agent-conditions-group').style.display = 'none';
 
         // Hide Output section
         document.getElementById('agent-output-label').style.display = 'none';
@@ -526,6 +530,12 @@ function updateFieldsVisibility() {
         document.getElementById('agent-output-notification-group').style.display = 'none';
         document.getElementById('agent-output-command-label').style.display = 'none';
         document.getElementById('agent-output-command-group').style.display = 'none';
+        document.getElementById('agent-output-memory-label').style.display = 'none';
+        document.getElementById('agent-output-memory-group').style.display = 'none';
+        document.getElementById('agent-output-documents-label').style.display = 'none';
+        document.getElementById('agent-output-documents-group').style.display = 'none';
+        document.getElementById('agent-dry-run-label').style.display = 'none';
+        document.getElementById('agent-dry-run-group').style.display = 'none';
 
         // Hide common fields separator
         document.getElementById('agent-common-separator').style.display = 'none';
@@ -562,6 +572,8 @@ function updateFieldsVisibility() {
         document.getElementById('agent-input-website-group').style.display = 'flex';
         document.getElementById('agent-scrape-mode-label').style.display = 'block';
         document.getElementById('agent-scrape-mode-group').style.display = 'flex';
+        document.getElementById('agent-conditions-label').style.display = 'block';
+        document.getElementById('agent-conditions-group').style.display = 'flex';
 
         // Show Output section
         document.getElementById('agent-output-label').style.display = 'block';
@@ -570,6 +582,12 @@ function updateFieldsVisibility() {
         document.getElementById('agent-output-notification-group').style.display = 'flex';
         document.getElementById('agent-output-command-label').style.display = 'block';
         document.getElementById('agent-output-command-group').style.display = 'flex';
+        document.getElementById('agent-output-memory-label').style.display = 'block';
+        document.getElementById('agent-output-memory-group').style.display = 'flex';
+        document.getElementById('agent-output-documents-label').style.display = 'block';
+        document.getElementById('agent-output-documents-group').style.display = 'flex';
+        document.getElementById('agent-dry-run-label').style.display = 'block';
+        document.getElementById('agent-dry-run-group').style.display = 'flex';
 
         // Show common fields separator
         document.getElementById('agent-common-separator').style.display = 'block';
@@ -607,6 +625,8 @@ function updateFieldsVisibility() {
         document.getElementById('agent-input-website-group').style.display = 'none';
         document.getElementById('agent-scrape-mode-label').style.display = 'none';
         document.getElementById('agent-scrape-mode-group').style.display = 'none';
+        document.getElementById('agent-conditions-label').style.display = 'none';
+        document.getElementById('agent-conditions-group').style.display = 'none';
 
         // Hide Output section
         document.getElementById('agent-output-label').style.display = 'none';
@@ -615,11 +635,36 @@ function updateFieldsVisibility() {
         document.getElementById('agent-output-notification-group').style.display = 'none';
         document.getElementById('agent-output-command-label').style.display = 'none';
         document.getElementById('agent-output-command-group').style.display = 'none';
+        document.getElementById('agent-output-memory-label').style.display = 'none';
+        document.getElementById('agent-output-memory-group').style.display = 'none';
+        document.getElementById('agent-output-documents-label').style.display = 'none';
+        document.getElementById('agent-output-documents-group').style.display = 'none';
+        document.getElementById('agent-dry-run-label').style.display = 'none';
+        document.getElementById('agent-dry-run-group').style.display = 'none';
 
         // Hide common fields separator
         document.getElementById('agent-common-separator').style.display = 'none';
     }
 
+    // Working directory and environment apply to native and terminal apps
+    const isCommand = type === 'app' || type === 'tui';
+    ['working-dir', 'env', 'env-file'].forEach(field => {
+        document.getElementById(`${field}-label`).style.display = isCommand ? 'block' : 'none';
+        document.getElementById(`${field}-group`).style.display = isCommand ? 'flex' : 'none';
+    });
+    document.getElementById('single-instance-label').style.display = type === 'app' ? 'block' : 'none';
+    document.getElementById('single-instance-group').style.display = type === 'app' ? 'flex' : 'none';
+
+    // Workspaces start other apps instead of a binary
+    const isWorkspace = type === 'workspace';
+    document.getElementById('workspace-items-label').style.display = isWorkspace ? 'block' : 'none';
+    document.getElementById('workspace-items-group').style.display = isWorkspace ? 'flex' : 'none';
+    if (isWorkspace) {
+        ['binary-label', 'binary-group', 'params-label', 'params-group'].forEach(id => {
+            document.getElementById(id).style.display = 'none';
+        });
+    }
+
     // Update auto-close timeout visibility
     updateAutoCloseTimeoutVisibility();
 
@@ -634,8 +679,135 @@ function updateFieldsVisibility() {
 
 // No longer needed - removed updateAgentToolFields function
 
+// List the tags already in use below the tags field
+async function loadTagSuggestions() {
+    try {
+        const tags = await invoke('get_tags');
+        document.getElementById('app-tags-existing').textContent =
+            tags.length > 0 ? 'Comma separated. In use: ' + tags.map(tag => tag.name).join(', ') : 'Comma separated';
+    } catch (error) {
+        console.error('[AppForm] Failed to load tags:', error);
+    }
+}
+
+// Environment variables as "NAME=value" lines; a line with just a name removes the variable
+function formatEnv(env) {
+    return (env || []).map(v => v.value === null || v.value === undefined ? v.name : `${v.name}=${v.value}`).join('\n');
+}
+
+function parseEnv(text) {
+    return text.split('\n')
+        .map(line => line.trim())
+        .filter(line => line && !line.startsWith('#'))
+        .map(line => {
+            const eq = line.indexOf('=');
+            return eq === -1
+                ? { name: line, value: null }
+                : { name: line.slice(0, eq).trim(), value: line.slice(eq + 1) };
+        });
+}
+
+// Show the app's recent failed launches, with the output captured from crashes
+async function loadLaunchErrors(appId) {
+    try {
+        const errors = await invoke('get_launch_errors', { appId, limit: 10 });
+        const list = document.getElementById('launch-errors-list');
+        list.innerHTML = '';
+        errors.forEach(record => {
+            const entry = document.createElement('div');
+            const when = document.createElement('div');
+            when.style.cssText = 'font-size: 11px; color: #666;';
+            when.textContent = `${new Date(record.launched_at * 1000).toLocaleString()} · ${record.trigger.replaceAll('_', ' ')}`;
+            const message = document.createElement('pre');
+            message.style.cssText = 'margin: 0; font-size: 11px; white-space: pre-wrap; word-break: break-word;';
+            message.textContent = record.error;
+            entry.append(when, message);
+            list.appendChild(entry);
+        });
+        document.getElementById('launch-errors-label').style.display = errors.length > 0 ? 'block' : 'none';
+        document.getElementById('launch-errors-group').style.display = errors.length > 0 ? 'flex' : 'none';
+    } catch (error) {
+        console.error('[AppForm] Failed to load launch errors:', error);
+    }
+}
+
+// Apps a workspace can start; workspaces can't contain other workspaces
+async function loadWorkspaceApps() {
+    try {
+        workspaceApps = (await invoke('get_all_apps')).filter(app => app.app_type !== 'workspace');
+    } catch (error) {
+        console.error('[AppForm] Failed to load apps for the workspace:', error);
+    }
+}
+
+function addWorkspaceItemRow(item) {
+    const row = document.createElement('div');
+    row.className = 'workspace-item-row';
+    row.style.display = 'flex';
+    row.style.alignItems = 'center';
+    row.style.gap = '4px';
+    row.innerHTML = `
+        <select class="workspace-item-app" style="flex: 1; min-width: 120px;"></select>
+        <input type="number" class="workspace-item-delay" min="0" max="600" step="0.5" title="Seconds to wait before starting this app" style="width: 70px;">
+        <button type="button" class="btn btn-secondary workspace-item-layout" title="Forget the saved window position and size">Reset Layout</button>
+        <button type="button" class="btn btn-secondary workspace-item-up" title="Start earlier">↑</button>
+        <button type="button" class="btn btn-secondary workspace-item-down" title="Start later">↓</button>
+        <button type="button" class="btn btn-secondary workspace-item-remove">Remove</button>
+    `;
+
+    const select = row.querySelector('.workspace-item-app');
+    workspaceApps.forEach(app => {
+        const option = document.createElement('option');
+        option.value = app.id;
+        option.textContent = app.name;
+        select.appendChild(option);
+    });
+    if (item.app_id) {
+        select.value = item.app_id;
+    }
+    row.querySelector('.workspace-item-delay').value = (item.delay_ms || 0) / 1000;
+
+    // The saved layout belongs to the app it was saved for
+    const layoutButton = row.querySelector('.workspace-item-layout');
+    const setGeometry = geometry => {
+        row.dataset.geometry = geometry ? JSON.stringify(geometry) : '';
+        layoutButton.style.display = geometry ? 'inline-block' : 'none';
+    };
+    setGeometry(item.geometry);
+    layoutButton.addEventListener('click', () => setGeometry(null));
+    select.addEventListener('change', () => setGeometry(null));
+
+    row.querySelector('.workspace-item-up').addEventListener('click', () => {
+        if (row.previousElementSibling) {
+            row.parentNode.insertBefore(row, row.previousElementSibling);
+        }
+    });
+    row.querySelector('.workspace-item-down').addEventListener('click', () => {
+        if (row.nextElementSibling) {
+            row.parentNode.insertBefore(row.nextElementSibling, row);
+        }
+    });
+    row.querySelector('.workspace-item-remove').addEventListener('click', () => {
+        row.remove();
+        autoResizeWindow();
+    });
+
+    document.getElementById('workspace-items-list').appendChild(row);
+}
+
+// Read the workspace rows into the format expected by set_workspace_items
+function getWorkspaceItems() {
+    return Array.from(document.querySelectorAll('#workspace-items-list .workspace-item-row')).map(row => ({
+        app_id: parseInt(row.querySelector('.workspace-item-app').value, 10),
+        delay_ms: Math.round((parseFloat(row.querySelector('.workspace-item-delay').value) || 0) * 1000),
+        geometry: row.dataset.geometry ? JSON.parse(row.dataset.geometry) : null
+    }));
+}
+
 // Load app data (for edit mode)
 async function loadAppData() {
+    loadTagSuggestions();
+    await loadWorkspaceApps();
     try {
         // Get window label to determine if we're in edit mode
         const window = getCurrentWindow();
@@ -655,9 +827,14 @@ async function loadAppData() {
                 const seg = document.getElementById('type-segment');
                 if (seg) Array.from(seg.querySelectorAll('button')).forEach(b => b.disabled = true);
                 document.getElementById('app-name').value = appData.name || '';
+                document.getElementById('app-tags').value = (appData.tags || []).join(', ');
                 document.getElementById('app-url').value = appData.url || '';
                 document.getElementById('app-binary').value = appData.binary_path || '';
                 document.getElementById('app-params').value = appData.cli_params || '';
+                document.getElementById('single-instance').checked = appData.single_instance || false;
+                document.getElementById('app-working-dir').value = appData.working_dir || '';
+                document.getElementById('app-env').value = formatEnv(appData.env);
+                document.getElementById('app-env-file').value = appData.env_file || '';
 
                 // Set webapp-specific checkboxes and fields
                 if (appData.app_type === 'webapp') {
@@ -691,19 +868,42 @@ async function loadAppData() {
                     document.getElementById('hide-on-shortcut').checked = appData.hide_on_shortcut || false;
                 }
 
+                if (appData.app_type === 'workspace') {
+                    try {
+                        const items = await invoke('get_workspace_items', { workspaceId: appId });
+                        items.forEach(addWorkspaceItemRow);
+                    } catch (error) {
+                        console.error('Failed to load workspace apps:', error);
+                    }
+                }
+
                 // Load agent configuration if agent type
                 if (appData.app_type === 'agent') {
                     try {
                         const agentConfig = await invoke('get_agent_app', { appId: appId });
                         console.log('[AppForm] Loaded agent config:', agentConfig);
                         if (agentConfig) {
+                            agentConfigData = agentConfig;
                             console.log('[AppForm] Setting model to:', agentConfig.model);
                             document.getElementById('agent-model').value = agentConfig.model || '';
                             document.getElementById('agent-prompt').value = agentConfig.prompt || '';
                             document.getElementById('agent-input-command').value = agentConfig.command || '';
                             document.getElementById('agent-input-website-url').value = agentConfig.websiteUrl || '';
                             document.getElementById('agent-output-notification').checked = agentConfig.toolNotification || false;
+                            document.getElementById('agent-notification-target').value = agentConfig.notificationTarget || 'os';
+                            (agentConfig.notificationChannelIds || []).forEach(channelId => {
+                                const checkbox = document.querySelector(`#agent-notification-channels input[value="${channelId}"]`);
+                                if (checkbox) {
+                                    checkbox.checked = true;
+                                }
+                            });
                             document.getElementById('agent-output-run-command').checked = agentConfig.toolRunCommand || false;
+                            document.getElementById('agent-tool-memory').checked = agentConfig.toolMemory || false;
+                            document.getElementById('agent-memory-in-prompt').checked = agentConfig.memoryInPrompt || false;
+                            document.getElementById('agent-tool-search-documents').checked = agentConfig.toolSearchDocuments || false;
+                            document.getElementById('clear-agent-memory-btn').style.display = 'inline-block';
+                            (agentConfig.conditions || []).forEach(addConditionRow);
+                            document.getElementById('agent-conditions-default').value = agentConfig.conditionsDefault || 'run';
 
                             // Set scrape mode
                             const scrapeMode = agentConfig.websiteScrapeMode || 'text';
@@ -733,6 +933,7 @@ async function loadAppData() {
                 }
                 updateIconPreview();
                 updateFieldsVisibility();
+                loadLaunchErrors(appId);
             }
         }
     } catch (error) {
@@ -803,6 +1004,12 @@ async function saveApp() {
     const url = document.getElementById('app-url').value.trim();
     const binaryPath = document.getElementById('app-binary').value.trim();
     const cliParams = document.getElementById('app-params').value.trim();
+    const isCommand = appType === 'app' || appType === 'tui';
+    const workingDir = isCommand ? document.getElementById('app-working-dir').value.trim() : '';
+    const env = isCommand ? parseEnv(document.getElementById('app-env').value) : [];
+    const envFile = isCommand ? document.getElementById('app-env-file').value.trim() : '';
+    const singleInstance = appType === 'app' ? document.getElementById('single-instance').checked : null;
+    const tags = document.getElementById('app-tags').value.split(',').map(tag => tag.trim()).filter(tag => tag);
 
     // Get raw shortcut values (not the formatted display values)
     const shortcutInput = document.getElementById('app-shortcut');
@@ -827,6 +1034,11 @@ async function saveApp() {
             alert('Please enter a prompt for the agent');
             return;
         }
+    } else if (appType === 'workspace') {
+        if (getWorkspaceItems().length === 0) {
+            alert('Please add at least one app to the workspace');
+            return;
+        }
     } else if (appType !== 'webapp' && !binaryPath) {
         alert('Please enter a binary path');
         return;
@@ -944,33 +1156,25 @@ async function saveApp() {
                     enable_oauth: enableOauth,
                     auto_close_timeout: autoCloseTimeout,
                     always_on_top: alwaysOnTop,
-                    hide_on_shortcut: hideOnShortcut
+                    hide_on_shortcut: hideOnShortcut,
+                    single_instance: singleInstance,
+                    working_dir: workingDir || null,
+                    env: env,
+                    env_file: envFile || null
                 }
             });
             
-            // Update agent configuration if agent type
-            if (appType === 'agent') {
-                const model = document.getElementById('agent-model').value.trim() || null;
-                const prompt = document.getElementById('agent-prompt').value.trim();
-                const inputCommand = document.getElementById('agent-input-command').value.trim() || null;
-                const inputWebsiteUrl = document.getElementById('agent-input-website-url').value.trim() || null;
-                const toolNotification = document.getElementById('agent-output-notification').checked;
-                const toolRunCommand = document.getElementById('agent-output-run-command').checked;
+            await invoke('set_app_tags', { appId: appData.id, tags });
 
-                // Website scrape is enabled if URL is provided
-                const toolWebsiteScrape = inputWebsiteUrl !== null && inputWebsiteUrl !== '';
-                const scrapeModeValue = getScrapeMode();
+            if (appType === 'workspace') {
+                await invoke('set_workspace_items', { workspaceId: appData.id, items: getWorkspaceItems() });
+            }
 
+            // Update agent configuration if agent type
+            if (appType === 'agent') {
                 const agentAppData = {
-                    appId: appData.id,
-                    model: model,
-                    prompt: prompt,
-                    toolNotification: toolNotification,
-                    toolWebsiteScrape: toolWebsiteScrape,
-                    toolRunCommand: toolRunCommand,
-                    websiteUrl: inputWebsiteUrl,
-                    websiteScrapeMode: scrapeModeValue,
-                    command: inputCommand
+                    ...(agentConfigData || {}),
+                    ...getAgentFormData(appData.id)
                 };
                 console.log('[AppForm] Saving agent config:', agentAppData);
 
@@ -1015,37 +1219,28 @@ async function saveApp() {
                     enable_oauth: enableOauth,
                     auto_close_timeout: autoCloseTimeout,
                     always_on_top: alwaysOnTop,
-                    hide_on_shortcut: hideOnShortcut
+                    hide_on_shortcut: hideOnShortcut,
+                    single_instance: singleInstance,
+                    working_dir: workingDir || null,
+                    env: env,
+                    env_file: envFile || null
                 }
             });
             
+            if (tags.length > 0) {
+                await invoke('set_app_tags', { appId, tags });
+            }
+
             // Save agent configuration if agent type
             if (appType === 'agent') {
-                const model = document.getElementById('agent-model').value.trim() || null;
-                const prompt = document.getElementById('agent-prompt').value.trim();
-                const inputCommand = document.getElementById('agent-input-command').value.trim() || null;
-                const inputWebsiteUrl = document.getElementById('agent-input-website-url').value.trim() || null;
-                const toolNotification = document.getElementById('agent-output-notification').checked;
-                const toolRunCommand = document.getElementById('agent-output-run-command').checked;
-
-                // Website scrape is enabled if URL is provided
-                const toolWebsiteScrape = inputWebsiteUrl !== null && inputWebsiteUrl !== '';
-                const scrapeModeValue = getScrapeMode();
-
                 await invoke('save_agent_app', {
-                    agentApp: {
-                        appId: appId,
-                        model: model,
-                        prompt: prompt,
-                        toolNotification: toolNotification,
-                        toolWebsiteScrape: toolWebsiteScrape,
-                        toolRunCommand: toolRunCommand,
-                        websiteUrl: inputWebsiteUrl,
-                        websiteScrapeMode: scrapeModeValue,
-                        command: inputCommand
-                    }
+                    agentApp: getAgentFormData(appId)
                 });
             }
+
+            if (appType === 'workspace') {
+                await invoke('set_workspace_items', { workspaceId: appId, items: getWorkspaceItems() });
+            }
         }
 
         // Close the window
@@ -1057,6 +1252,66 @@ async function saveApp() {
     }
 }
 
+// Read the agent fields of the form into the format expected by save_agent_app
+function getAgentFormData(appId) {
+    const inputWebsiteUrl = document.getElementById('agent-input-website-url').value.trim() || null;
+
+    return {
+        appId: appId,
+        model: document.getElementById('agent-model').value.trim() || null,
+        prompt: document.getElementById('agent-prompt').value.trim(),
+        toolNotification: document.getElementById('agent-output-notification').checked,
+        // Website scrape is enabled if URL is provided
+        toolWebsiteScrape: inputWebsiteUrl !== null && inputWebsiteUrl !== '',
+        toolRunCommand: document.getElementById('agent-output-run-command').checked,
+        websiteUrl: inputWebsiteUrl,
+        websiteScrapeMode: getScrapeMode(),
+        command: document.getElementById('agent-input-command').value.trim() || null,
+        toolMemory: document.getElementById('agent-tool-memory').checked,
+        memoryInPrompt: document.getElementById('agent-memory-in-prompt').checked,
+        toolSearchDocuments: document.getElementById('agent-tool-search-documents').checked,
+        notificationTarget: document.getElementById('agent-notification-target').value,
+        notificationChannelIds: getSelectedNotificationChannels(),
+        conditions: getAgentConditions(),
+        conditionsDefault: document.getElementById('agent-conditions-default').value
+    };
+}
+
+// Run the agent as configured in the form without executing state-changing tools
+async function dryRunAgent() {
+    const button = document.getElementById('dry-run-agent-btn');
+    const output = document.getElementById('agent-dry-run-output');
+    const agentName = document.getElementById('app-name').value.trim() || null;
+
+    button.disabled = true;
+    output.style.display = 'block';
+    output.textContent = 'Running…';
+
+    try {
+        const result = await invoke('dry_run_agent', {
+            agentApp: getAgentFormData(appData ? appData.id : 0),
+            agentName: agentName
+        });
+
+        const lines = [];
+        if (result.decision) {
+            lines.push(`Conditions: ${result.decision}`);
+        }
+        lines.push(`Model: ${result.model || 'not called'}`);
+        result.tool_calls.forEach(call => {
+            const state = call.executed ? 'executed' : 'recorded only';
+            lines.push(`Tool ${call.name} (${state}): ${JSON.stringify(call.arguments)}`);
+        });
+        lines.push('', result.output);
+        output.textContent = lines.join('\n');
+    } catch (error) {
+        console.error('Dry run failed:', error);
+        output.textContent = 'Dry run failed: ' + error;
+    } finally {
+        button.disabled = false;
+    }
+}
+
 // Load models for agent model dropdown
 async function loadAgentModels() {
     try {
@@ -1077,6 +1332,132 @@ async function loadAgentModels() {
     }
 }
 
+// Load outbound notification channels as checkboxes for the agent notification output
+async function loadAgentNotificationChannels() {
+    try {
+        const channels = await invoke('get_notification_channels');
+        const container = document.getElementById('agent-notification-channels');
+        container.innerHTML = '';
+
+        (channels || []).forEach(channel => {
+            const label = document.createElement('label');
+            label.style.display = 'flex';
+            label.style.alignItems = 'center';
+            label.style.gap = '6px';
+            label.style.cursor = 'pointer';
+
+            const checkbox = document.createElement('input');
+            checkbox.type = 'checkbox';
+            checkbox.value = channel.id;
+            checkbox.style.width = 'auto';
+            checkbox.style.height = 'auto';
+            checkbox.style.margin = '0';
+
+            const text = document.createElement('span');
+            text.style.fontSize = '13px';
+            text.textContent = `Also send to ${channel.name} (${channel.kind})`;
+
+            label.appendChild(checkbox);
+            label.appendChild(text);
+            container.appendChild(label);
+        });
+    } catch (error) {
+        console.error('[AppForm] Failed to load notification channels:', error);
+    }
+}
+
+// Get the ids of the checked notification channels
+function getSelectedNotificationChannels() {
+    return Array.from(document.querySelectorAll('#agent-notification-channels input:checked'))
+        .map(checkbox => parseInt(checkbox.value));
+}
+
+// Add a row to the agent conditions editor
+function addConditionRow(condition) {
+    const row = document.createElement('div');
+    row.className = 'agent-condition-row';
+    row.style.display = 'flex';
+    row.style.flexWrap = 'wrap';
+    row.style.alignItems = 'center';
+    row.style.gap = '4px';
+    row.innerHTML = `
+        <select class="condition-source" style="width: auto;">
+            <option value="website">Website</option>
+            <option value="command">Command output</option>
+        </select>
+        <select class="condition-check" style="width: auto;">
+            <option value="matches">matches</option>
+            <option value="notMatches">does not match</option>
+            <option value="number">number</option>
+            <option value="exitCode">exit code</option>
+        </select>
+        <input type="text" class="condition-pattern" placeholder="Regex, e.g. In stock" style="flex: 1; min-width: 120px;">
+        <select class="condition-op" style="width: auto;">
+            <option value="lt">&lt;</option>
+            <option value="le">&le;</option>
+            <option value="eq">=</option>
+            <option value="ne">&ne;</option>
+            <option value="ge">&ge;</option>
+            <option value="gt">&gt;</option>
+        </select>
+        <input type="number" class="condition-value" step="any" style="width: 80px;">
+        <select class="condition-action" style="width: auto;">
+            <option value="notify">Notify</option>
+            <option value="skip">Skip run</option>
+            <option value="run">Run model</option>
+        </select>
+        <input type="text" class="condition-message" placeholder="Notification, {{match}} inserts the match" style="flex-basis: 100%;">
+        <button type="button" class="btn btn-secondary condition-remove">Remove</button>
+    `;
+
+    row.querySelector('.condition-source').value = condition.source || 'website';
+    row.querySelector('.condition-check').value = condition.check || 'matches';
+    row.querySelector('.condition-pattern').value = condition.pattern || '';
+    row.querySelector('.condition-op').value = condition.op || 'lt';
+    row.querySelector('.condition-value').value = condition.value ?? '';
+    row.querySelector('.condition-action').value = condition.action || 'notify';
+    row.querySelector('.condition-message').value = condition.message || '';
+
+    // Only show the fields the selected check and action use
+    const updateFields = () => {
+        const check = row.querySelector('.condition-check').value;
+        row.querySelector('.condition-pattern').style.display = check === 'exitCode' ? 'none' : '';
+        row.querySelector('.condition-op').style.display = check === 'number' || check === 'exitCode' ? '' : 'none';
+        row.querySelector('.condition-value').style.display = check === 'number' || check === 'exitCode' ? '' : 'none';
+        row.querySelector('.condition-message').style.display = row.querySelector('.condition-action').value === 'notify' ? '' : 'none';
+    };
+    row.querySelector('.condition-check').addEventListener('change', updateFields);
+    row.querySelector('.condition-action').addEventListener('change', updateFields);
+    row.querySelector('.condition-remove').addEventListener('click', () => row.remove());
+    updateFields();
+
+    document.getElementById('agent-conditions-list').appendChild(row);
+}
+
+// Read the agent conditions editor into the format expected by save_agent_app
+function getAgentConditions() {
+    return Array.from(document.querySelectorAll('#agent-conditions-list .agent-condition-row')).map(row => {
+        const check = row.querySelector('.condition-check').value;
+        const condition = {
+            source: check === 'exitCode' ? 'command' : row.querySelector('.condition-source').value,
+            check: check,
+            action: row.querySelector('.condition-action').value,
+            message: row.querySelector('.condition-message').value.trim() || null
+        };
+
+        if (check !== 'exitCode') {
+            condition.pattern = row.querySelector('.condition-pattern').value;
+        }
+        if (check === 'number' || check === 'exitCode') {
+            condition.op = row.querySelector('.condition-op').value;
+            const value = parseFloat(row.querySelector('.condition-value').value) || 0;
+            condition.value = check === 'exitCode' ? Math.trunc(value) : value;
+        }
+
+        return condition;
+    });
+}
+
 // Initialize
 async function init() {
     console.log('[AppForm] Initializing...');
@@ -1102,6 +1483,7 @@ async function init() {
         // Load models for agent model dropdown BEFORE loading app data
         // This ensures the dropdown is populated before we try to set the selected value
         await loadAgentModels();
+        await loadAgentNotificationChannels();
 
         // Load app data
         await loadAppData();
@@ -1125,6 +1507,28 @@ async function init() {
         updateFieldsVisibility();
 
         // Browse binary button
+        document.getElementById('browse-working-dir-btn').addEventListener('click', async () => {
+            try {
+                const selected = await openDialog({ multiple: false, directory: true });
+                if (selected) {
+                    document.getElementById('app-working-dir').value = selected;
+                }
+            } catch (error) {
+                console.error('[AppForm] Failed to open folder dialog:', error);
+            }
+        });
+
+        document.getElementById('browse-env-file-btn').addEventListener('click', async () => {
+            try {
+                const selected = await openDialog({ multiple: false, directory: false });
+                if (selected) {
+                    document.getElementById('app-env-file').value = selected;
+                }
+            } catch (error) {
+                console.error('[AppForm] Failed to open file dialog:', error);
+            }
+        });
+
         document.getElementById('browse-binary-btn').addEventListener('click', async () => {
             try {
                 const selected = await openDialog({
@@ -1307,6 +1711,33 @@ async function init() {
             setScrapeMode('visual');
         });
 
+        // Add agent condition button
+        document.getElementById('add-agent-condition-btn').addEventListener('click', () => {
+            addConditionRow({ source: 'website', check: 'matches', action: 'notify' });
+        });
+
+        // Add workspace app button
+        document.getElementById('add-workspace-item-btn').addEventListener('click', () => {
+            addWorkspaceItemRow({});
+            autoResizeWindow();
+        });
+
+        // Dry run button
+        document.getElementById('dry-run-agent-btn').addEventListener('click', dryRunAgent);
+
+        // Clear agent memory button (edit mode only)
+        document.getElementById('clear-agent-memory-btn').addEventListener('click', async () => {
+            if (!appData || !confirm('Clear all memory stored by this agent?')) {
+                return;
+            }
+            try {
+                await invoke('clear_agent_memory', { appId: appData.id });
+            } catch (error) {
+                console.error('Failed to clear agent memory:', error);
+                alert('Failed to clear agent memory: ' + error);
+            }
+        });
+
         // Save button
         document.getElementById('save-app-btn').addEventListener('click', saveApp);
 
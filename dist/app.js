@@ -20,6 +20,14 @@ const openDialog = async (options = {}) => {
     return await tauri.dialog.open(options);
 };
 
+const saveDialog = async (options = {}) => {
+    const tauri = getTauriAPI();
+    if (!tauri) {
+        throw new Error('Tauri API not available');
+    }
+    return await tauri.dialog.save(options);
+};
+
 const convertFileSrc = (filePath) => {
     const tauri = getTauriAPI();
     if (!tauri || !tauri.core.convertFileSrc) {
@@ -97,18 +105,67 @@ let draggedIndex = null; // Track which item is being dragged
 let draggedElement = null; // Track the dragged DOM element
 let dragOverElement = null; // Track the element currently being dragged over
 let dragGhost = null; // Visual drag ghost element
-let activeTab = 'all'; // Current active tab: 'all' or 'agents'
+let activeTab = 'all'; // Current active tab: 'all' (the pages), 'agents' or 'filter'
+let searchResults = null; // Results of the current search, or null when not searching
+let pages = []; // Pages of the grid, in order
+let groups = []; // Folders on all pages
+let activePage = null; // Id of the page shown
+let openGroup = null; // Id of the open folder, or null for the top level of the page
+let savedFilters = []; // Saved filters; pinned ones are shown as tabs
+let activeFilter = null; // Id of the filter shown when activeTab is 'filter'
+let filterAppIds = []; // Apps matching the active filter, in grid order
+let runningApps = new Set(); // Ids of native apps with a running process
+
+// Apps and folders directly in a page or folder, in the order the backend stores them
+function containerItems(pageId, groupId) {
+    const folders = groups
+        .filter(g => g.page_id === pageId && (g.parent_id ?? null) === groupId)
+        .map(g => ({ ...g, isFolder: true }));
+    const members = apps.filter(a => a.page_id === pageId && (a.group_id ?? null) === groupId);
+    return [...members, ...folders].sort((a, b) =>
+        a.position - b.position || Number(!!a.isFolder) - Number(!!b.isFolder) || a.id - b.id
+    );
+}
 
-// Get filtered apps based on current tab
+// Get the items of the current page or folder, the agents tab, or the search results while searching
 function getFilteredApps() {
+    if (searchResults !== null) {
+        return searchResults.map(result => {
+            const app = result.app_id !== null ? apps.find(a => a.id === result.app_id) : null;
+            return app ? { ...app, search: result } : { id: null, name: result.name, action: result.action, search: result };
+        });
+    }
+    if (settings.separate_agent_apps && activeTab === 'agents') {
+        return apps.filter(app => app.app_type === 'agent');
+    }
+    if (activeTab === 'filter') {
+        return filterAppIds.map(id => apps.find(app => app.id === id)).filter(app => app);
+    }
+
+    let items = containerItems(activePage, openGroup);
     if (settings.separate_agent_apps) {
-        if (activeTab === 'agents') {
-            return apps.filter(app => app.app_type === 'agent');
-        } else {
-            return apps.filter(app => app.app_type !== 'agent');
-        }
+        items = items.filter(item => item.app_type !== 'agent');
+    }
+    // Inside a folder, the first tile leads back out
+    if (openGroup !== null) {
+        const folder = groups.find(g => g.id === openGroup);
+        items.unshift({ id: null, isBack: true, name: folder ? folder.name : 'Back' });
     }
-    return apps;
+    return items;
+}
+
+// Grid size of the page shown, which may override the settings
+function gridSize() {
+    const page = activeTab === 'all' ? pages.find(p => p.id === activePage) : null;
+    return {
+        cols: (page && page.grid_cols) || settings.grid_cols,
+        rows: (page && page.grid_rows) || settings.grid_rows
+    };
+}
+
+// The tab bar shows pages, pinned filters and, when enabled, the agents tab
+function tabsVisible() {
+    return pages.length > 1 || !!settings.separate_agent_apps || savedFilters.some(f => f.pinned);
 }
 
 // Shortcut recording state
@@ -317,11 +374,30 @@ async function init() {
                 applyTheme();
             });
 
+            // Listen for settings saved from the settings window or a configuration file
+            tauri.event.listen('settings-changed', async () => {
+                console.log('Settings changed, reloading settings...');
+                await loadSettings();
+                renderApps();
+                applyTheme();
+            });
+
             // Listen for app updates (create, update, delete)
             tauri.event.listen('app-updated', async () => {
                 console.log('App updated, reloading apps...');
                 await loadApps();
             });
+
+            // Show which native apps are running
+            tauri.event.listen('app-running-changed', (event) => {
+                const { app_id, running } = event.payload;
+                if (running) {
+                    runningApps.add(app_id);
+                } else {
+                    runningApps.delete(app_id);
+                }
+                renderApps();
+            });
         }
 
         console.log('App initialized successfully');
@@ -349,14 +425,10 @@ async function loadSettings() {
         } else {
             document.documentElement.classList.remove('hide-app-names');
         }
-        // Show/hide tabs based on separate_agent_apps setting
-        const tabsElement = document.getElementById('launcher-tabs');
-        if (settings.separate_agent_apps) {
-            tabsElement.style.display = 'flex';
-        } else {
-            tabsElement.style.display = 'none';
-            activeTab = 'all'; // Reset to all apps when tabs are hidden
+        if (!settings.separate_agent_apps && activeTab === 'agents') {
+            activeTab = 'all'; // Reset to the pages when the agents tab is hidden
         }
+        renderTabs();
         // Resize window to match grid dimensions
         await resizeWindow();
     } catch (error) {
@@ -370,23 +442,128 @@ async function resizeWindow() {
         await invoke('resize_main_window', {
             gridCols: settings.grid_cols,
             gridRows: settings.grid_rows,
-            tabsVisible: settings.separate_agent_apps || false
+            tabsVisible: tabsVisible(),
+            pageId: activeTab === 'all' ? activePage : null
         });
     } catch (error) {
         console.error('Failed to resize window:', error);
     }
 }
 
-// Load apps from backend
+// Load apps, pages and folders from backend
 async function loadApps() {
     try {
-        apps = await invoke('get_all_apps');
+        const hadTabs = tabsVisible();
+        let running;
+        [apps, pages, groups, savedFilters, running] = await Promise.all([
+            invoke('get_all_apps'),
+            invoke('get_pages'),
+            invoke('get_groups'),
+            invoke('get_saved_filters'),
+            invoke('get_running_apps')
+        ]);
+        runningApps = new Set(running);
+        // Fall back to the first page or the top level when the shown page or folder is gone
+        if (!pages.some(p => p.id === activePage)) {
+            activePage = pages.length > 0 ? pages[0].id : null;
+            openGroup = null;
+        }
+        if (openGroup !== null && !groups.some(g => g.id === openGroup && g.page_id === activePage)) {
+            openGroup = null;
+        }
+        if (activeTab === 'filter') {
+            if (savedFilters.some(f => f.id === activeFilter && f.pinned)) {
+                filterAppIds = await invoke('get_filter_apps', { filterId: activeFilter });
+            } else {
+                activeTab = 'all';
+            }
+        }
+        renderTabs();
+        updateGridSize();
         renderApps();
+        if (hadTabs !== tabsVisible()) {
+            await resizeWindow();
+        }
     } catch (error) {
         console.error('Failed to load apps:', error);
     }
 }
 
+// Render the page tabs, the agents tab when agents are shown separately, and pinned filters
+function renderTabs() {
+    const tabsElement = document.getElementById('launcher-tabs');
+    tabsElement.innerHTML = '';
+    tabsElement.style.display = tabsVisible() ? 'flex' : 'none';
+
+    pages.forEach(page => {
+        const tab = document.createElement('button');
+        tab.className = 'launcher-tab';
+        tab.dataset.page = page.id;
+        tab.textContent = page.name;
+        if (activeTab === 'all' && page.id === activePage) {
+            tab.classList.add('active');
+        }
+        tab.addEventListener('click', () => switchPage(page.id));
+        tab.addEventListener('contextmenu', (e) => showPageMenu(e, page, tab));
+        tabsElement.appendChild(tab);
+    });
+
+    if (settings.separate_agent_apps) {
+        const tab = document.createElement('button');
+        tab.className = 'launcher-tab';
+        tab.textContent = 'Agent Apps';
+        if (activeTab === 'agents') {
+            tab.classList.add('active');
+        }
+        tab.addEventListener('click', () => switchPage(null));
+        tabsElement.appendChild(tab);
+    }
+
+    savedFilters.filter(filter => filter.pinned).forEach(filter => {
+        const tab = document.createElement('button');
+        tab.className = 'launcher-tab filter-tab';
+        tab.textContent = filter.name;
+        tab.title = 'Saved filter';
+        if (activeTab === 'filter' && filter.id === activeFilter) {
+            tab.classList.add('active');
+        }
+        tab.addEventListener('click', () => switchFilter(filter.id));
+        tabsElement.appendChild(tab);
+    });
+}
+
+// Show the apps matching a saved filter
+async function switchFilter(filterId) {
+    try {
+        filterAppIds = await invoke('get_filter_apps', { filterId });
+    } catch (error) {
+        console.error('Failed to apply filter:', error);
+        return;
+    }
+    activeTab = 'filter';
+    activeFilter = filterId;
+    openGroup = null;
+    selectedIndex = null;
+    renderTabs();
+    updateGridSize();
+    renderApps();
+    await resizeWindow();
+}
+
+// Show a page, or the agents tab when pageId is null
+async function switchPage(pageId) {
+    activeTab = pageId === null ? 'agents' : 'all';
+    if (pageId !== null) {
+        activePage = pageId;
+    }
+    openGroup = null;
+    selectedIndex = null;
+    renderTabs();
+    updateGridSize();
+    renderApps();
+    await resizeWindow();
+}
+
 // Render apps grid
 function renderApps() {
     const grid = document.getElementById('app-grid');
@@ -400,6 +577,14 @@ function renderApps() {
         item.className = 'icon-item';
         item.dataset.index = index;
         item.dataset.appId = app.id;
+        if (app.isFolder) {
+            item.classList.add('folder-item');
+        } else if (app.isBack) {
+            item.classList.add('back-item');
+        } else if (runningApps.has(app.id)) {
+            item.classList.add('running');
+            item.title = 'Running';
+        }
         // Don't use HTML5 draggable - we'll use custom mouse event drag instead
         item.draggable = false;
         
@@ -420,15 +605,25 @@ function renderApps() {
         } else {
             const placeholder = document.createElement('div');
             placeholder.className = 'icon-placeholder';
-            placeholder.textContent = app.name.charAt(0).toUpperCase();
+            if (app.isFolder) {
+                renderFolderPreview(placeholder, app);
+            } else if (app.isBack) {
+                placeholder.textContent = '‹';
+            } else {
+                placeholder.textContent = app.name.charAt(0).toUpperCase();
+            }
             placeholder.draggable = false; // Prevent placeholder from interfering with drag
             item.appendChild(placeholder);
         }
         
-        // Name
+        // Name, with the matched characters highlighted while searching
         const name = document.createElement('div');
         name.className = 'app-name';
-        name.textContent = app.name;
+        if (app.search && app.search.positions.length > 0) {
+            renderHighlightedName(name, app.name, app.search.positions);
+        } else {
+            name.textContent = app.name;
+        }
         name.draggable = false; // Prevent text from interfering with drag
         item.appendChild(name);
 
@@ -470,7 +665,7 @@ function renderApps() {
                 e.stopPropagation();
                 return;
             }
-            launchApp(app.id);
+            activateItem(app);
         });
         
         // Track if drag happened to prevent click
@@ -478,7 +673,18 @@ function renderApps() {
         item.addEventListener('mousedown', (e) => {
             wasDragging = false;
         });
-        item.addEventListener('contextmenu', (e) => showContextMenu(e, app));
+        item.addEventListener('contextmenu', (e) => {
+            e.stopPropagation();
+            if (app.action || app.isBack) {
+                e.preventDefault();
+                return;
+            }
+            if (app.isFolder) {
+                showFolderMenu(e, app, name);
+                return;
+            }
+            showContextMenu(e, app);
+        });
         
         // Custom drag implementation using mouse events (more reliable than HTML5 drag in Tauri)
         let dragStartX = 0;
@@ -486,8 +692,8 @@ function renderApps() {
         let isMouseDown = false;
         
         item.addEventListener('mousedown', (e) => {
-            // Only start drag on left mouse button
-            if (e.button !== 0) return;
+            // Only start drag on left mouse button, and not on search results or the back tile
+            if (e.button !== 0 || searchResults !== null || app.isBack) return;
             
             // Don't start drag if clicking on a button or interactive element
             if (e.target.closest('button') || e.target.closest('input')) return;
@@ -598,10 +804,29 @@ function renderApps() {
 // Update grid size
 function updateGridSize() {
     const grid = document.getElementById('app-grid');
-    grid.style.gridTemplateColumns = `repeat(${settings.grid_cols}, 1fr)`;
-    grid.style.gridTemplateRows = `repeat(${settings.grid_rows}, auto)`;
+    const size = gridSize();
+    grid.style.gridTemplateColumns = `repeat(${size.cols}, 1fr)`;
+    grid.style.gridTemplateRows = `repeat(${size.rows}, auto)`;
     // Resizing handled by backend upon startup and settings change
-    console.log(`Grid updated to ${settings.grid_cols} × ${settings.grid_rows}`);
+    console.log(`Grid updated to ${size.cols} × ${size.rows}`);
+}
+
+// Show up to four of a folder's apps as small tiles
+function renderFolderPreview(element, folder) {
+    element.classList.add('folder-preview');
+    containerItems(folder.page_id, folder.id).filter(item => !item.isFolder).slice(0, 4).forEach(app => {
+        if (app.icon_path) {
+            const img = document.createElement('img');
+            img.src = toAssetUrl(app.icon_path);
+            img.alt = '';
+            img.draggable = false;
+            element.appendChild(img);
+        } else {
+            const letter = document.createElement('span');
+            letter.textContent = app.name.charAt(0).toUpperCase();
+            element.appendChild(letter);
+        }
+    });
 }
 
 // Apply theme
@@ -656,32 +881,19 @@ function setupEventListeners() {
         console.error('Settings button not found!');
     }
 
-    // Tab click handlers
-    const tabButtons = document.querySelectorAll('.launcher-tab');
-    tabButtons.forEach(tab => {
-        tab.addEventListener('click', (e) => {
-            const tabName = tab.dataset.tab;
-            console.log('Tab clicked:', tabName);
-
-            // Update active tab
-            activeTab = tabName;
-
-            // Update tab UI
-            tabButtons.forEach(t => t.classList.remove('active'));
-            tab.classList.add('active');
-
-            // Reset selection when switching tabs
-            selectedIndex = null;
-
-            // Re-render apps with new filter
-            renderApps();
-        });
-    });
-
     // Keyboard navigation on grid
     const appGrid = document.getElementById('app-grid');
     if (appGrid) {
         appGrid.addEventListener('keydown', handleKeyDown);
+
+        // Right-click on empty grid space to add folders and pages
+        appGrid.addEventListener('contextmenu', (e) => {
+            if (e.target.closest('.icon-item') || searchResults !== null || activeTab !== 'all') return;
+            showMenu(e, [
+                { label: 'New Folder', action: createFolder },
+                { label: 'New Page', action: createPage }
+            ]);
+        });
         
         // Prevent default drag behavior on grid to allow drops
         // But don't stop propagation so items can also handle it
@@ -702,8 +914,9 @@ function setupEventListeners() {
                 e.preventDefault();
                 e.stopPropagation();
                 // If drop happens on grid but not on an item, drop at the end
-                if (draggedIndex !== null && draggedIndex < apps.length) {
-                    handleDrop(e, apps.length - 1);
+                const itemCount = getFilteredApps().length;
+                if (draggedIndex !== null && draggedIndex < itemCount) {
+                    handleDrop(e, itemCount - 1);
                 }
             }
             // Otherwise let the item handle it - don't prevent propagation
@@ -768,11 +981,18 @@ function setupEventListeners() {
             if (matchingApp) {
                 e.preventDefault();
                 console.log(`Launching app via shortcut: ${matchingApp.name} (${pressedShortcut})`);
-                launchApp(matchingApp.id);
+                launchApp(matchingApp.id, 'app_shortcut');
                 return;
             }
         }
 
+        // Typing a character that isn't an app shortcut starts a search
+        if (e.key.length === 1 && e.key !== ' ' && !e.ctrlKey && !e.metaKey && !e.altKey) {
+            e.preventDefault();
+            openSearch(e.key);
+            return;
+        }
+
         // Handle Escape key
         if (e.key === 'Escape') {
             console.log('Escape pressed, hiding window');
@@ -780,6 +1000,21 @@ function setupEventListeners() {
         }
     });
     console.log('Global keyboard listeners attached');
+
+    // Search input: arrows and Enter navigate the results, Escape closes the search
+    const searchInput = document.getElementById('search-input');
+    if (searchInput) {
+        searchInput.addEventListener('input', () => runSearch(searchInput.value));
+        searchInput.addEventListener('keydown', (e) => {
+            if (e.key === 'Escape') {
+                e.preventDefault();
+                e.stopPropagation();
+                closeSearch();
+            } else if (['ArrowUp', 'ArrowDown', 'Enter'].includes(e.key)) {
+                handleKeyDown(e);
+            }
+        });
+    }
     
     // Close context menu on click
     document.addEventListener('click', closeContextMenu);
@@ -788,9 +1023,9 @@ function setupEventListeners() {
 // Handle keyboard navigation
 function handleKeyDown(e) {
     const filteredApps = getFilteredApps();
-    if (filteredApps.length === 0) return;
+    if (filteredApps.length === 0 && e.key !== 'Escape') return;
 
-    const gridCols = settings.grid_cols;  // Use columns for horizontal navigation
+    const gridCols = gridSize().cols;  // Use columns for horizontal navigation
     const totalApps = filteredApps.length;
 
     switch (e.key) {
@@ -843,20 +1078,116 @@ function handleKeyDown(e) {
             }
             // Launch the selected app
             if (filteredApps[selectedIndex]) {
-                launchApp(filteredApps[selectedIndex].id);
+                activateItem(filteredApps[selectedIndex]);
             }
             break;
         case 'Escape':
             e.preventDefault();
-            hideWindow();
+            if (searchResults !== null) {
+                e.stopPropagation();
+                closeSearch();
+            } else if (openGroup !== null) {
+                e.stopPropagation();
+                closeFolder();
+            } else {
+                hideWindow();
+            }
+            break;
+        case 'Backspace':
+            if (openGroup !== null) {
+                e.preventDefault();
+                closeFolder();
+            }
             break;
     }
 }
 
-// Launch app
-async function launchApp(appId) {
+// Show the search bar, starting the query with the typed character
+function openSearch(initial) {
+    const bar = document.getElementById('search-bar');
+    const input = document.getElementById('search-input');
+    bar.style.display = 'flex';
+    input.value += initial;
+    input.focus();
+    runSearch(input.value);
+}
+
+// Hide the search bar and show the grid again
+function closeSearch() {
+    const bar = document.getElementById('search-bar');
+    const input = document.getElementById('search-input');
+    input.value = '';
+    bar.style.display = 'none';
+    searchResults = null;
+    selectedIndex = null;
+    renderApps();
+    focusGrid();
+}
+
+// Run a search and show the results in the grid, best match selected
+async function runSearch(query) {
+    if (query.trim() === '') {
+        closeSearch();
+        return;
+    }
+    try {
+        const results = await invoke('search', { query });
+        // Ignore results for a query that has since changed
+        if (document.getElementById('search-input').value !== query) return;
+        searchResults = results;
+        selectedIndex = results.length > 0 ? 0 : null;
+        renderApps();
+    } catch (error) {
+        console.error('Failed to search:', error);
+    }
+}
+
+// Fill an element with a name, marking the characters at the given positions
+function renderHighlightedName(element, text, positions) {
+    const matched = new Set(positions);
+    Array.from(text).forEach((ch, i) => {
+        if (matched.has(i)) {
+            const mark = document.createElement('mark');
+            mark.textContent = ch;
+            element.appendChild(mark);
+        } else {
+            element.appendChild(document.createTextNode(ch));
+        }
+    });
+}
+
+// Launch an app or run a built-in action from the grid or search results
+function activateItem(item) {
+    if (item.isFolder) {
+        openFolder(item.id);
+        return;
+    }
+    if (item.isBack) {
+        closeFolder();
+        return;
+    }
+    if (!item.action) {
+        launchApp(item.id);
+        if (searchResults !== null) closeSearch();
+        return;
+    }
+    const actions = {
+        settings: () => showSettingsModal(),
+        add_app: () => showAddModal(),
+        notifications: () => invoke('open_notifications_window'),
+        ai_queue: () => invoke('open_ai_queue_window'),
+        quit: () => invoke('quit_app'),
+    };
+    closeSearch();
+    Promise.resolve(actions[item.action]?.()).catch(error => {
+        console.error(`Failed to run action ${item.action}:`, error);
+    });
+}
+
+// Launch app; trigger is recorded in the launch history ('click' or 'app_shortcut')
+async function launchApp(appId, trigger = 'click') {
     try {
-        await invoke('launch', { appId });
+        await invoke('launch', { appId, trigger });
     } catch (error) {
         console.error('Failed to launch app:', error);
     }
@@ -873,6 +1204,94 @@ async function hideWindow() {
 
 // Show context menu
 function showContextMenu(e, app) {
+    const entries = [{ label: 'Edit', action: () => showEditModal(app) }];
+
+    if (app.app_type === 'agent') {
+        entries.push({ label: 'Export…', action: () => exportAgent(app) });
+    }
+
+    if (app.app_type === 'workspace') {
+        entries.push({ label: 'Save Layout', action: () => saveWorkspaceLayout(app) });
+        entries.push({ label: 'Close All', action: () => closeWorkspace(app) });
+    }
+
+    if (runningApps.has(app.id)) {
+        entries.push({ label: 'Quit', action: () => stopApp(app, false) });
+        entries.push({ label: 'Force Quit', danger: true, action: () => stopApp(app, true) });
+    }
+
+    if (activeTab === 'all') {
+        if (openGroup !== null) {
+            const folder = groups.find(g => g.id === openGroup);
+            entries.push({ label: 'Move Out of Folder', action: () => moveItem(app, activePage, folder ? folder.parent_id : null) });
+        }
+        pages.filter(page => page.id !== app.page_id).forEach(page => {
+            entries.push({ label: `Move to ${page.name}`, action: () => moveItem(app, page.id, null) });
+        });
+    }
+
+    entries.push({ label: 'Delete', danger: true, action: () => deleteApp(app.id) });
+    showMenu(e, entries);
+}
+
+// Quit a running app; the running indicator goes away once its process has exited
+async function stopApp(app, force) {
+    try {
+        await invoke('stop_app', { appId: app.id, force });
+    } catch (error) {
+        console.error('Failed to stop app:', error);
+        alert('Failed to quit ' + app.name + ': ' + error);
+    }
+}
+
+// Remember where the workspace's windows are, to restore them on its next launch
+async function saveWorkspaceLayout(app) {
+    try {
+        await invoke('save_workspace_layout', { workspaceId: app.id });
+    } catch (error) {
+        console.error('Failed to save workspace layout:', error);
+        alert('Failed to save the layout of ' + app.name + ': ' + error);
+    }
+}
+
+// Close every app of a workspace; the layout is saved first
+async function closeWorkspace(app) {
+    try {
+        await invoke('close_workspace', { workspaceId: app.id });
+    } catch (error) {
+        console.error('Failed to close workspace:', error);
+        alert('Failed to close ' + app.name + ': ' + error);
+    }
+}
+
+// Show the menu of a folder; nameElement is edited in place when renaming
+function showFolderMenu(e, folder, nameElement) {
+    const entries = [
+        { label: 'Open', action: () => openFolder(folder.id) },
+        { label: 'Rename', action: () => renameFolder(folder, nameElement) }
+    ];
+    pages.filter(page => page.id !== folder.page_id).forEach(page => {
+        entries.push({ label: `Move to ${page.name}`, action: () => moveItem(folder, page.id, null) });
+    });
+    entries.push({ label: 'Delete Folder', danger: true, action: () => deleteFolder(folder) });
+    showMenu(e, entries);
+}
+
+// Show the menu of a page tab
+function showPageMenu(e, page, tab) {
+    const entries = [
+        { label: 'Rename', action: () => renamePage(page, tab) },
+        { label: 'Grid Size…', action: () => editPageGrid(page, tab) },
+        { label: 'New Page', action: createPage }
+    ];
+    if (pages.length > 1) {
+        entries.push({ label: 'Delete Page', danger: true, action: () => deletePage(page) });
+    }
+    showMenu(e, entries);
+}
+
+// Show a menu of { label, action, danger } entries at the mouse position
+function showMenu(e, entries) {
     e.preventDefault();
     closeContextMenu();
 
@@ -880,24 +1299,19 @@ function showContextMenu(e, app) {
     menu.className = 'context-menu';
     menu.id = 'context-menu';
 
-    const editBtn = document.createElement('button');
-    editBtn.textContent = 'Edit';
-    editBtn.addEventListener('click', () => {
-        showEditModal(app);
-        closeContextMenu();
-    });
-
-    const deleteBtn = document.createElement('button');
-    deleteBtn.className = 'danger';
-    deleteBtn.textContent = 'Delete';
-    deleteBtn.addEventListener('click', () => {
-        deleteApp(app.id);
-        closeContextMenu();
+    entries.forEach(entry => {
+        const button = document.createElement('button');
+        button.textContent = entry.label;
+        if (entry.danger) {
+            button.className = 'danger';
+        }
+        button.addEventListener('click', () => {
+            entry.action();
+            closeContextMenu();
+        });
+        menu.appendChild(button);
     });
 
-    menu.appendChild(editBtn);
-    menu.appendChild(deleteBtn);
-
     // Append to DOM first to measure dimensions
     menu.style.visibility = 'hidden';
     document.body.appendChild(menu);
@@ -949,6 +1363,211 @@ function closeContextMenu() {
     }
 }
 
+// Open a folder on the current page
+function openFolder(groupId) {
+    openGroup = groupId;
+    selectedIndex = null;
+    renderApps();
+    focusGrid();
+}
+
+// Go up to the folder or page containing the open folder
+function closeFolder() {
+    const folder = groups.find(g => g.id === openGroup);
+    openGroup = folder ? (folder.parent_id ?? null) : null;
+    selectedIndex = null;
+    renderApps();
+    focusGrid();
+}
+
+// Replace an element's text with an input; resolves to the entered text, or null when cancelled
+function editTextInline(element, value, placeholder = '') {
+    return new Promise(resolve => {
+        const original = element.textContent;
+        const input = document.createElement('input');
+        input.type = 'text';
+        input.className = 'inline-edit';
+        input.value = value;
+        input.placeholder = placeholder;
+        element.textContent = '';
+        element.appendChild(input);
+        input.focus();
+        input.select();
+
+        let done = false;
+        const finish = (result) => {
+            if (done) return;
+            done = true;
+            element.textContent = original;
+            focusGrid();
+            resolve(result);
+        };
+        input.addEventListener('keydown', (e) => {
+            e.stopPropagation();
+            if (e.key === 'Enter') {
+                e.preventDefault();
+                finish(input.value.trim());
+            } else if (e.key === 'Escape') {
+                e.preventDefault();
+                finish(null);
+            }
+        });
+        input.addEventListener('click', (e) => e.stopPropagation());
+        input.addEventListener('blur', () => finish(input.value.trim()));
+    });
+}
+
+// Create a folder in the page or folder shown and let the user name it
+async function createFolder() {
+    try {
+        await invoke('create_group', { name: 'New Folder', pageId: activePage, parentId: openGroup });
+        await loadApps();
+        const items = getFilteredApps();
+        const index = items.findIndex(item => item.isFolder && item.id === Math.max(...groups.map(g => g.id)));
+        const nameElement = document.querySelector(`.icon-item[data-index="${index}"] .app-name`);
+        if (nameElement) {
+            await renameFolder(items[index], nameElement);
+        }
+    } catch (error) {
+        console.error('Failed to create folder:', error);
+        alert('Failed to create folder: ' + error);
+    }
+}
+
+// Rename a folder in place
+async function renameFolder(folder, nameElement) {
+    const name = await editTextInline(nameElement, folder.name);
+    if (!name || name === folder.name) return;
+    try {
+        await invoke('rename_group', { groupId: folder.id, name });
+        await loadApps();
+    } catch (error) {
+        console.error('Failed to rename folder:', error);
+        alert('Failed to rename folder: ' + error);
+    }
+}
+
+// Delete a folder; its apps take its place
+async function deleteFolder(folder) {
+    if (!confirm(`Delete the folder "${folder.name}"? The apps in it are kept.`)) return;
+    try {
+        await invoke('delete_group', { groupId: folder.id });
+        await loadApps();
+    } catch (error) {
+        console.error('Failed to delete folder:', error);
+        alert('Failed to delete folder: ' + error);
+    }
+}
+
+// Add a page, switch to it and let the user name it
+async function createPage() {
+    try {
+        const pageId = await invoke('create_page', { name: `Page ${pages.length + 1}` });
+        await loadApps();
+        await switchPage(pageId);
+        const tab = document.querySelector(`.launcher-tab[data-page="${pageId}"]`);
+        const page = pages.find(p => p.id === pageId);
+        if (tab && page) {
+            await renamePage(page, tab);
+        }
+    } catch (error) {
+        console.error('Failed to create page:', error);
+        alert('Failed to create page: ' + error);
+    }
+}
+
+// Rename a page in place
+async function renamePage(page, tab) {
+    const name = await editTextInline(tab, page.name);
+    if (!name || name === page.name) return;
+    try {
+        await invoke('update_page', { pageId: page.id, name, gridCols: page.grid_cols, gridRows: page.grid_rows });
+        await loadApps();
+    } catch (error) {
+        console.error('Failed to rename page:', error);
+        alert('Failed to rename page: ' + error);
+    }
+}
+
+// Set a page's own grid size as "columns x rows"; empty uses the grid size from Settings
+async function editPageGrid(page, tab) {
+    const current = page.grid_cols && page.grid_rows ? `${page.grid_cols}x${page.grid_rows}` : '';
+    const value = await editTextInline(tab, current, `${settings.grid_cols}x${settings.grid_rows}`);
+    if (value === null || value === current) return;
+
+    let gridCols = null;
+    let gridRows = null;
+    if (value !== '') {
+        const match = value.match(/^(\d+)\s*[x×]\s*(\d+)$/);
+        if (!match) {
+            alert('Enter the grid size as columns x rows, for example 5x4');
+            return;
+        }
+        gridCols = parseInt(match[1]);
+        gridRows = parseInt(match[2]);
+    }
+
+    try {
+        await invoke('update_page', { pageId: page.id, name: page.name, gridCols, gridRows });
+        await loadApps();
+        updateGridSize();
+        await resizeWindow();
+    } catch (error) {
+        console.error('Failed to change page grid size:', error);
+        alert('Failed to change page grid size: ' + error);
+    }
+}
+
+// Delete a page; its apps move to the first remaining page
+async function deletePage(page) {
+    if (!confirm(`Delete the page "${page.name}"? Its apps and folders move to the first page.`)) return;
+    try {
+        await invoke('delete_page', { pageId: page.id });
+        await loadApps();
+        await resizeWindow();
+    } catch (error) {
+        console.error('Failed to delete page:', error);
+        alert('Failed to delete page: ' + error);
+    }
+}
+
+// Move an app or folder to the end of a page or folder
+async function moveItem(item, pageId, groupId, index = null) {
+    const target = index ?? containerItems(pageId, groupId).length;
+    try {
+        if (item.isFolder) {
+            await invoke('move_group', { groupId: item.id, pageId, parentId: groupId, index: target });
+        } else {
+            await invoke('move_app', { appId: item.id, pageId, groupId, index: target });
+        }
+    } catch (error) {
+        console.error('Failed to move item:', error);
+        alert('Failed to move: ' + error);
+    }
+    await loadApps();
+}
+
+// Export an agent to a JSON or TOML file
+async function exportAgent(app) {
+    try {
+        const path = await saveDialog({
+            defaultPath: `${app.name}.json`,
+            filters: [
+                { name: 'JSON', extensions: ['json'] },
+                { name: 'TOML', extensions: ['toml'] }
+            ]
+        });
+        if (!path) {
+            return;
+        }
+
+        await invoke('export_agent', { appId: app.id, path });
+    } catch (error) {
+        console.error('Failed to export agent:', error);
+        alert('Failed to export agent: ' + error);
+    }
+}
+
 // Delete app
 async function deleteApp(appId) {
     if (!confirm('Are you sure you want to delete this app?')) return;
@@ -1068,11 +1687,20 @@ function setupCustomDragTracking(startEvent) {
             dragGhost.style.display = '';
         }
         
+        const tabBelow = elementBelow ? elementBelow.closest('.launcher-tab[data-page]') : null;
+        
         if (itemBelow && itemBelow !== draggedElement && draggedIndex !== null) {
             const targetIndex = parseInt(itemBelow.dataset.index);
             if (targetIndex !== draggedIndex) {
                 handleDrop(null, targetIndex);
             }
+        } else if (tabBelow && draggedIndex !== null) {
+            // Dropping on a page tab moves the item to the top level of that page
+            const item = getFilteredApps()[draggedIndex];
+            const pageId = parseInt(tabBelow.dataset.page);
+            if (item && pageId !== activePage) {
+                moveItem(item, pageId, null);
+            }
         }
         
         cleanupCustomDragTracking(e);
@@ -1109,6 +1737,9 @@ function cleanupCustomDragTracking(e) {
 }
 
 // Handle drag and drop
+//
+// Dropping on a folder moves the item into it, dropping on the back tile moves it out of the
+// open folder, and dropping on another item moves it to that item's place.
 async function handleDrop(e, targetIndex) {
     if (e) {
         e.preventDefault();
@@ -1121,26 +1752,45 @@ async function handleDrop(e, targetIndex) {
         return;
     }
     
-    if (sourceIndex >= apps.length || targetIndex >= apps.length) {
+    const items = getFilteredApps();
+    if (sourceIndex >= items.length || targetIndex >= items.length) {
         return;
     }
     
-    // Reorder apps array locally first for immediate visual feedback
-    const [movedApp] = apps.splice(sourceIndex, 1);
-    apps.splice(targetIndex, 0, movedApp);
-    
-    // Update visual immediately
-    renderApps();
-    
-    // Update positions in backend
-    try {
-        const appIds = apps.map(app => app.id);
-        await invoke('reorder_apps', { appIds: appIds });
-    } catch (error) {
-        console.error('Failed to reorder apps:', error);
-        // Reload on error to restore correct state
-        await loadApps();
+    const source = items[sourceIndex];
+    const target = items[targetIndex];
+    if (searchResults !== null || source.isBack || activeTab !== 'all') {
+        return;
     }
+
+    if (target.isFolder) {
+        await moveItem(source, activePage, target.id);
+        return;
+    }
+    if (target.isBack) {
+        const folder = groups.find(g => g.id === openGroup);
+        await moveItem(source, activePage, folder ? (folder.parent_id ?? null) : null);
+        return;
+    }
+
+    // Positions are shared with items hidden from this view, so use the target's place among all of them
+    const container = containerItems(activePage, openGroup);
+    const index = container.findIndex(item => !!item.isFolder === !!target.isFolder && item.id === target.id);
+    if (index < 0) {
+        return;
+    }
+
+    // Reorder locally first for immediate visual feedback
+    const without = container.filter(item => !(!!item.isFolder === !!source.isFolder && item.id === source.id));
+    without.splice(index, 0, source);
+    without.forEach((item, position) => {
+        const list = item.isFolder ? groups : apps;
+        const stored = list.find(x => x.id === item.id);
+        if (stored) stored.position = position;
+    });
+    renderApps();
+
+    await moveItem(source, activePage, openGroup, index);
 }
 
 // Show add modal
@@ -76,38 +76,134 @@ function formatTimestamp(timestamp) {
     return date.toLocaleString();
 }
 
-// Load notifications
+// Notifications expanded by the user, kept across refreshes
+const expandedIds = new Set();
+
+// Load the source filter options
+async function loadSources() {
+    try {
+        const sources = await invoke('get_notification_sources');
+        const select = document.getElementById('source-filter');
+        const selected = select.value;
+
+        select.innerHTML = '<option value="">All sources</option>';
+        sources.filter(source => source.app_id !== null).forEach(source => {
+            const option = document.createElement('option');
+            option.value = source.app_id;
+            option.textContent = `${source.name} (${source.count})`;
+            select.appendChild(option);
+        });
+
+        // Keep the current selection if the source still has notifications
+        select.value = selected;
+        if (select.value !== selected) {
+            select.value = '';
+        }
+    } catch (error) {
+        console.error('Failed to load notification sources:', error);
+    }
+}
+
+// Render a single notification
+function renderNotification(notif) {
+    const preview = notif.text.length > 100
+        ? notif.text.substring(0, 100) + '...'
+        : notif.text;
+    const title = notif.title ? `<strong>${escapeHtml(notif.title)}</strong> — ` : '';
+    const expanded = expandedIds.has(notif.id) ? ' expanded' : '';
+
+    const actions = [];
+    if (notif.queue_id !== null) {
+        actions.push(`<button class="btn btn-secondary" data-action="open-result">Open Result</button>`);
+    }
+    if (notif.url) {
+        actions.push(`<button class="btn btn-secondary" data-action="open-link">Open Link</button>`);
+    }
+    actions.push(`
+        <select data-action="snooze">
+            <option value="">Snooze…</option>
+            <option value="15">15 minutes</option>
+            <option value="60">1 hour</option>
+            <option value="240">4 hours</option>
+            <option value="1440">1 day</option>
+        </select>`);
+    actions.push(`<button class="btn btn-secondary" data-action="dismiss">Dismiss</button>`);
+
+    return `
+        <div class="notification-item severity-${escapeHtml(notif.severity)}${expanded}" data-id="${notif.id}">
+            <div class="notification-preview">${title}${escapeHtml(preview)}</div>
+            <div class="notification-full">${escapeHtml(notif.text)}${notif.url ? '\n\n' + escapeHtml(notif.url) : ''}</div>
+            <div class="notification-deliveries"></div>
+            <div class="notification-time">${formatTimestamp(notif.created_at)}</div>
+            <div class="notification-actions">${actions.join('')}</div>
+        </div>
+    `;
+}
+
+// Load notifications, grouped by source
 async function loadNotifications() {
     try {
-        const notifications = await invoke('get_notifications', { includeDismissed: false });
+        const sourceFilter = document.getElementById('source-filter').value;
+        const severityFilter = document.getElementById('severity-filter').value;
+        const notifications = await invoke('get_notifications', {
+            includeDismissed: false,
+            appId: sourceFilter ? parseInt(sourceFilter) : null,
+            severity: severityFilter || null
+        });
         const list = document.getElementById('notifications-list');
         
         if (notifications.length === 0) {
             list.innerHTML = '<div class="empty-state">No notifications</div>';
             return;
         }
+
+        // Group by source, ordered by each source's newest notification
+        const groups = new Map();
+        notifications.forEach(notif => {
+            const source = notif.source_name || 'Other';
+            if (!groups.has(source)) {
+                groups.set(source, []);
+            }
+            groups.get(source).push(notif);
+        });
+
+        list.innerHTML = Array.from(groups.entries()).map(([source, items]) => `
+            <div class="notification-group-header">${escapeHtml(source)} (${items.length})</div>
+            ${items.map(renderNotification).join('')}
+        `).join('');
         
-        list.innerHTML = notifications.map(notif => {
-            const preview = notif.text.length > 100 
-                ? notif.text.substring(0, 100) + '...' 
-                : notif.text;
-            
-            return `
-                <div class="notification-item" data-id="${notif.id}">
-                    <div class="notification-preview">${escapeHtml(preview)}</div>
-                    <div class="notification-full">${escapeHtml(notif.text)}</div>
-                    <div class="notification-time">${formatTimestamp(notif.created_at)}</div>
-                    <button class="btn btn-secondary" style="margin-top: 8px; width: 100%;" onclick="dismissNotification(${notif.id})">Dismiss</button>
-                </div>
-            `;
-        }).join('');
-        
-        // Add click handlers to expand/collapse
         document.querySelectorAll('.notification-item').forEach(item => {
-            const dismissBtn = item.querySelector('button');
+            const id = parseInt(item.dataset.id);
+            const notif = notifications.find(n => n.id === id);
+
+            if (item.classList.contains('expanded')) {
+                loadDeliveries(item);
+            }
+
+            // Expand/collapse when clicking outside the action controls
             item.addEventListener('click', (e) => {
-                if (e.target !== dismissBtn && !dismissBtn.contains(e.target)) {
-                    item.classList.toggle('expanded');
+                if (e.target.closest('.notification-actions')) {
+                    return;
+                }
+                item.classList.toggle('expanded');
+                if (item.classList.contains('expanded')) {
+                    expandedIds.add(id);
+                    loadDeliveries(item);
+                } else {
+                    expandedIds.delete(id);
+                }
+            });
+
+            item.querySelectorAll('[data-action]').forEach(control => {
+                const action = control.dataset.action;
+                if (action === 'snooze') {
+                    control.addEventListener('change', () => {
+                        if (control.value) {
+                            snoozeNotification(id, parseInt(control.value));
+                        }
+                    });
+                } else {
+                    control.addEventListener('click', () => handleAction(action, notif));
                 }
             });
         });
@@ -118,11 +214,61 @@ async function loadNotifications() {
     }
 }
 
+// Run a notification action button
+async function handleAction(action, notif) {
+    try {
+        if (action === 'open-result') {
+            await invoke('open_queue_detail_window', { queueId: notif.queue_id });
+        } else if (action === 'open-link') {
+            await getTauriAPI().shell.open(notif.url);
+        } else if (action === 'dismiss') {
+            await dismissNotification(notif.id);
+        }
+    } catch (error) {
+        console.error(`Failed to run notification action ${action}:`, error);
+        alert('Action failed: ' + error);
+    }
+}
+
+// Snooze notification
+async function snoozeNotification(id, minutes) {
+    try {
+        await invoke('snooze_notification', { id: id, minutes: minutes });
+        expandedIds.delete(id);
+        await refresh();
+    } catch (error) {
+        console.error('Failed to snooze notification:', error);
+        alert('Failed to snooze notification: ' + error);
+    }
+}
+
+// Reload sources and notifications
+async function refresh() {
+    await loadSources();
+    await loadNotifications();
+}
+
+// Show outbound channel delivery attempts for an expanded notification
+async function loadDeliveries(item) {
+    const container = item.querySelector('.notification-deliveries');
+    try {
+        const deliveries = await invoke('get_notification_deliveries', { notificationId: parseInt(item.dataset.id) });
+        container.innerHTML = deliveries.map(delivery => {
+            const status = delivery.success ? 'Delivered' : 'Failed';
+            const error = delivery.error ? `: ${escapeHtml(delivery.error)}` : '';
+            return `<div class="notification-time">${status} via ${escapeHtml(delivery.channel_name)} (attempt ${delivery.attempt})${error}</div>`;
+        }).join('');
+    } catch (error) {
+        console.error('Failed to load deliveries:', error);
+    }
+}
+
 // Dismiss notification
 async function dismissNotification(id) {
     try {
         await invoke('dismiss_notification', { id: id });
-        await loadNotifications();
+        expandedIds.delete(id);
+        await refresh();
     } catch (error) {
         console.error('Failed to dismiss notification:', error);
         alert('Failed to dismiss notification: ' + error);
@@ -137,7 +283,8 @@ async function dismissAll() {
     
     try {
         await invoke('dismiss_all_notifications');
-        await loadNotifications();
+        expandedIds.clear();
+        await refresh();
     } catch (error) {
         console.error('Failed to dismiss all notifications:', error);
         alert('Failed to dismiss all notifications: ' + error);
@@ -151,19 +298,18 @@ function escapeHtml(text) {
     return div.innerHTML;
 }
 
-// Make dismissNotification available globally
-window.dismissNotification = dismissNotification;
-
 // Initialize
 async function init() {
     detectPlatform();
     await applyTheme();
-    await loadNotifications();
+    await refresh();
 
     document.getElementById('dismiss-all-btn').addEventListener('click', dismissAll);
+    document.getElementById('source-filter').addEventListener('change', loadNotifications);
+    document.getElementById('severity-filter').addEventListener('change', loadNotifications);
 
     // Auto-refresh every 2 seconds
-    setInterval(loadNotifications, 2000);
+    setInterval(refresh, 2000);
 }
 
 if (document.readyState === 'loading') {
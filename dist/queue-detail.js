@@ -159,6 +159,12 @@ async function loadQueueItem() {
                         <div class="info-label">Status</div>
                         <div class="info-value">${item.status}</div>
                     </div>
+                    ${item.decision ? `
+                    <div class="info-row">
+                        <div class="info-label">Conditions</div>
+                        <div class="info-value">${escapeHtml(item.decision)}</div>
+                    </div>
+                    ` : ''}
                     <div class="info-row">
                         <div class="info-label">Created</div>
                         <div class="info-value">${formatTimestamp(item.created_at)}</div>
@@ -219,7 +225,32 @@ async function loadQueueItem() {
             </div>
         `;
 
+        // Replay section (only for finished runs recorded with their agent)
+        const canReplay = item.app_id && item.status !== 'pending' && item.status !== 'processing';
+        if (canReplay) {
+            html += `
+                <div class="section">
+                    <div class="section-header">Replay</div>
+                    <div class="section-content replay-form">
+                        <textarea id="replay-prompt" placeholder="System prompt"></textarea>
+                        <div class="replay-controls">
+                            <select id="replay-model">
+                                <option value="">Agent's model</option>
+                            </select>
+                            <button id="replay-btn">Replay</button>
+                            <span class="info-label">Uses the recorded input. Tools are recorded, not executed.</span>
+                        </div>
+                        <div id="replay-result"></div>
+                    </div>
+                </div>
+            `;
+        }
+
         document.getElementById('content').innerHTML = html;
+
+        if (canReplay) {
+            await setupReplay(item);
+        }
     } catch (error) {
         console.error('Failed to load queue item:', error);
         document.getElementById('content').innerHTML = `
@@ -231,6 +262,75 @@ async function loadQueueItem() {
     }
 }
 
+// Prefill the replay form with the agent's current prompt and the available models
+async function setupReplay(item) {
+    try {
+        const agent = await invoke('get_agent_app', { appId: item.app_id });
+        if (agent) {
+            document.getElementById('replay-prompt').value = agent.prompt || '';
+        }
+    } catch (error) {
+        console.error('Failed to load agent for replay:', error);
+    }
+
+    try {
+        const models = await invoke('get_models');
+        const select = document.getElementById('replay-model');
+        (models || []).forEach(model => {
+            const option = document.createElement('option');
+            option.value = model.id;
+            option.textContent = model.id;
+            select.appendChild(option);
+        });
+    } catch (error) {
+        console.error('Failed to load models for replay:', error);
+    }
+
+    document.getElementById('replay-btn').addEventListener('click', () => replay(item));
+}
+
+// Replay the item with the prompt and model from the form and show both outputs side by side
+async function replay(item) {
+    const button = document.getElementById('replay-btn');
+    const resultContainer = document.getElementById('replay-result');
+    const model = document.getElementById('replay-model').value;
+
+    button.disabled = true;
+    resultContainer.innerHTML = '<div class="empty-response">Replaying…</div>';
+
+    try {
+        const result = await invoke('replay_queue_item', {
+            queueId: item.id,
+            prompt: document.getElementById('replay-prompt').value,
+            model: model || null
+        });
+
+        const toolCalls = result.replay.tool_calls.map(call => {
+            const state = call.executed ? 'executed' : 'recorded only';
+            return `<div>${escapeHtml(call.name)} (${state}): ${escapeHtml(JSON.stringify(call.arguments))}</div>`;
+        }).join('');
+
+        resultContainer.innerHTML = `
+            <div class="replay-compare">
+                <div class="message-item">
+                    <div class="message-role role-system">Original</div>
+                    <div class="response-content">${escapeHtml(result.original.response || '')}</div>
+                </div>
+                <div class="message-item">
+                    <div class="message-role role-assistant">Replay (${escapeHtml(result.replay.model || '')})</div>
+                    <div class="response-content">${escapeHtml(result.replay.output)}</div>
+                    ${toolCalls ? `<div class="replay-tool-calls">${toolCalls}</div>` : ''}
+                </div>
+            </div>
+        `;
+    } catch (error) {
+        console.error('Replay failed:', error);
+        resultContainer.innerHTML = `<div class="empty-response">${escapeHtml(String(error))}</div>`;
+    } finally {
+        button.disabled = false;
+    }
+}
+
 // Initialize
 async function init() {
     detectPlatform();
@@ -9,6 +9,22 @@ const invoke = async (cmd, args = {}) => {
     return await tauri.core.invoke(cmd, args);
 };
 
+const openDialog = async (options = {}) => {
+    const tauri = getTauriAPI();
+    if (!tauri) {
+        throw new Error('Tauri API not available');
+    }
+    return await tauri.dialog.open(options);
+};
+
+const saveDialog = async (options = {}) => {
+    const tauri = getTauriAPI();
+    if (!tauri) {
+        throw new Error('Tauri API not available');
+    }
+    return await tauri.dialog.save(options);
+};
+
 const getCurrentWindow = () => {
     const tauri = getTauriAPI();
     if (!tauri) {
@@ -19,6 +35,7 @@ const getCurrentWindow = () => {
 
 // Platform detection
 let isMacOS = false;
+let isLinux = false;
 
 // Auto-resize window to fit content
 async function autoResizeWindow() {
@@ -69,6 +86,7 @@ async function detectPlatform() {
         if (tauri && tauri.os && tauri.os.platform) {
             const platform = tauri.os.platform();
             isMacOS = platform === 'macos';
+            isLinux = platform === 'linux';
             console.log('[Settings] Platform detected via Tauri:', platform, '(isMacOS:', isMacOS, ')');
 
             // Apply platform-specific class to root element
@@ -342,6 +360,16 @@ async function loadSettings() {
         document.getElementById('settings-start-login').checked = settings.start_at_login || false;
         document.getElementById('settings-hide-app-names').checked = settings.hide_app_names || false;
         document.getElementById('settings-separate-agent-apps').checked = settings.separate_agent_apps || false;
+        document.getElementById('settings-notification-retention').value = settings.notification_retention_days ?? 30;
+        document.getElementById('settings-launch-history-retention').value = settings.launch_history_retention_days ?? 365;
+        loadUsageStats();
+        loadTags();
+        loadSavedFilters();
+        document.getElementById('settings-backup-interval').value = settings.backup_interval_hours ?? 24;
+        document.getElementById('settings-backup-keep').value = settings.backup_keep ?? 7;
+        document.getElementById('settings-backup-sessions').checked = settings.backup_include_sessions || false;
+        loadBackupStatus();
+        loadConfigWatch();
 
         // Load AI settings
         try {
@@ -350,6 +378,7 @@ async function loadSettings() {
             document.getElementById('ai-endpoint-url').value = aiSettings.endpoint_url || 'http://192.168.1.113:1234';
             document.getElementById('ai-api-key').value = aiSettings.api_key || '';
             document.getElementById('ai-max-concurrent').value = aiSettings.max_concurrent_agents || 1;
+            document.getElementById('ai-embedding-model').value = aiSettings.embedding_model || '';
             
             // Load models
             await loadModels();
@@ -359,6 +388,9 @@ async function loadSettings() {
                 document.getElementById('ai-default-model').value = aiSettings.default_model;
             }
             
+            await loadDocumentSources();
+            await loadNotificationChannels();
+            await loadSecrets();
             updateAISettingsDisabled();
         } catch (error) {
             console.error('[Settings] Failed to load AI settings:', error);
@@ -376,6 +408,893 @@ async function loadSettings() {
     }
 }
 
+// Load indexed document folders
+async function loadDocumentSources() {
+    const list = document.getElementById('document-sources-list');
+    try {
+        const sources = await invoke('get_document_sources');
+        list.innerHTML = '';
+
+        if (!sources || sources.length === 0) {
+            const empty = document.createElement('div');
+            empty.className = 'hint';
+            empty.textContent = 'No folders indexed';
+            list.appendChild(empty);
+            return;
+        }
+
+        sources.forEach(source => {
+            const row = document.createElement('div');
+            row.style.display = 'flex';
+            row.style.alignItems = 'center';
+            row.style.gap = '8px';
+
+            const enabled = document.createElement('input');
+            enabled.type = 'checkbox';
+            enabled.checked = source.enabled;
+            enabled.addEventListener('change', async () => {
+                try {
+                    await invoke('set_document_source_enabled', { id: source.id, enabled: enabled.checked });
+                } catch (error) {
+                    console.error('[Settings] Failed to update document folder:', error);
+                    enabled.checked = !enabled.checked;
+                }
+            });
+
+            const label = document.createElement('span');
+            label.style.flex = '1';
+            label.style.overflow = 'hidden';
+            label.style.textOverflow = 'ellipsis';
+            label.title = source.path;
+            label.textContent = `${source.path} (${source.file_count} files, ${source.chunk_count} chunks)`;
+
+            const remove = document.createElement('button');
+            remove.type = 'button';
+            remove.className = 'btn btn-secondary';
+            remove.textContent = 'Remove';
+            remove.addEventListener('click', async () => {
+                try {
+                    await invoke('remove_document_source', { id: source.id });
+                    await loadDocumentSources();
+                } catch (error) {
+                    console.error('[Settings] Failed to remove document folder:', error);
+                    alert('Failed to remove folder: ' + error);
+                }
+            });
+
+            row.appendChild(enabled);
+            row.appendChild(label);
+            row.appendChild(remove);
+            list.appendChild(row);
+        });
+    } catch (error) {
+        console.error('[Settings] Failed to load document folders:', error);
+    }
+}
+
+// Add a document folder to the index
+async function addDocumentSource() {
+    try {
+        const selected = await openDialog({ directory: true, multiple: false });
+        if (!selected) {
+            return;
+        }
+        await invoke('add_document_source', { path: selected });
+        await loadDocumentSources();
+    } catch (error) {
+        console.error('[Settings] Failed to add document folder:', error);
+        alert('Failed to add folder: ' + error);
+    }
+}
+
+// Import an agent from an exported JSON or TOML file
+async function importAgent() {
+    const result = document.getElementById('import-agent-result');
+
+    try {
+        const path = await openDialog({
+            multiple: false,
+            filters: [{ name: 'Agent', extensions: ['json', 'toml'] }]
+        });
+        if (!path) {
+            return;
+        }
+
+        const imported = await invoke('import_agent', { path });
+        const lines = [`Imported "${imported.name}".`];
+        imported.warnings.forEach(warning => lines.push(`⚠ ${warning}`));
+        result.textContent = lines.join('\n');
+    } catch (error) {
+        console.error('Failed to import agent:', error);
+        result.textContent = String(error);
+    }
+    result.style.display = 'block';
+}
+
+// Re-index all document folders
+async function reindexDocuments() {
+    const btn = document.getElementById('reindex-documents-btn');
+    const originalText = btn.textContent;
+    try {
+        btn.disabled = true;
+        btn.textContent = 'Indexing...';
+        await invoke('reindex_documents');
+        await loadDocumentSources();
+    } catch (error) {
+        console.error('[Settings] Failed to re-index documents:', error);
+        alert('Failed to re-index documents: ' + error);
+    } finally {
+        btn.disabled = false;
+        btn.textContent = originalText;
+    }
+}
+
+// Config fields for each notification channel kind
+const CHANNEL_FIELDS = {
+    webhook: [
+        { key: 'url', placeholder: 'https://example.com/hook' },
+        { key: 'body_template', placeholder: 'Body template, e.g. {"text": "{{title}}: {{message}}"} (optional)', multiline: true },
+        { key: 'headers', placeholder: 'Extra headers, one "Name: value" per line (optional)', multiline: true, headers: true }
+    ],
+    smtp: [
+        { key: 'host', placeholder: 'smtp.example.com' },
+        { key: 'port', placeholder: 'Port (default depends on security)', number: true },
+        { key: 'security', options: ['starttls', 'tls', 'none'] },
+        { key: 'username', placeholder: 'Username (optional)' },
+        { key: 'password', placeholder: 'Password (optional)', secret: true },
+        { key: 'from', placeholder: 'From address' },
+        { key: 'to', placeholder: 'To addresses, comma-separated' },
+        { key: 'subject_template', placeholder: 'Subject template (default: {{title}})' }
+    ],
+    ntfy: [
+        { key: 'server_url', placeholder: 'https://ntfy.sh' },
+        { key: 'topic', placeholder: 'Topic' },
+        { key: 'token', placeholder: 'Access token (optional)', secret: true }
+    ],
+    gotify: [
+        { key: 'server_url', placeholder: 'https://gotify.example.com' },
+        { key: 'token', placeholder: 'Application token', secret: true }
+    ]
+};
+
+// Channel currently being edited (id 0 for a new channel)
+let editingChannelId = null;
+
+// Render the config inputs for a channel kind
+function renderChannelFields(kind, config = {}) {
+    const container = document.getElementById('channel-fields');
+    container.innerHTML = '';
+
+    CHANNEL_FIELDS[kind].forEach(field => {
+        let input;
+        if (field.options) {
+            input = document.createElement('select');
+            field.options.forEach(value => {
+                const option = document.createElement('option');
+                option.value = value;
+                option.textContent = value;
+                input.appendChild(option);
+            });
+        } else if (field.multiline) {
+            input = document.createElement('textarea');
+            input.rows = 2;
+        } else {
+            input = document.createElement('input');
+            input.type = field.secret ? 'password' : (field.number ? 'number' : 'text');
+        }
+
+        input.dataset.key = field.key;
+        if (field.placeholder) {
+            input.placeholder = field.placeholder;
+        }
+
+        const value = config[field.key];
+        if (field.headers && value) {
+            input.value = Object.entries(value).map(([name, v]) => `${name}: ${v}`).join('\n');
+        } else if (value !== undefined && value !== null) {
+            input.value = value;
+        }
+
+        container.appendChild(input);
+    });
+}
+
+// Build a channel object from the editor inputs
+function readChannelEditor() {
+    const kind = document.getElementById('channel-kind').value;
+    const config = {};
+
+    CHANNEL_FIELDS[kind].forEach(field => {
+        const input = document.querySelector(`#channel-fields [data-key="${field.key}"]`);
+        const value = input.value.trim();
+        if (!value) {
+            return;
+        }
+
+        if (field.headers) {
+            config[field.key] = {};
+            value.split('\n').forEach(line => {
+                const index = line.indexOf(':');
+                if (index > 0) {
+                    config[field.key][line.substring(0, index).trim()] = line.substring(index + 1).trim();
+                }
+            });
+        } else if (field.number) {
+            config[field.key] = parseInt(value);
+        } else {
+            config[field.key] = value;
+        }
+    });
+
+    return {
+        id: editingChannelId || 0,
+        name: document.getElementById('channel-name').value.trim(),
+        kind: kind,
+        config: config,
+        enabled: document.getElementById('channel-enabled').checked
+    };
+}
+
+// Show the channel editor for a new or existing channel
+function openChannelEditor(channel) {
+    editingChannelId = channel ? channel.id : 0;
+    document.getElementById('channel-name').value = channel ? channel.name : '';
+    document.getElementById('channel-kind').value = channel ? channel.kind : 'webhook';
+    document.getElementById('channel-enabled').checked = channel ? channel.enabled : true;
+    renderChannelFields(document.getElementById('channel-kind').value, channel ? channel.config : {});
+    document.getElementById('channel-editor').style.display = 'flex';
+    document.getElementById('add-channel-btn').style.display = 'none';
+}
+
+function closeChannelEditor() {
+    editingChannelId = null;
+    document.getElementById('channel-editor').style.display = 'none';
+    document.getElementById('add-channel-btn').style.display = '';
+}
+
+// Show the most used apps over the last 30 days
+async function loadUsageStats() {
+    const container = document.getElementById('usage-stats');
+    try {
+        const [stats, apps] = await Promise.all([
+            invoke('get_launch_stats', { days: 30 }),
+            invoke('get_all_apps')
+        ]);
+        const names = new Map(apps.map(app => [app.id, app.name]));
+        const top = stats.filter(s => names.has(s.app_id)).slice(0, 5);
+
+        container.textContent = top.length
+            ? 'Most used in the last 30 days: ' + top
+                .map(s => `${names.get(s.app_id)} (${s.launch_count}, last ${new Date(s.last_launched_at * 1000).toLocaleDateString()})`)
+                .join(', ')
+            : 'No launches in the last 30 days';
+    } catch (error) {
+        console.error('[Settings] Failed to load usage statistics:', error);
+    }
+}
+
+// Applications found in .desktop files, offered for import
+let desktopEntries = [];
+
+// List installed applications that aren't in the launcher yet
+async function scanDesktopEntries() {
+    const status = document.getElementById('desktop-entries-status');
+    const list = document.getElementById('desktop-entries-list');
+    try {
+        desktopEntries = await invoke('scan_desktop_entries');
+    } catch (error) {
+        console.error('[Settings] Failed to scan desktop entries:', error);
+        status.textContent = 'Failed to find installed apps: ' + error;
+        return;
+    }
+
+    list.innerHTML = '';
+    if (desktopEntries.length === 0) {
+        status.textContent = 'No new apps found';
+        return;
+    }
+    status.textContent = `${desktopEntries.length} apps not in the launcher yet`;
+
+    desktopEntries.forEach((entry, index) => {
+        const label = document.createElement('label');
+        label.className = 'checkbox-label';
+        label.style.gap = '8px';
+        label.title = entry.path;
+
+        const checkbox = document.createElement('input');
+        checkbox.type = 'checkbox';
+        checkbox.dataset.index = index;
+
+        const name = document.createElement('span');
+        name.textContent = entry.terminal ? `${entry.name} (terminal)` : entry.name;
+
+        label.appendChild(checkbox);
+        label.appendChild(name);
+        list.appendChild(label);
+    });
+    document.getElementById('desktop-entries').style.display = 'flex';
+}
+
+function closeDesktopEntries() {
+    desktopEntries = [];
+    document.getElementById('desktop-entries').style.display = 'none';
+    document.getElementById('desktop-entries-list').innerHTML = '';
+}
+
+// Add the checked applications to the launcher
+async function importDesktopEntries() {
+    const status = document.getElementById('desktop-entries-status');
+    const entries = Array.from(document.querySelectorAll('#desktop-entries-list input:checked'))
+        .map(input => desktopEntries[Number(input.dataset.index)]);
+    if (entries.length === 0) return;
+
+    try {
+        const appIds = await invoke('import_desktop_entries', { entries });
+        closeDesktopEntries();
+        status.textContent = `Added ${appIds.length} app${appIds.length === 1 ? '' : 's'}`;
+    } catch (error) {
+        console.error('[Settings] Failed to import desktop entries:', error);
+        status.textContent = 'Failed to add apps: ' + error;
+    }
+}
+
+// List tags with their app counts, with rename and delete
+async function loadTags() {
+    const list = document.getElementById('tags-list');
+    try {
+        const tags = await invoke('get_tags');
+        list.innerHTML = '';
+
+        if (!tags || tags.length === 0) {
+            const empty = document.createElement('div');
+            empty.className = 'hint';
+            empty.textContent = 'No tags yet, add them from the app form';
+            list.appendChild(empty);
+            return;
+        }
+
+        tags.forEach(tag => {
+            const row = document.createElement('div');
+            row.style.display = 'flex';
+            row.style.alignItems = 'center';
+            row.style.gap = '8px';
+
+            const name = document.createElement('input');
+            name.type = 'text';
+            name.value = tag.name;
+            name.style.flex = '1';
+
+            const count = document.createElement('span');
+            count.className = 'hint';
+            count.textContent = `${tag.app_count} app${tag.app_count === 1 ? '' : 's'}`;
+
+            const rename = document.createElement('button');
+            rename.type = 'button';
+            rename.className = 'btn btn-secondary';
+            rename.textContent = 'Rename';
+            rename.addEventListener('click', async () => {
+                const newName = name.value.trim();
+                if (!newName || newName === tag.name) return;
+                try {
+                    await invoke('rename_tag', { old: tag.name, new: newName });
+                    await Promise.all([loadTags(), loadSavedFilters()]);
+                } catch (error) {
+                    console.error('[Settings] Failed to rename tag:', error);
+                    alert('Failed to rename tag: ' + error);
+                }
+            });
+
+            const remove = document.createElement('button');
+            remove.type = 'button';
+            remove.className = 'btn btn-secondary';
+            remove.textContent = 'Delete';
+            remove.addEventListener('click', async () => {
+                if (!confirm(`Remove the tag "${tag.name}" from all apps?`)) return;
+                try {
+                    await invoke('delete_tag', { tag: tag.name });
+                    await loadTags();
+                } catch (error) {
+                    console.error('[Settings] Failed to delete tag:', error);
+                    alert('Failed to delete tag: ' + error);
+                }
+            });
+
+            row.appendChild(name);
+            row.appendChild(count);
+            row.appendChild(rename);
+            row.appendChild(remove);
+            list.appendChild(row);
+        });
+    } catch (error) {
+        console.error('[Settings] Failed to load tags:', error);
+    }
+}
+
+// Saved filter being edited: 0 for a new one, null when the editor is closed
+let editingFilter = null;
+
+// Short description of a filter rule, e.g. "tags: work · agent · run_command"
+function describeFilterRule(rule) {
+    const parts = [];
+    if (rule.tags.length) parts.push('tags: ' + rule.tags.join(', '));
+    if (rule.app_types.length) parts.push(rule.app_types.join(', '));
+    if (rule.tools.length) parts.push('tools: ' + rule.tools.join(', '));
+    return parts.length ? parts.join(' · ') : 'all apps';
+}
+
+// List saved filters with edit and delete
+async function loadSavedFilters() {
+    const list = document.getElementById('saved-filters-list');
+    try {
+        const filters = await invoke('get_saved_filters');
+        list.innerHTML = '';
+
+        if (!filters || filters.length === 0) {
+            const empty = document.createElement('div');
+            empty.className = 'hint';
+            empty.textContent = 'No saved filters';
+            list.appendChild(empty);
+            return;
+        }
+
+        filters.forEach(filter => {
+            const row = document.createElement('div');
+            row.style.display = 'flex';
+            row.style.alignItems = 'center';
+            row.style.gap = '8px';
+
+            const label = document.createElement('span');
+            label.style.flex = '1';
+            label.style.overflow = 'hidden';
+            label.style.textOverflow = 'ellipsis';
+            label.textContent = `${filter.name}${filter.pinned ? ' (pinned)' : ''} — ${describeFilterRule(filter.rule)}`;
+
+            const edit = document.createElement('button');
+            edit.type = 'button';
+            edit.className = 'btn btn-secondary';
+            edit.textContent = 'Edit';
+            edit.addEventListener('click', () => openFilterEditor(filter));
+
+            const remove = document.createElement('button');
+            remove.type = 'button';
+            remove.className = 'btn btn-secondary';
+            remove.textContent = 'Delete';
+            remove.addEventListener('click', async () => {
+                try {
+                    await invoke('delete_saved_filter', { filterId: filter.id });
+                    await loadSavedFilters();
+                } catch (error) {
+                    console.error('[Settings] Failed to delete filter:', error);
+                    alert('Failed to delete filter: ' + error);
+                }
+            });
+
+            row.appendChild(label);
+            row.appendChild(edit);
+            row.appendChild(remove);
+            list.appendChild(row);
+        });
+    } catch (error) {
+        console.error('[Settings] Failed to load saved filters:', error);
+    }
+}
+
+function setCheckedValues(containerId, values) {
+    document.querySelectorAll(`#${containerId} input[type="checkbox"]`).forEach(input => {
+        input.checked = values.includes(input.value);
+    });
+}
+
+function checkedValues(containerId) {
+    return Array.from(document.querySelectorAll(`#${containerId} input[type="checkbox"]:checked`))
+        .map(input => input.value);
+}
+
+function openFilterEditor(filter) {
+    editingFilter = filter ? filter.id : 0;
+    const rule = filter ? filter.rule : { tags: [], app_types: [], tools: [] };
+    document.getElementById('filter-name').value = filter ? filter.name : '';
+    document.getElementById('filter-tags').value = rule.tags.join(', ');
+    setCheckedValues('filter-types', rule.app_types);
+    setCheckedValues('filter-tools', rule.tools);
+    document.getElementById('filter-pinned').checked = filter ? filter.pinned : true;
+    document.getElementById('filter-editor').style.display = 'flex';
+    document.getElementById('add-filter-btn').style.display = 'none';
+}
+
+function closeFilterEditor() {
+    editingFilter = null;
+    document.getElementById('filter-editor').style.display = 'none';
+    document.getElementById('add-filter-btn').style.display = '';
+}
+
+async function saveFilter() {
+    const filter = {
+        id: editingFilter || 0,
+        name: document.getElementById('filter-name').value.trim(),
+        rule: {
+            tags: document.getElementById('filter-tags').value.split(',').map(t => t.trim()).filter(t => t),
+            app_types: checkedValues('filter-types'),
+            tools: checkedValues('filter-tools')
+        },
+        pinned: document.getElementById('filter-pinned').checked
+    };
+    try {
+        await invoke('save_saved_filter', { filter });
+        closeFilterEditor();
+        await loadSavedFilters();
+    } catch (error) {
+        console.error('[Settings] Failed to save filter:', error);
+        alert('Failed to save filter: ' + error);
+    }
+}
+
+// Show when the last backup in the backups folder was made
+async function loadBackupStatus() {
+    try {
+        const backups = await invoke('list_backups');
+        const last = backups.find(b => b.kind === 'auto') || backups[0];
+        document.getElementById('backup-status').textContent = last
+            ? `Last ${last.kind} backup: ${new Date(last.modified_at * 1000).toLocaleString()}`
+            : 'No backups yet';
+    } catch (error) {
+        console.error('[Settings] Failed to list backups:', error);
+    }
+}
+
+// Write a backup archive to a location chosen by the user
+async function backupNow() {
+    const status = document.getElementById('backup-status');
+    try {
+        const path = await saveDialog({
+            defaultPath: `jvlauncher-backup-${new Date().toISOString().slice(0, 10)}.zip`,
+            filters: [{ name: 'Backup', extensions: ['zip'] }]
+        });
+        if (!path) {
+            return;
+        }
+
+        status.textContent = 'Backing up…';
+        await invoke('create_backup', {
+            path,
+            includeSessions: document.getElementById('settings-backup-sessions').checked
+        });
+        status.textContent = `Saved backup to ${path}`;
+    } catch (error) {
+        console.error('[Settings] Backup failed:', error);
+        status.textContent = String(error);
+    }
+}
+
+// Validate a backup archive, confirm, then restore it (the launcher restarts afterwards)
+async function restoreBackup() {
+    const status = document.getElementById('backup-status');
+    try {
+        const path = await openDialog({
+            multiple: false,
+            filters: [{ name: 'Backup', extensions: ['zip'] }]
+        });
+        if (!path) {
+            return;
+        }
+
+        const manifest = await invoke('validate_backup', { path });
+        const created = new Date(manifest.created_at * 1000).toLocaleString();
+        const sessions = manifest.includes_sessions ? ' including webapp sessions' : '';
+        if (!confirm(`Restore the backup from ${created} (version ${manifest.app_version})${sessions}?\n\nYour current apps, settings and icons will be replaced. A copy of them is kept in the backups folder. The launcher restarts afterwards.`)) {
+            return;
+        }
+
+        status.textContent = 'Restoring…';
+        await invoke('restore_backup', { path });
+    } catch (error) {
+        console.error('[Settings] Restore failed:', error);
+        status.textContent = String(error);
+    }
+}
+
+// Show the configuration file being watched, if any
+async function loadConfigWatch() {
+    try {
+        const watch = await invoke('get_config_watch');
+        document.getElementById('config-watch-path').textContent = watch.path || 'Not watching a file';
+        document.getElementById('config-watch-mode').value = watch.mode;
+        document.getElementById('config-unwatch-btn').style.display = watch.path ? '' : 'none';
+    } catch (error) {
+        console.error('[Settings] Failed to get configuration watch:', error);
+    }
+}
+
+// Export apps, settings and agents to a TOML or YAML file
+async function exportConfig() {
+    const status = document.getElementById('config-status');
+    try {
+        const path = await saveDialog({
+            defaultPath: 'jvlauncher.toml',
+            filters: [{ name: 'Configuration', extensions: ['toml', 'yaml', 'yml'] }]
+        });
+        if (!path) {
+            return;
+        }
+
+        await invoke('export_config', { path });
+        status.textContent = `Exported to ${path}`;
+    } catch (error) {
+        console.error('[Settings] Configuration export failed:', error);
+        status.textContent = String(error);
+    }
+}
+
+// Describe an import preview, one line per added, updated or removed entry
+function describeConfigPlan(plan) {
+    const labels = { add: '+ add', update: '~ update', remove: '- remove' };
+    const lines = plan.changes.map(change => {
+        const fields = change.fields.length ? ` (${change.fields.join(', ')})` : '';
+        return `${labels[change.action]} ${change.target} ${change.name}${fields}`;
+    });
+    return [
+        ...(lines.length ? lines : ['No changes']),
+        ...plan.warnings.map(warning => `! ${warning}`)
+    ].join('\n');
+}
+
+// Preview a configuration file, confirm, then apply it
+async function importConfig() {
+    const status = document.getElementById('config-status');
+    const mode = document.getElementById('config-import-mode').value;
+    try {
+        const path = await openDialog({
+            multiple: false,
+            filters: [{ name: 'Configuration', extensions: ['toml', 'yaml', 'yml'] }]
+        });
+        if (!path) {
+            return;
+        }
+
+        const plan = await invoke('preview_config_import', { path, mode });
+        if (!plan.changes.length) {
+            status.textContent = describeConfigPlan(plan);
+            return;
+        }
+        const removes = mode === 'replace' ? '\n\nApps that are not in the file will be deleted.' : '';
+        if (!confirm(`Apply ${path}?\n\n${describeConfigPlan(plan)}${removes}`)) {
+            return;
+        }
+
+        const applied = await invoke('apply_config_import', { path, mode });
+        status.textContent = `Applied ${applied.changes.length} change(s)`
+            + (applied.warnings.length ? `\n${applied.warnings.join('\n')}` : '');
+        await loadSettings();
+    } catch (error) {
+        console.error('[Settings] Configuration import failed:', error);
+        status.textContent = String(error);
+    }
+}
+
+// Apply a configuration file automatically whenever it is saved
+async function watchConfig() {
+    const status = document.getElementById('config-status');
+    try {
+        const path = await openDialog({
+            multiple: false,
+            filters: [{ name: 'Configuration', extensions: ['toml', 'yaml', 'yml'] }]
+        });
+        if (!path) {
+            return;
+        }
+
+        await invoke('set_config_watch', {
+            watch: { path, mode: document.getElementById('config-watch-mode').value }
+        });
+        status.textContent = 'Changes to the file are applied when it is saved';
+        await loadConfigWatch();
+    } catch (error) {
+        console.error('[Settings] Failed to watch configuration:', error);
+        status.textContent = String(error);
+    }
+}
+
+async function unwatchConfig() {
+    try {
+        await invoke('set_config_watch', {
+            watch: { path: null, mode: document.getElementById('config-watch-mode').value }
+        });
+        await loadConfigWatch();
+    } catch (error) {
+        console.error('[Settings] Failed to stop watching configuration:', error);
+    }
+}
+
+// Load secrets status and names (values never leave the backend)
+async function loadSecrets() {
+    const status = document.getElementById('secrets-status');
+    const unlock = document.getElementById('secrets-unlock');
+    const editor = document.getElementById('secrets-editor');
+    const list = document.getElementById('secrets-list');
+    const unlockBtn = document.getElementById('secrets-unlock-btn');
+
+    try {
+        const state = await invoke('get_secrets_status');
+        list.innerHTML = '';
+
+        if (!state.unlocked) {
+            editor.style.display = 'none';
+            unlock.style.display = 'flex';
+            unlockBtn.textContent = state.backend ? 'Unlock' : 'Set Passphrase';
+            status.textContent = state.backend
+                ? 'Secrets are locked. Enter your passphrase to use them this session.'
+                : 'The system keyring is unavailable. Choose a passphrase (8+ characters) to encrypt secrets.';
+            return;
+        }
+
+        unlock.style.display = 'none';
+        editor.style.display = 'flex';
+        status.textContent = state.backend === 'keyring'
+            ? 'Encrypted with a key stored in the system keyring.'
+            : 'Encrypted with your passphrase.';
+
+        const secrets = await invoke('list_secrets');
+        secrets.forEach(secret => {
+            const row = document.createElement('div');
+            row.style.display = 'flex';
+            row.style.alignItems = 'center';
+            row.style.gap = '8px';
+
+            const label = document.createElement('span');
+            label.style.flex = '1';
+            label.textContent = `\${secret:${secret.name}}`;
+
+            const remove = document.createElement('button');
+            remove.type = 'button';
+            remove.className = 'btn btn-secondary';
+            remove.textContent = 'Remove';
+            remove.addEventListener('click', async () => {
+                if (!confirm(`Remove secret "${secret.name}"? Settings referencing it will stop working.`)) {
+                    return;
+                }
+                try {
+                    await invoke('delete_secret', { name: secret.name });
+                    await loadSecrets();
+                } catch (error) {
+                    console.error('[Settings] Failed to delete secret:', error);
+                    alert('Failed to delete secret: ' + error);
+                }
+            });
+
+            row.appendChild(label);
+            row.appendChild(remove);
+            list.appendChild(row);
+        });
+    } catch (error) {
+        console.error('[Settings] Failed to load secrets:', error);
+        status.textContent = String(error);
+    }
+}
+
+// Unlock secrets, or set up a passphrase the first time
+async function unlockSecrets() {
+    const input = document.getElementById('secrets-passphrase');
+    try {
+        const state = await invoke('get_secrets_status');
+        const command = state.backend ? 'unlock_secrets' : 'setup_secrets_passphrase';
+        await invoke(command, { passphrase: input.value });
+        input.value = '';
+        await loadSecrets();
+        // The API key may have been moved into secrets
+        const aiSettings = await invoke('get_ai_settings');
+        document.getElementById('ai-api-key').value = aiSettings.api_key || '';
+    } catch (error) {
+        console.error('[Settings] Failed to unlock secrets:', error);
+        alert(String(error));
+    }
+}
+
+// Save a secret from the editor row
+async function saveSecret() {
+    const name = document.getElementById('secret-name');
+    const value = document.getElementById('secret-value');
+    try {
+        await invoke('set_secret', { name: name.value.trim(), value: value.value });
+        name.value = '';
+        value.value = '';
+        await loadSecrets();
+    } catch (error) {
+        console.error('[Settings] Failed to save secret:', error);
+        alert('Failed to save secret: ' + error);
+    }
+}
+
+// Load outbound notification channels
+async function loadNotificationChannels() {
+    const list = document.getElementById('notification-channels-list');
+    try {
+        const channels = await invoke('get_notification_channels');
+        list.innerHTML = '';
+
+        if (!channels || channels.length === 0) {
+            const empty = document.createElement('div');
+            empty.className = 'hint';
+            empty.textContent = 'No channels configured';
+            list.appendChild(empty);
+            return;
+        }
+
+        channels.forEach(channel => {
+            const row = document.createElement('div');
+            row.style.display = 'flex';
+            row.style.alignItems = 'center';
+            row.style.gap = '8px';
+
+            const label = document.createElement('span');
+            label.style.flex = '1';
+            label.textContent = `${channel.name} (${channel.kind}${channel.enabled ? '' : ', disabled'})`;
+
+            const edit = document.createElement('button');
+            edit.type = 'button';
+            edit.className = 'btn btn-secondary';
+            edit.textContent = 'Edit';
+            edit.addEventListener('click', () => openChannelEditor(channel));
+
+            const remove = document.createElement('button');
+            remove.type = 'button';
+            remove.className = 'btn btn-secondary';
+            remove.textContent = 'Remove';
+            remove.addEventListener('click', async () => {
+                if (!confirm(`Remove channel "${channel.name}"?`)) {
+                    return;
+                }
+                try {
+                    await invoke('delete_notification_channel', { id: channel.id });
+                    await loadNotificationChannels();
+                } catch (error) {
+                    console.error('[Settings] Failed to remove channel:', error);
+                    alert('Failed to remove channel: ' + error);
+                }
+            });
+
+            row.appendChild(label);
+            row.appendChild(edit);
+            row.appendChild(remove);
+            list.appendChild(row);
+        });
+    } catch (error) {
+        console.error('[Settings] Failed to load notification channels:', error);
+    }
+}
+
+// Save the channel in the editor
+async function saveChannel() {
+    try {
+        await invoke('save_notification_channel', { channel: readChannelEditor() });
+        closeChannelEditor();
+        await loadNotificationChannels();
+    } catch (error) {
+        console.error('[Settings] Failed to save channel:', error);
+        alert('Failed to save channel: ' + error);
+    }
+}
+
+// Send a test message through the channel in the editor
+async function testChannel() {
+    const btn = document.getElementById('test-channel-btn');
+    const originalText = btn.textContent;
+    try {
+        btn.disabled = true;
+        btn.textContent = 'Sending...';
+        await invoke('test_notification_channel', { channel: readChannelEditor() });
+        btn.textContent = 'Sent!';
+        setTimeout(() => {
+            btn.textContent = originalText;
+        }, 2000);
+    } catch (error) {
+        console.error('[Settings] Channel test failed:', error);
+        alert('Channel test failed: ' + error);
+        btn.textContent = originalText;
+    } finally {
+        btn.disabled = false;
+    }
+}
+
 // Load models list
 async function loadModels() {
     try {
@@ -569,23 +1488,34 @@ async function saveSettings() {
         global_shortcut: shortcutInput.dataset.rawValue || shortcutInput.value,
         start_at_login: document.getElementById('settings-start-login').checked,
         hide_app_names: document.getElementById('settings-hide-app-names').checked,
-        separate_agent_apps: document.getElementById('settings-separate-agent-apps').checked
+        separate_agent_apps: document.getElementById('settings-separate-agent-apps').checked,
+        notification_retention_days: Math.max(0, parseInt(document.getElementById('settings-notification-retention').value) || 0),
+        launch_history_retention_days: Math.max(0, parseInt(document.getElementById('settings-launch-history-retention').value) || 0),
+        backup_interval_hours: Math.max(0, parseInt(document.getElementById('settings-backup-interval').value) || 0),
+        backup_keep: Math.max(1, parseInt(document.getElementById('settings-backup-keep').value) || 1),
+        backup_include_sessions: document.getElementById('settings-backup-sessions').checked
     };
 
     // Save AI settings
     try {
-        await invoke('update_ai_setting', { key: 'enabled', value: document.getElementById('ai-enabled').checked ? 'true' : 'false' });
-        await invoke('update_ai_setting', { key: 'endpoint_url', value: document.getElementById('ai-endpoint-url').value });
-        await invoke('update_ai_setting', { key: 'api_key', value: document.getElementById('ai-api-key').value });
-        await invoke('update_ai_setting', { key: 'max_concurrent_agents', value: document.getElementById('ai-max-concurrent').value });
-        
+        await invoke('update_settings', {
+            values: {
+                ai_enabled: document.getElementById('ai-enabled').checked ? 'true' : 'false',
+                ai_endpoint_url: document.getElementById('ai-endpoint-url').value,
+                ai_api_key: document.getElementById('ai-api-key').value,
+                ai_max_concurrent_agents: document.getElementById('ai-max-concurrent').value,
+                ai_embedding_model: document.getElementById('ai-embedding-model').value.trim()
+            }
+        });
+
         const defaultModel = document.getElementById('ai-default-model').value;
         if (defaultModel) {
             await invoke('set_default_model', { modelId: defaultModel });
         }
     } catch (error) {
         console.error('[Settings] Failed to save AI settings:', error);
-        // Don't block save if AI settings fail
+        alert('Failed to save AI settings: ' + error);
+        return;
     }
 
     // Check for keyboard shortcut conflicts with app shortcuts
@@ -608,21 +1538,23 @@ async function saveSettings() {
     }
 
     try {
-        await invoke('update_setting', { key: 'theme', value: newSettings.theme });
-        await invoke('update_setting', { key: 'grid_cols', value: newSettings.grid_cols.toString() });
-        await invoke('update_setting', { key: 'grid_rows', value: newSettings.grid_rows.toString() });
-        await invoke('update_setting', { key: 'global_shortcut', value: newSettings.global_shortcut });
-        await invoke('update_setting', { key: 'start_at_login', value: newSettings.start_at_login ? 'true' : 'false' });
-        await invoke('update_setting', { key: 'hide_app_names', value: newSettings.hide_app_names ? 'true' : 'false' });
-        await invoke('update_setting', { key: 'separate_agent_apps', value: newSettings.separate_agent_apps ? 'true' : 'false' });
-
-        // Update the global shortcut registration in the backend
-        await invoke('update_global_shortcut', { shortcut: newSettings.global_shortcut });
-
-        // Resize the main window based on new grid dimensions
-        await invoke('resize_main_window', {
-            gridCols: newSettings.grid_cols,
-            gridRows: newSettings.grid_rows
+        // Validated and applied by the backend, which also re-registers the shortcut,
+        // resizes the main window and toggles start at login as needed
+        await invoke('update_settings', {
+            values: {
+                theme: newSettings.theme,
+                grid_cols: newSettings.grid_cols.toString(),
+                grid_rows: newSettings.grid_rows.toString(),
+                global_shortcut: newSettings.global_shortcut,
+                start_at_login: newSettings.start_at_login ? 'true' : 'false',
+                hide_app_names: newSettings.hide_app_names ? 'true' : 'false',
+                separate_agent_apps: newSettings.separate_agent_apps ? 'true' : 'false',
+                notification_retention_days: newSettings.notification_retention_days.toString(),
+                launch_history_retention_days: newSettings.launch_history_retention_days.toString(),
+                backup_interval_hours: newSettings.backup_interval_hours.toString(),
+                backup_keep: newSettings.backup_keep.toString(),
+                backup_include_sessions: newSettings.backup_include_sessions ? 'true' : 'false'
+            }
         });
 
         // Close the window
@@ -669,6 +1601,49 @@ async function init() {
         // Update models button
         document.getElementById('update-models-btn').addEventListener('click', updateModels);
 
+        // Document folder buttons
+        document.getElementById('add-document-source-btn').addEventListener('click', addDocumentSource);
+        document.getElementById('reindex-documents-btn').addEventListener('click', reindexDocuments);
+
+        // Agent import
+        document.getElementById('import-agent-btn').addEventListener('click', importAgent);
+
+        // Desktop entry import (Linux only)
+        document.querySelectorAll('.linux-only').forEach(el => {
+            if (!isLinux) el.style.display = 'none';
+        });
+        document.getElementById('scan-desktop-entries-btn').addEventListener('click', scanDesktopEntries);
+        document.getElementById('import-desktop-entries-btn').addEventListener('click', importDesktopEntries);
+        document.getElementById('cancel-desktop-entries-btn').addEventListener('click', closeDesktopEntries);
+
+        // Saved filters
+        document.getElementById('add-filter-btn').addEventListener('click', () => openFilterEditor(null));
+        document.getElementById('save-filter-btn').addEventListener('click', saveFilter);
+        document.getElementById('cancel-filter-btn').addEventListener('click', closeFilterEditor);
+
+        // Backups
+        document.getElementById('backup-now-btn').addEventListener('click', backupNow);
+        document.getElementById('restore-backup-btn').addEventListener('click', restoreBackup);
+
+        // Configuration file
+        document.getElementById('export-config-btn').addEventListener('click', exportConfig);
+        document.getElementById('import-config-btn').addEventListener('click', importConfig);
+        document.getElementById('config-watch-btn').addEventListener('click', watchConfig);
+        document.getElementById('config-unwatch-btn').addEventListener('click', unwatchConfig);
+
+        // Secrets
+        document.getElementById('secrets-unlock-btn').addEventListener('click', unlockSecrets);
+        document.getElementById('save-secret-btn').addEventListener('click', saveSecret);
+
+        // Notification channel editor
+        document.getElementById('add-channel-btn').addEventListener('click', () => openChannelEditor(null));
+        document.getElementById('cancel-channel-btn').addEventListener('click', closeChannelEditor);
+        document.getElementById('save-channel-btn').addEventListener('click', saveChannel);
+        document.getElementById('test-channel-btn').addEventListener('click', testChannel);
+        document.getElementById('channel-kind').addEventListener('change', (e) => {
+            renderChannelFields(e.target.value);
+        });
+
         // View AI queue button
         document.getElementById('view-ai-queue-btn').addEventListener('click', async () => {
             try {
@@ -1,24 +1,229 @@
-use crate::database::{AgentApp, DbPool};
-use crate::ai::{llm_client, queue, tools};
+use crate::database::{AIQueueItem, AISettings, AgentApp, DbPool};
+use crate::ai::{conditions, llm_client, queue, tools};
 use anyhow::{anyhow, Result};
+use serde::Serialize;
+use serde_json::Value;
 use tauri::AppHandle;
 
+/// Maximum number of memory entries included in the system prompt
+const MAX_PROMPT_MEMORY_ENTRIES: usize = 50;
+
+/// Maximum length of a single memory value included in the system prompt
+const MAX_PROMPT_MEMORY_VALUE_LEN: usize = 200;
+
+/// A tool call requested by the model during a run
+#[derive(Debug, Clone, Serialize)]
+pub struct RecordedToolCall {
+    pub name: String,
+    pub arguments: Value,
+    pub result: String,
+    /// False when the call was stubbed by a dry run
+    pub executed: bool,
+}
+
+/// The outcome of a dry run or replay
+#[derive(Debug, Clone, Serialize)]
+pub struct DryRunResult {
+    /// The model used, or `None` when the agent's conditions decided without the LLM
+    pub model: Option<String>,
+    pub output: String,
+    pub decision: Option<String>,
+    pub tool_calls: Vec<RecordedToolCall>,
+}
+
+/// A stored run replayed with a different prompt or model
+#[derive(Debug, Clone, Serialize)]
+pub struct ReplayResult {
+    pub original: AIQueueItem,
+    pub replay: DryRunResult,
+}
+
+/// Input gathered before an agent run
+struct AgentInputs {
+    /// User messages describing the scraped website and input command output
+    messages: Vec<llm_client::ChatMessage>,
+    website_content: Option<String>,
+    command_output: Option<(String, Option<i32>)>,
+}
+
 /// Execute an agent
 pub fn execute_agent(pool: &DbPool, agent: &AgentApp, agent_name: Option<&str>, app_handle: &AppHandle) -> Result<String> {
-    // Get AI settings
-    let ai_settings = crate::database::get_ai_settings(pool)?;
+    let ai_settings = get_enabled_ai_settings(pool)?;
+    let (system_prompt, tool_definitions) = build_system_prompt(pool, agent)?;
+    let inputs = gather_inputs(pool, agent);
+    let decision = evaluate_conditions(agent, &inputs);
+
+    let messages = build_messages(system_prompt, inputs.messages);
+    let message_text = serde_json::to_string(&messages).unwrap_or_default();
+
+    if let Some(decision) = decision.as_ref().filter(|d| d.action != conditions::ConditionAction::Run) {
+        return finish_without_llm(pool, app_handle, agent, agent_name, &message_text, decision);
+    }
+
+    let model = resolve_model(agent, &ai_settings)?;
+
+    // Enqueue request
+    let queue_manager = queue::get_queue_manager()?;
+    let queue_id = queue_manager.enqueue(&message_text, agent_name, Some(agent.app_id))?;
+    if let Some(decision) = &decision {
+        crate::database::set_queue_item_decision(pool, queue_id, &decision.reason)?;
+    }
+    
+    // Wait for processing slot
+    while !queue_manager.can_process() {
+        std::thread::sleep(std::time::Duration::from_millis(100));
+    }
     
+    // Start processing
+    queue_manager.start_processing(queue_id)?;
+
+    let tool_context = tools::ToolContext {
+        pool,
+        app_handle,
+        agent,
+        agent_name,
+        queue_id: Some(queue_id),
+    };
+
+    match run_conversation(pool, &model, messages, tool_definitions, &tool_context, false) {
+        Ok((content, _)) => {
+            queue_manager.complete(queue_id, &content)?;
+            Ok(content)
+        }
+        Err(e) => {
+            queue_manager.fail(queue_id, &e.to_string())?;
+            Err(e)
+        }
+    }
+}
+
+/// Run an agent without side effects
+///
+/// Inputs are gathered as for a real run (the website is scraped and the input command runs),
+/// but tools that change state are recorded instead of executed and nothing is added to the
+/// queue. Read-only tools still run so the model sees realistic results.
+pub fn dry_run_agent(pool: &DbPool, agent: &AgentApp, agent_name: Option<&str>, app_handle: &AppHandle) -> Result<DryRunResult> {
+    let ai_settings = get_enabled_ai_settings(pool)?;
+    let (system_prompt, tool_definitions) = build_system_prompt(pool, agent)?;
+    let inputs = gather_inputs(pool, agent);
+    let decision = evaluate_conditions(agent, &inputs);
+
+    let tool_context = tools::ToolContext {
+        pool,
+        app_handle,
+        agent,
+        agent_name,
+        queue_id: None,
+    };
+
+    if let Some(decision) = decision.as_ref().filter(|d| d.action != conditions::ConditionAction::Run) {
+        let tool_calls = match &decision.message {
+            Some(message) => {
+                let arguments = serde_json::json!({ "message": message });
+                vec![call_tool(&tool_context, "send_notification", &arguments, true)]
+            }
+            None => Vec::new(),
+        };
+
+        return Ok(DryRunResult {
+            model: None,
+            output: decision.message.clone().unwrap_or_else(|| decision.reason.clone()),
+            decision: Some(decision.reason.clone()),
+            tool_calls,
+        });
+    }
+
+    let model = resolve_model(agent, &ai_settings)?;
+    let messages = build_messages(system_prompt, inputs.messages);
+    let (output, tool_calls) = run_conversation(pool, &model, messages, tool_definitions, &tool_context, true)?;
+
+    Ok(DryRunResult {
+        model: Some(model),
+        output,
+        decision: decision.map(|d| d.reason),
+        tool_calls,
+    })
+}
+
+/// Replay a stored run against a different prompt and/or model as a dry run
+///
+/// The recorded website and command input from the original run is reused, so nothing is
+/// scraped or executed again. Conditions are not re-evaluated.
+pub fn replay_queue_item(
+    pool: &DbPool,
+    queue_id: i64,
+    prompt: Option<&str>,
+    model: Option<&str>,
+    app_handle: &AppHandle,
+) -> Result<ReplayResult> {
+    let ai_settings = get_enabled_ai_settings(pool)?;
+
+    let original = crate::database::get_queue_item(pool, queue_id)?
+        .ok_or_else(|| anyhow!("Queue item {} not found", queue_id))?;
+    let app_id = original
+        .app_id
+        .ok_or_else(|| anyhow!("Queue item {} was not recorded with its agent", queue_id))?;
+    let mut agent = crate::database::get_agent_app(pool, app_id)?
+        .ok_or_else(|| anyhow!("The agent for queue item {} no longer exists", queue_id))?;
+
+    if let Some(prompt) = prompt {
+        agent.prompt = prompt.to_string();
+    }
+    if let Some(model) = model.filter(|m| !m.is_empty()) {
+        agent.model = Some(model.to_string());
+    }
+
+    let recorded: Vec<llm_client::ChatMessage> = serde_json::from_str(&original.message)
+        .map_err(|e| anyhow!("Failed to read the recorded transcript: {}", e))?;
+    let inputs = recorded.into_iter().filter(|m| m.role != "system").collect();
+
+    let (system_prompt, tool_definitions) = build_system_prompt(pool, &agent)?;
+    let model = resolve_model(&agent, &ai_settings)?;
+    let tool_context = tools::ToolContext {
+        pool,
+        app_handle,
+        agent: &agent,
+        agent_name: original.agent_name.as_deref(),
+        queue_id: None,
+    };
+
+    let messages = build_messages(system_prompt, inputs);
+    let (output, tool_calls) = run_conversation(pool, &model, messages, tool_definitions, &tool_context, true)?;
+
+    Ok(ReplayResult {
+        original,
+        replay: DryRunResult {
+            model: Some(model),
+            output,
+            decision: None,
+            tool_calls,
+        },
+    })
+}
+
+/// Get the AI settings, failing when AI features are disabled
+fn get_enabled_ai_settings(pool: &DbPool) -> Result<AISettings> {
+    let ai_settings = crate::database::get_ai_settings(pool)?;
+
     if !ai_settings.enabled {
         return Err(anyhow!("AI features are not enabled"));
     }
-    
-    // Determine model to use
-    let model = agent
+
+    Ok(ai_settings)
+}
+
+/// Determine the model to use for an agent
+fn resolve_model(agent: &AgentApp, ai_settings: &AISettings) -> Result<String> {
+    agent
         .model
         .as_ref()
         .or(ai_settings.default_model.as_ref())
-        .ok_or_else(|| anyhow!("No model specified and no default model set"))?;
-    
+        .cloned()
+        .ok_or_else(|| anyhow!("No model specified and no default model set"))
+}
+
+/// Build the system prompt with tool descriptions, and the tool definitions sent to the model
+fn build_system_prompt(pool: &DbPool, agent: &AgentApp) -> Result<(String, Vec<llm_client::ToolDefinition>)> {
     // Build system prompt with tool descriptions
     let mut system_prompt = agent.prompt.clone();
     
@@ -36,6 +241,20 @@ pub fn execute_agent(pool: &DbPool, agent: &AgentApp, agent_name: Option<&str>,
         tool_descriptions.push("• run_command(command: string) - Execute a system command and get its output");
     }
 
+    if agent.tool_memory {
+        tool_definitions.push(llm_client::ToolDefinition::memory_get());
+        tool_definitions.push(llm_client::ToolDefinition::memory_set());
+        tool_definitions.push(llm_client::ToolDefinition::memory_list());
+        tool_descriptions.push("• memory_get(key: string) - Read a value from your persistent memory");
+        tool_descriptions.push("• memory_set(key: string, value: string) - Store a value in your persistent memory for future runs");
+        tool_descriptions.push("• memory_list() - List everything stored in your persistent memory");
+    }
+
+    if agent.tool_search_documents {
+        tool_definitions.push(llm_client::ToolDefinition::search_documents());
+        tool_descriptions.push("• search_documents(query: string, top_k?: integer) - Search the user's indexed local notes and documents");
+    }
+
     // Note: Website scraping and input command execution are NOT tools - they are pre-executed and provided as input
     // The tool_website_scrape flag and command field are used to determine if we should execute and include output,
     // but the LLM doesn't get to choose whether to scrape/run the input command or not
@@ -69,24 +288,61 @@ pub fn execute_agent(pool: &DbPool, agent: &AgentApp, agent_name: Option<&str>,
             system_prompt.push_str("  - Need to gather system information → run_command with appropriate command\n\n");
         }
 
+        if agent.tool_memory {
+            system_prompt.push_str("MEMORY: Your memory persists between runs. Use memory_set to remember values you will need next time (e.g., the last price you saw) and memory_get or memory_list to recall them.\n");
+            system_prompt.push_str("Examples of when to use memory:\n");
+            system_prompt.push_str("  - User says 'notify me when the price drops' → memory_get({\"key\": \"last_price\"}), compare, then memory_set({\"key\": \"last_price\", \"value\": \"19.99\"})\n");
+            system_prompt.push_str("  - User says 'tell me about new articles' → memory_get the last seen article title before deciding what is new\n\n");
+        }
+
+        if agent.tool_search_documents {
+            system_prompt.push_str("DOCUMENT SEARCH: Use search_documents to look up information in the user's local notes and documents before answering questions about them.\n");
+            system_prompt.push_str("Cite the file path of the passages you rely on. If nothing relevant is found, say so instead of guessing.\n\n");
+        }
+
         system_prompt.push_str("IMPORTANT: Only use tools when necessary to fulfill the user's request or when there's critical information to report!\n");
     }
-    
-    // Build messages
+
+    // Summarise current memory into the prompt so simple agents don't need a memory_get round trip
+    if agent.memory_in_prompt {
+        let entries = crate::database::get_agent_memory(pool, agent.app_id)?;
+        system_prompt.push_str(&build_memory_summary(&entries));
+    }
+
+    Ok((system_prompt, tool_definitions))
+}
+
+/// Build the messages for a run from the system prompt and the input messages
+fn build_messages(system_prompt: String, inputs: Vec<llm_client::ChatMessage>) -> Vec<llm_client::ChatMessage> {
     let mut messages = vec![llm_client::ChatMessage {
         role: "system".to_string(),
         content: system_prompt,
     }];
-    
+    messages.extend(inputs);
+    messages
+}
+
+/// Scrape the website and run the input command configured for an agent
+///
+/// Secret references in the URL and command are resolved only for the request itself; the
+/// messages keep the references so the values never reach the transcript.
+fn gather_inputs(pool: &DbPool, agent: &AgentApp) -> AgentInputs {
+    let mut messages = Vec::new();
+
+    // Inputs the agent's conditions are evaluated against
+    let mut website_content: Option<String> = None;
+    let mut command_output: Option<(String, Option<i32>)> = None;
+
     // Add user message if website URL is provided and scrape is enabled
     if agent.tool_website_scrape {
         if let Some(url) = &agent.website_url {
-            match crate::scraper::scrape_website(url) {
+            match crate::secrets::resolve(pool, url).and_then(|resolved| crate::scraper::scrape_website(&resolved)) {
                 Ok(content) => {
                     messages.push(llm_client::ChatMessage {
                         role: "user".to_string(),
                         content: format!("Please analyze the following website content from {}:\n\n{}", url, content),
                     });
+                    website_content = Some(content);
                 }
                 Err(e) => {
                     // Continue even if scraping fails
@@ -102,8 +358,15 @@ pub fn execute_agent(pool: &DbPool, agent: &AgentApp, agent_name: Option<&str>,
         use std::process::Command;
 
         // Parse command (simple split for now, can be improved)
-        let parts: Vec<&str> = cmd.split_whitespace().collect();
-        if !parts.is_empty() {
+        let resolved = crate::secrets::resolve(pool, cmd);
+        let parts: Vec<&str> = resolved.as_deref().map(|c| c.split_whitespace().collect()).unwrap_or_default();
+        if let Err(e) = &resolved {
+            eprintln!("Failed to resolve secrets in input command '{}': {}", cmd, e);
+            messages.push(llm_client::ChatMessage {
+                role: "user".to_string(),
+                content: format!("Error executing input command '{}': {}", cmd, e),
+            });
+        } else if !parts.is_empty() {
             let program = parts[0];
             let args = &parts[1..];
 
@@ -127,6 +390,7 @@ pub fn execute_agent(pool: &DbPool, agent: &AgentApp, agent_name: Option<&str>,
                         role: "user".to_string(),
                         content: result,
                     });
+                    command_output = Some((stdout.into_owned(), output.status.code()));
                 }
                 Err(e) => {
                     // Continue even if command execution fails
@@ -140,24 +404,44 @@ pub fn execute_agent(pool: &DbPool, agent: &AgentApp, agent_name: Option<&str>,
         }
     }
 
-    // Enqueue request
-    let queue_manager = queue::get_queue_manager()?;
-    let message_text = serde_json::to_string(&messages).unwrap_or_default();
-    let queue_id = queue_manager.enqueue(&message_text, agent_name)?;
-    
-    // Wait for processing slot
-    while !queue_manager.can_process() {
-        std::thread::sleep(std::time::Duration::from_millis(100));
+
+    AgentInputs {
+        messages,
+        website_content,
+        command_output,
     }
-    
-    // Start processing
-    queue_manager.start_processing(queue_id)?;
-    
+}
+
+/// Evaluate an agent's rule-based conditions, if it has any
+fn evaluate_conditions(agent: &AgentApp, inputs: &AgentInputs) -> Option<conditions::Decision> {
+    if agent.conditions.is_empty() {
+        return None;
+    }
+
+    let condition_inputs = conditions::ConditionInputs {
+        website: inputs.website_content.as_deref(),
+        command_stdout: inputs.command_output.as_ref().map(|(stdout, _)| stdout.as_str()),
+        command_exit_code: inputs.command_output.as_ref().and_then(|(_, code)| *code),
+    };
+    let decision = conditions::evaluate(&agent.conditions, agent.conditions_default, &condition_inputs);
+    println!("[Agent] {}", decision.reason);
+    Some(decision)
+}
+
+/// Send the messages to the model, execute any tool calls and return the final answer
+fn run_conversation(
+    pool: &DbPool,
+    model: &str,
+    messages: Vec<llm_client::ChatMessage>,
+    tool_definitions: Vec<llm_client::ToolDefinition>,
+    tool_context: &tools::ToolContext,
+    dry_run: bool,
+) -> Result<(String, Vec<RecordedToolCall>)> {
     // Convert tool definitions
     let api_tools = if tool_definitions.is_empty() {
         None
     } else {
-        Some(tool_definitions.clone())
+        Some(tool_definitions)
     };
 
     // Debug: Log tool definitions
@@ -174,84 +458,150 @@ pub fn execute_agent(pool: &DbPool, agent: &AgentApp, agent_name: Option<&str>,
     let messages_clone = messages.clone();
 
     // Make LLM request
-    let response = match llm_client::chat_completion(pool, model, messages, api_tools) {
-        Ok(resp) => resp,
-        Err(e) => {
-            queue_manager.fail(queue_id, &format!("LLM request failed: {}", e))?;
-            return Err(e);
-        }
+    let response = llm_client::chat_completion(pool, model, messages, api_tools)
+        .map_err(|e| anyhow!("LLM request failed: {}", e))?;
+
+    let choice = response
+        .choices
+        .first()
+        .ok_or_else(|| anyhow!("No choices in response"))?;
+
+    // Debug: Log the response
+    println!("[Agent] LLM Response - Content: {}", crate::secrets::redact(pool, &format!("{:?}", choice.message.content)));
+    println!("[Agent] LLM Response - Tool Calls: {}", crate::secrets::redact(pool, &format!("{:?}", choice.message.tool_calls)));
+
+    let Some(ref tool_calls) = choice.message.tool_calls else {
+        // No tool calls, return content directly
+        println!("[Agent] No tool calls detected, returning content directly");
+        return Ok((choice.message.content.clone().unwrap_or_default(), Vec::new()));
     };
-    
-    // Process response
-    if let Some(choice) = response.choices.first() {
-        // Debug: Log the response
-        println!("[Agent] LLM Response - Content: {:?}", choice.message.content);
-        println!("[Agent] LLM Response - Tool Calls: {:?}", choice.message.tool_calls);
-
-        // Check for tool calls
-        if let Some(ref tool_calls) = choice.message.tool_calls {
-            println!("[Agent] Processing {} tool calls", tool_calls.len());
-
-            // Execute tools and continue conversation
-            let mut tool_results = Vec::new();
-
-            for tool_call in tool_calls {
-                let function_name = &tool_call.function.name;
-                let arguments: serde_json::Value = serde_json::from_str(&tool_call.function.arguments)
-                    .unwrap_or_else(|_| serde_json::json!({}));
-
-                println!("[Agent] Executing tool: {} with args: {}", function_name, arguments);
-
-                match tools::execute_tool(pool, app_handle, function_name, &arguments) {
-                    Ok(result) => {
-                        println!("[Agent] Tool execution success: {}", result);
-                        // Format tool result message (OpenAI format: role="tool", content=result)
-                        tool_results.push(llm_client::ChatMessage {
-                            role: "tool".to_string(),
-                            content: result,
-                        });
-                    }
-                    Err(e) => {
-                        println!("[Agent] Tool execution error: {}", e);
-                        // Format tool error message
-                        tool_results.push(llm_client::ChatMessage {
-                            role: "tool".to_string(),
-                            content: format!("Error: {}", e),
-                        });
-                    }
-                }
+
+    println!("[Agent] Processing {} tool calls", tool_calls.len());
+
+    // Execute tools and continue conversation
+    let mut recorded = Vec::new();
+    for tool_call in tool_calls {
+        let arguments: Value = serde_json::from_str(&tool_call.function.arguments)
+            .unwrap_or_else(|_| serde_json::json!({}));
+        recorded.push(call_tool(tool_context, &tool_call.function.name, &arguments, dry_run));
+    }
+
+    // Add tool results to messages and make another request
+    let mut final_messages = messages_clone;
+    final_messages.push(llm_client::ChatMessage {
+        role: "assistant".to_string(),
+        content: choice.message.content.clone().unwrap_or_default(),
+    });
+    // Format tool result messages (OpenAI format: role="tool", content=result)
+    final_messages.extend(recorded.iter().map(|call| llm_client::ChatMessage {
+        role: "tool".to_string(),
+        content: call.result.clone(),
+    }));
+
+    // Make final request
+    let final_response = llm_client::chat_completion(pool, model, final_messages, None)?;
+    let final_choice = final_response
+        .choices
+        .first()
+        .ok_or_else(|| anyhow!("No response from LLM"))?;
+
+    let final_content = final_choice.message.content.clone().unwrap_or_default();
+    println!("[Agent] Final response after tool execution: {}", crate::secrets::redact(pool, &final_content));
+    Ok((final_content, recorded))
+}
+
+/// Execute a tool call, or record it without executing it during a dry run
+fn call_tool(tool_context: &tools::ToolContext, name: &str, arguments: &Value, dry_run: bool) -> RecordedToolCall {
+    let executed = !dry_run || tools::is_read_only(name);
+
+    let result = if executed {
+        println!("[Agent] Executing tool: {} with args: {}", name, arguments);
+        match tools::execute_tool(tool_context, name, arguments) {
+            Ok(result) => {
+                println!("[Agent] Tool execution success: {}", crate::secrets::redact(tool_context.pool, &result));
+                result
             }
-            
-            // Add tool results to messages and make another request
-            let mut final_messages = messages_clone;
-            final_messages.push(llm_client::ChatMessage {
-                role: "assistant".to_string(),
-                content: choice.message.content.clone().unwrap_or_default(),
-            });
-            final_messages.extend(tool_results);
-            
-            // Make final request
-            let final_response = llm_client::chat_completion(pool, model, final_messages, None)?;
-            
-            if let Some(final_choice) = final_response.choices.first() {
-                let final_content = final_choice.message.content.clone().unwrap_or_default();
-                println!("[Agent] Final response after tool execution: {}", final_content);
-                queue_manager.complete(queue_id, &final_content)?;
-                Ok(final_content)
-            } else {
-                queue_manager.fail(queue_id, "No response from LLM")?;
-                Err(anyhow!("No response from LLM"))
+            Err(e) => {
+                println!("[Agent] Tool execution error: {}", e);
+                format!("Error: {}", e)
             }
-        } else {
-            // No tool calls, return content directly
-            println!("[Agent] No tool calls detected, returning content directly");
-            let content = choice.message.content.clone().unwrap_or_default();
-            queue_manager.complete(queue_id, &content)?;
-            Ok(content)
         }
     } else {
-        queue_manager.fail(queue_id, "No choices in response")?;
-        Err(anyhow!("No choices in response"))
+        println!("[Agent] Dry run, recording tool: {} with args: {}", name, arguments);
+        format!("Dry run: {} was recorded but not executed. Assume it succeeded.", name)
+    };
+
+    RecordedToolCall {
+        name: name.to_string(),
+        arguments: arguments.clone(),
+        result,
+        executed,
     }
 }
 
+/// Record a run that was decided by the agent's conditions, sending the notification if needed
+///
+/// These runs never take an LLM processing slot, so they bypass the queue manager and are
+/// written to the queue history directly.
+fn finish_without_llm(
+    pool: &DbPool,
+    app_handle: &AppHandle,
+    agent: &AgentApp,
+    agent_name: Option<&str>,
+    message_text: &str,
+    decision: &conditions::Decision,
+) -> Result<String> {
+    let queue_id = crate::database::add_queue_item(pool, message_text, agent_name, Some(agent.app_id))?;
+    crate::database::set_queue_item_decision(pool, queue_id, &decision.reason)?;
+
+    match (decision.action, &decision.message) {
+        (conditions::ConditionAction::Notify, Some(message)) => {
+            let tool_context = tools::ToolContext {
+                pool,
+                app_handle,
+                agent,
+                agent_name,
+                queue_id: Some(queue_id),
+            };
+
+            if let Err(e) = tools::execute_tool(&tool_context, "send_notification", &serde_json::json!({ "message": message })) {
+                let error = format!("Failed to send notification: {}", e);
+                crate::database::update_queue_item_status(pool, queue_id, "failed", Some(&error))?;
+                return Err(anyhow!(error));
+            }
+
+            crate::database::update_queue_item_status(pool, queue_id, "completed", Some(message))?;
+            Ok(message.clone())
+        }
+        _ => {
+            crate::database::update_queue_item_status(pool, queue_id, "skipped", Some(&decision.reason))?;
+            Ok(decision.reason.clone())
+        }
+    }
+}
+
+/// Build the memory section appended to the system prompt
+fn build_memory_summary(entries: &[crate::database::AgentMemoryEntry]) -> String {
+    let mut summary = String::from("\n\n=== YOUR MEMORY (from previous runs) ===\n");
+
+    if entries.is_empty() {
+        summary.push_str("(empty)\n");
+        return summary;
+    }
+
+    for entry in entries.iter().take(MAX_PROMPT_MEMORY_ENTRIES) {
+        let value = if entry.value.chars().count() > MAX_PROMPT_MEMORY_VALUE_LEN {
+            let truncated: String = entry.value.chars().take(MAX_PROMPT_MEMORY_VALUE_LEN).collect();
+            format!("{}…", truncated)
+        } else {
+            entry.value.clone()
+        };
+        summary.push_str(&format!("{} = {}\n", entry.key, value));
+    }
+
+    if entries.len() > MAX_PROMPT_MEMORY_ENTRIES {
+        summary.push_str(&format!("... and {} more entries\n", entries.len() - MAX_PROMPT_MEMORY_ENTRIES));
+    }
+
+    summary
+}
@@ -0,0 +1,388 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// Where a condition reads its input from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConditionSource {
+    /// Scraped website text
+    Website,
+    /// Output (stdout) of the input command
+    Command,
+}
+
+/// Comparison operator for exit code and numeric checks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl CompareOp {
+    fn compare(self, left: f64, right: f64) -> bool {
+        match self {
+            CompareOp::Eq => left == right,
+            CompareOp::Ne => left != right,
+            CompareOp::Lt => left < right,
+            CompareOp::Le => left <= right,
+            CompareOp::Gt => left > right,
+            CompareOp::Ge => left >= right,
+        }
+    }
+
+    fn symbol(self) -> &'static str {
+        match self {
+            CompareOp::Eq => "==",
+            CompareOp::Ne => "!=",
+            CompareOp::Lt => "<",
+            CompareOp::Le => "<=",
+            CompareOp::Gt => ">",
+            CompareOp::Ge => ">=",
+        }
+    }
+}
+
+/// What happens when a condition holds (or, for the default, when none does)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConditionAction {
+    /// Run the agent normally (call the LLM)
+    #[default]
+    Run,
+    /// Skip the run without calling the LLM
+    Skip,
+    /// Send the condition's message as a notification without calling the LLM
+    Notify,
+}
+
+impl ConditionAction {
+    /// Parse a stored action name, falling back to `Run` for unknown values
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "skip" => ConditionAction::Skip,
+            "notify" => ConditionAction::Notify,
+            _ => ConditionAction::Run,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConditionAction::Run => "run",
+            ConditionAction::Skip => "skip",
+            ConditionAction::Notify => "notify",
+        }
+    }
+}
+
+/// The test a condition applies to its input
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "check", rename_all = "camelCase")]
+pub enum ConditionCheck {
+    /// The input matches the regex
+    Matches { pattern: String },
+    /// The input does not match the regex
+    NotMatches { pattern: String },
+    /// The input command's exit code compares to `value`
+    ExitCode { op: CompareOp, value: i64 },
+    /// The number captured by the regex (first group, or whole match) compares to `value`
+    Number { pattern: String, op: CompareOp, value: f64 },
+}
+
+/// A rule evaluated before the LLM is called
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AgentCondition {
+    pub source: ConditionSource,
+    #[serde(flatten)]
+    pub check: ConditionCheck,
+    pub action: ConditionAction,
+    /// Notification text for `Notify`; `{{match}}` is replaced with the matched text
+    #[serde(default)]
+    pub message: Option<String>,
+}
+
+/// Inputs gathered for an agent run
+#[derive(Debug, Default)]
+pub struct ConditionInputs<'a> {
+    pub website: Option<&'a str>,
+    pub command_stdout: Option<&'a str>,
+    pub command_exit_code: Option<i32>,
+}
+
+/// The outcome of evaluating an agent's conditions
+#[derive(Debug, Clone, PartialEq)]
+pub struct Decision {
+    pub action: ConditionAction,
+    /// Human readable explanation, recorded on the queue item
+    pub reason: String,
+    /// Notification text when the action is `Notify`
+    pub message: Option<String>,
+}
+
+/// Evaluate conditions in order; the first one that holds decides, otherwise `default` applies.
+///
+/// A condition whose input is unavailable (no website configured, scrape or command failed)
+/// never holds.
+pub fn evaluate(conditions: &[AgentCondition], default: ConditionAction, inputs: &ConditionInputs) -> Decision {
+    for (index, condition) in conditions.iter().enumerate() {
+        let description = describe(condition);
+
+        match check(condition, inputs) {
+            Ok(Some(matched)) => {
+                let message = (condition.action == ConditionAction::Notify).then(|| {
+                    condition
+                        .message
+                        .as_deref()
+                        .filter(|m| !m.trim().is_empty())
+                        .map(|m| m.replace("{{match}}", &matched))
+                        .unwrap_or_else(|| format!("Condition met: {}", description))
+                });
+
+                return Decision {
+                    action: condition.action,
+                    reason: format!("Condition {} held ({}): {}", index + 1, description, action_label(condition.action)),
+                    message,
+                };
+            }
+            Ok(None) => {}
+            Err(e) => eprintln!("[Agent] Skipping invalid condition {} ({}): {}", index + 1, description, e),
+        }
+    }
+
+    Decision {
+        action: default,
+        reason: if conditions.is_empty() {
+            format!("No conditions: {}", action_label(default))
+        } else {
+            format!("No condition held: {}", action_label(default))
+        },
+        message: None,
+    }
+}
+
+/// Check that every condition's regex compiles, naming the first one that doesn't
+pub fn validate(conditions: &[AgentCondition]) -> anyhow::Result<()> {
+    for (index, condition) in conditions.iter().enumerate() {
+        let pattern = match &condition.check {
+            ConditionCheck::Matches { pattern }
+            | ConditionCheck::NotMatches { pattern }
+            | ConditionCheck::Number { pattern, .. } => pattern,
+            ConditionCheck::ExitCode { .. } => continue,
+        };
+
+        if let Err(e) = Regex::new(pattern) {
+            anyhow::bail!("condition {} has an invalid pattern /{}/: {}", index + 1, pattern, e);
+        }
+    }
+
+    Ok(())
+}
+
+/// Check a single condition, returning the matched text when it holds
+fn check(condition: &AgentCondition, inputs: &ConditionInputs) -> Result<Option<String>, regex::Error> {
+    let text = match condition.source {
+        ConditionSource::Website => inputs.website,
+        ConditionSource::Command => inputs.command_stdout,
+    };
+
+    match &condition.check {
+        ConditionCheck::Matches { pattern } => {
+            let Some(text) = text else { return Ok(None) };
+            Ok(Regex::new(pattern)?.find(text).map(|m| m.as_str().to_string()))
+        }
+        ConditionCheck::NotMatches { pattern } => {
+            let Some(text) = text else { return Ok(None) };
+            Ok((!Regex::new(pattern)?.is_match(text)).then(String::new))
+        }
+        ConditionCheck::ExitCode { op, value } => {
+            if condition.source != ConditionSource::Command {
+                return Ok(None);
+            }
+            let Some(code) = inputs.command_exit_code else { return Ok(None) };
+            Ok(op.compare(code as f64, *value as f64).then(|| code.to_string()))
+        }
+        ConditionCheck::Number { pattern, op, value } => {
+            let Some(text) = text else { return Ok(None) };
+            let Some(captures) = Regex::new(pattern)?.captures(text) else { return Ok(None) };
+            let raw = captures.get(1).or_else(|| captures.get(0)).map(|m| m.as_str()).unwrap_or_default();
+            Ok(parse_number(raw)
+                .filter(|number| op.compare(*number, *value))
+                .map(|_| raw.trim().to_string()))
+        }
+    }
+}
+
+/// Parse a number as it appears on a web page ("1,299.00", "17,49", "€ 20")
+fn parse_number(raw: &str) -> Option<f64> {
+    let cleaned: String = raw
+        .chars()
+        .filter(|c| c.is_ascii_digit() || matches!(c, '.' | ',' | '-'))
+        .collect();
+
+    // With both separators present the comma groups thousands; on its own it is a decimal comma
+    let normalized = if cleaned.contains('.') {
+        cleaned.replace(',', "")
+    } else {
+        cleaned.replace(',', ".")
+    };
+
+    normalized.parse().ok()
+}
+
+fn describe(condition: &AgentCondition) -> String {
+    let source = match condition.source {
+        ConditionSource::Website => "website",
+        ConditionSource::Command => "command output",
+    };
+
+    match &condition.check {
+        ConditionCheck::Matches { pattern } => format!("{} matches /{}/", source, pattern),
+        ConditionCheck::NotMatches { pattern } => format!("{} does not match /{}/", source, pattern),
+        ConditionCheck::ExitCode { op, value } => format!("exit code {} {}", op.symbol(), value),
+        ConditionCheck::Number { pattern, op, value } => {
+            format!("number /{}/ in {} {} {}", pattern, source, op.symbol(), value)
+        }
+    }
+}
+
+fn action_label(action: ConditionAction) -> &'static str {
+    match action {
+        ConditionAction::Run => "ran the LLM",
+        ConditionAction::Skip => "skipped the run",
+        ConditionAction::Notify => "notified without the LLM",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn condition(source: ConditionSource, check: ConditionCheck, action: ConditionAction) -> AgentCondition {
+        AgentCondition { source, check, action, message: None }
+    }
+
+    #[test]
+    fn test_first_matching_condition_decides() {
+        let conditions = vec![
+            condition(
+                ConditionSource::Website,
+                ConditionCheck::Matches { pattern: "(?i)sold out".to_string() },
+                ConditionAction::Skip,
+            ),
+            AgentCondition {
+                message: Some("Back in stock: {{match}}".to_string()),
+                ..condition(
+                    ConditionSource::Website,
+                    ConditionCheck::Matches { pattern: r"In stock \(\d+\)".to_string() },
+                    ConditionAction::Notify,
+                )
+            },
+        ];
+        let inputs = ConditionInputs { website: Some("Widget - In stock (3)"), ..Default::default() };
+
+        let decision = evaluate(&conditions, ConditionAction::Skip, &inputs);
+        assert_eq!(decision.action, ConditionAction::Notify);
+        assert_eq!(decision.message.as_deref(), Some("Back in stock: In stock (3)"));
+        assert!(decision.reason.starts_with("Condition 2 held"));
+
+        let inputs = ConditionInputs { website: Some("Widget - Sold Out"), ..Default::default() };
+        assert_eq!(evaluate(&conditions, ConditionAction::Run, &inputs).action, ConditionAction::Skip);
+    }
+
+    #[test]
+    fn test_default_applies_when_nothing_holds() {
+        let conditions = vec![condition(
+            ConditionSource::Website,
+            ConditionCheck::Matches { pattern: "In stock".to_string() },
+            ConditionAction::Notify,
+        )];
+        let inputs = ConditionInputs { website: Some("Unavailable"), ..Default::default() };
+
+        let decision = evaluate(&conditions, ConditionAction::Skip, &inputs);
+        assert_eq!(decision.action, ConditionAction::Skip);
+        assert_eq!(decision.reason, "No condition held: skipped the run");
+
+        assert_eq!(evaluate(&[], ConditionAction::Run, &inputs).action, ConditionAction::Run);
+    }
+
+    #[test]
+    fn test_missing_input_never_holds() {
+        let conditions = vec![condition(
+            ConditionSource::Website,
+            ConditionCheck::NotMatches { pattern: "In stock".to_string() },
+            ConditionAction::Skip,
+        )];
+
+        let decision = evaluate(&conditions, ConditionAction::Run, &ConditionInputs::default());
+        assert_eq!(decision.action, ConditionAction::Run);
+    }
+
+    #[test]
+    fn test_exit_code_check() {
+        let conditions = vec![condition(
+            ConditionSource::Command,
+            ConditionCheck::ExitCode { op: CompareOp::Ne, value: 0 },
+            ConditionAction::Skip,
+        )];
+
+        let failed = ConditionInputs { command_stdout: Some(""), command_exit_code: Some(2), ..Default::default() };
+        assert_eq!(evaluate(&conditions, ConditionAction::Run, &failed).action, ConditionAction::Skip);
+
+        let ok = ConditionInputs { command_stdout: Some(""), command_exit_code: Some(0), ..Default::default() };
+        assert_eq!(evaluate(&conditions, ConditionAction::Run, &ok).action, ConditionAction::Run);
+    }
+
+    #[test]
+    fn test_numeric_comparison() {
+        let conditions = vec![AgentCondition {
+            message: Some("Price dropped to {{match}}".to_string()),
+            ..condition(
+                ConditionSource::Website,
+                ConditionCheck::Number { pattern: r"Price: \$?([\d.,]+)".to_string(), op: CompareOp::Lt, value: 20.0 },
+                ConditionAction::Notify,
+            )
+        }];
+
+        let cheap = ConditionInputs { website: Some("Price: $17.49"), ..Default::default() };
+        let decision = evaluate(&conditions, ConditionAction::Skip, &cheap);
+        assert_eq!(decision.action, ConditionAction::Notify);
+        assert_eq!(decision.message.as_deref(), Some("Price dropped to 17.49"));
+
+        let expensive = ConditionInputs { website: Some("Price: $1,299.00"), ..Default::default() };
+        assert_eq!(evaluate(&conditions, ConditionAction::Skip, &expensive).action, ConditionAction::Skip);
+    }
+
+    #[test]
+    fn test_parse_number() {
+        assert_eq!(parse_number("1,299.00"), Some(1299.0));
+        assert_eq!(parse_number("17,49"), Some(17.49));
+        assert_eq!(parse_number("€ 20"), Some(20.0));
+        assert_eq!(parse_number("n/a"), None);
+    }
+
+    #[test]
+    fn test_invalid_regex_is_ignored() {
+        let conditions = vec![condition(
+            ConditionSource::Website,
+            ConditionCheck::Matches { pattern: "(".to_string() },
+            ConditionAction::Skip,
+        )];
+        let inputs = ConditionInputs { website: Some("anything"), ..Default::default() };
+
+        assert_eq!(evaluate(&conditions, ConditionAction::Run, &inputs).action, ConditionAction::Run);
+        assert!(validate(&conditions).unwrap_err().to_string().starts_with("condition 1 has an invalid pattern"));
+    }
+
+    #[test]
+    fn test_condition_json_format() {
+        let json = r#"{"source":"website","check":"number","pattern":"(\\d+)","op":"lt","value":20,"action":"notify","message":"Cheap!"}"#;
+        let parsed: AgentCondition = serde_json::from_str(json).unwrap();
+        assert_eq!(parsed.action, ConditionAction::Notify);
+        assert!(matches!(parsed.check, ConditionCheck::Number { op: CompareOp::Lt, .. }));
+    }
+}
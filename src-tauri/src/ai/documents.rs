@@ -0,0 +1,410 @@
+use crate::database::{DbPool, DocumentSource};
+use crate::ai::llm_client;
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, UNIX_EPOCH};
+
+/// Target size of a chunk in characters (~400 tokens)
+const CHUNK_CHARS: usize = 1600;
+
+/// Characters carried over from the end of one chunk into the next
+const CHUNK_OVERLAP: usize = 200;
+
+/// Number of chunks sent to the embeddings endpoint per request
+const EMBEDDING_BATCH_SIZE: usize = 16;
+
+/// Files larger than this are skipped (10 MB)
+const MAX_FILE_SIZE: u64 = 10 * 1024 * 1024;
+
+/// How deep to descend into a document source directory
+const MAX_DEPTH: usize = 16;
+
+/// How often the background indexer looks for changed files
+const REINDEX_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Set while an indexing run is in progress so runs never overlap
+static INDEXING: AtomicBool = AtomicBool::new(false);
+
+/// Summary of an indexing run
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct IndexStats {
+    pub files_indexed: usize,
+    pub files_unchanged: usize,
+    pub files_removed: usize,
+    pub chunks_indexed: usize,
+    pub errors: Vec<String>,
+}
+
+/// A passage returned by a document search
+#[derive(Debug, Clone, Serialize)]
+pub struct DocumentMatch {
+    pub path: String,
+    pub chunk_index: i64,
+    pub content: String,
+    pub score: f32,
+}
+
+/// Index all enabled document sources, re-embedding only new or changed files
+pub fn index_all(pool: &DbPool) -> Result<IndexStats> {
+    if INDEXING.swap(true, Ordering::SeqCst) {
+        return Err(anyhow!("Document indexing is already running"));
+    }
+
+    let result = (|| {
+        let model = embedding_model(pool)?;
+        let mut stats = IndexStats::default();
+
+        for source in crate::database::get_document_sources(pool)? {
+            if !source.enabled {
+                continue;
+            }
+            if let Err(e) = index_source(pool, &model, &source, &mut stats) {
+                stats.errors.push(format!("{}: {}", source.path, e));
+            }
+        }
+
+        Ok(stats)
+    })();
+
+    INDEXING.store(false, Ordering::SeqCst);
+    result
+}
+
+/// Index a single document source
+fn index_source(pool: &DbPool, model: &str, source: &DocumentSource, stats: &mut IndexStats) -> Result<()> {
+    let root = Path::new(&source.path);
+    if !root.is_dir() {
+        return Err(anyhow!("Directory does not exist"));
+    }
+
+    let mut files = Vec::new();
+    collect_files(root, 0, &mut files);
+
+    let mut known: HashMap<String, crate::database::DocumentFile> = crate::database::get_document_files(pool, source.id)?
+        .into_iter()
+        .map(|f| (f.path.clone(), f))
+        .collect();
+
+    for path in files {
+        let path_str = path.to_string_lossy().to_string();
+        let metadata = match std::fs::metadata(&path) {
+            Ok(m) => m,
+            Err(e) => {
+                stats.errors.push(format!("{}: {}", path_str, e));
+                continue;
+            }
+        };
+        let modified_at = metadata
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or(0);
+        let size = metadata.len() as i64;
+
+        // Skip files that haven't changed since they were last indexed
+        if let Some(existing) = known.remove(&path_str) {
+            if existing.modified_at == modified_at && existing.size == size {
+                stats.files_unchanged += 1;
+                continue;
+            }
+        }
+
+        match index_file(pool, model, source.id, &path, modified_at, size) {
+            Ok(chunk_count) => {
+                stats.files_indexed += 1;
+                stats.chunks_indexed += chunk_count;
+            }
+            Err(e) => stats.errors.push(format!("{}: {}", path_str, e)),
+        }
+    }
+
+    // Anything left in `known` no longer exists on disk
+    for (_, file) in known {
+        crate::database::delete_document_file(pool, file.id)?;
+        stats.files_removed += 1;
+    }
+
+    crate::database::mark_document_source_indexed(pool, source.id)?;
+    Ok(())
+}
+
+/// Extract, chunk and embed a single file; returns the number of chunks stored
+fn index_file(pool: &DbPool, model: &str, source_id: i64, path: &Path, modified_at: i64, size: i64) -> Result<usize> {
+    let text = extract_text(path)?;
+    let chunks = chunk_text(&text);
+
+    let mut embedded = Vec::with_capacity(chunks.len());
+    for batch in chunks.chunks(EMBEDDING_BATCH_SIZE) {
+        let vectors = llm_client::embeddings(pool, model, batch)?;
+        embedded.extend(batch.iter().cloned().zip(vectors));
+    }
+
+    // Files without any text are still recorded so they aren't re-read every run
+    crate::database::save_document_file(pool, source_id, &path.to_string_lossy(), modified_at, size, &embedded)?;
+
+    Ok(embedded.len())
+}
+
+/// Search indexed documents for the passages most similar to the query
+pub fn search(pool: &DbPool, query: &str, top_k: usize) -> Result<Vec<DocumentMatch>> {
+    let query = query.trim();
+    if query.is_empty() {
+        return Err(anyhow!("Search query must not be empty"));
+    }
+
+    let model = embedding_model(pool)?;
+    let query_embedding = llm_client::embeddings(pool, &model, &[query.to_string()])?
+        .into_iter()
+        .next()
+        .ok_or_else(|| anyhow!("No embedding returned for query"))?;
+
+    let mut matches: Vec<DocumentMatch> = crate::database::get_document_chunks(pool)?
+        .into_iter()
+        .map(|chunk| DocumentMatch {
+            score: cosine_similarity(&query_embedding, &chunk.embedding),
+            path: chunk.path,
+            chunk_index: chunk.chunk_index,
+            content: chunk.content,
+        })
+        .collect();
+
+    matches.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+    matches.truncate(top_k);
+
+    Ok(matches)
+}
+
+/// Start a background thread that periodically re-indexes changed files
+pub fn start_background_indexer(pool: DbPool) {
+    std::thread::spawn(move || {
+        loop {
+            std::thread::sleep(REINDEX_INTERVAL);
+
+            // Only index when AI is enabled and an embedding model is configured
+            let ready = crate::database::get_ai_settings(&pool)
+                .map(|s| s.enabled && s.embedding_model.is_some())
+                .unwrap_or(false);
+            if !ready {
+                continue;
+            }
+
+            match index_all(&pool) {
+                Ok(stats) => {
+                    if stats.files_indexed > 0 || stats.files_removed > 0 {
+                        println!(
+                            "[Documents] Re-indexed {} files ({} chunks), removed {}",
+                            stats.files_indexed, stats.chunks_indexed, stats.files_removed
+                        );
+                    }
+                    for error in stats.errors {
+                        eprintln!("[Documents] {}", error);
+                    }
+                }
+                Err(e) => eprintln!("[Documents] Indexing failed: {}", e),
+            }
+        }
+    });
+}
+
+/// Get the configured embedding model
+fn embedding_model(pool: &DbPool) -> Result<String> {
+    crate::database::get_ai_settings(pool)?
+        .embedding_model
+        .ok_or_else(|| anyhow!("No embedding model configured"))
+}
+
+/// Recursively collect supported files, skipping hidden entries and symlinked directories
+fn collect_files(dir: &Path, depth: usize, files: &mut Vec<PathBuf>) {
+    if depth > MAX_DEPTH {
+        return;
+    }
+
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let hidden = entry.file_name().to_string_lossy().starts_with('.');
+        let file_type = match entry.file_type() {
+            Ok(t) => t,
+            Err(_) => continue,
+        };
+
+        if hidden {
+            continue;
+        }
+
+        if file_type.is_dir() {
+            collect_files(&path, depth + 1, files);
+        } else if is_supported(&path) {
+            let small_enough = std::fs::metadata(&path)
+                .map(|m| m.len() <= MAX_FILE_SIZE)
+                .unwrap_or(false);
+            if small_enough {
+                files.push(path);
+            }
+        }
+    }
+}
+
+/// Check whether a file has a supported extension
+fn is_supported(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|e| e.to_str()).map(|e| e.to_lowercase()).as_deref(),
+        Some("md" | "markdown" | "txt" | "text" | "pdf")
+    )
+}
+
+/// Extract plain text from a supported file
+fn extract_text(path: &Path) -> Result<String> {
+    let is_pdf = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.eq_ignore_ascii_case("pdf"))
+        .unwrap_or(false);
+
+    if is_pdf {
+        // pdf-extract panics on some malformed files, so contain it
+        let path = path.to_path_buf();
+        return std::panic::catch_unwind(move || pdf_extract::extract_text(&path))
+            .map_err(|_| anyhow!("PDF parser crashed"))?
+            .map_err(|e| anyhow!("Failed to extract PDF text: {}", e));
+    }
+
+    let bytes = std::fs::read(path)?;
+    Ok(String::from_utf8_lossy(&bytes).to_string())
+}
+
+/// Split text into overlapping chunks, preferring paragraph and word boundaries
+pub fn chunk_text(text: &str) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+
+    for paragraph in text.split("\n\n").map(str::trim).filter(|p| !p.is_empty()) {
+        for piece in split_long(paragraph) {
+            if !current.is_empty() && current.chars().count() + piece.chars().count() + 2 > CHUNK_CHARS {
+                let overlap = tail_at_word_boundary(&current, CHUNK_OVERLAP);
+                chunks.push(std::mem::take(&mut current));
+                current = overlap;
+            }
+            if !current.is_empty() {
+                current.push_str("\n\n");
+            }
+            current.push_str(&piece);
+        }
+    }
+
+    if !current.trim().is_empty() {
+        chunks.push(current);
+    }
+
+    chunks
+}
+
+/// Split a paragraph that is longer than a chunk at word boundaries
+fn split_long(paragraph: &str) -> Vec<String> {
+    let limit = CHUNK_CHARS - CHUNK_OVERLAP;
+    if paragraph.chars().count() <= limit {
+        return vec![paragraph.to_string()];
+    }
+
+    let mut pieces = Vec::new();
+    let mut piece = String::new();
+    for word in paragraph.split_whitespace() {
+        if !piece.is_empty() && piece.chars().count() + word.chars().count() + 1 > limit {
+            pieces.push(std::mem::take(&mut piece));
+        }
+        if !piece.is_empty() {
+            piece.push(' ');
+        }
+        piece.push_str(word);
+    }
+    if !piece.is_empty() {
+        pieces.push(piece);
+    }
+
+    pieces
+}
+
+/// Return roughly the last `max_chars` characters of `text`, starting at a word boundary
+fn tail_at_word_boundary(text: &str, max_chars: usize) -> String {
+    let char_count = text.chars().count();
+    if char_count <= max_chars {
+        return text.to_string();
+    }
+
+    let tail: String = text.chars().skip(char_count - max_chars).collect();
+    match tail.find(char::is_whitespace) {
+        Some(pos) => tail[pos..].trim_start().to_string(),
+        None => tail,
+    }
+}
+
+/// Cosine similarity of two vectors (0.0 if either is empty or they differ in length)
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() || a.is_empty() {
+        return 0.0;
+    }
+
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a: f32 = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b: f32 = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a * norm_b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chunk_text_keeps_short_text_whole() {
+        let chunks = chunk_text("# Title\n\nFirst paragraph.\n\nSecond paragraph.");
+        assert_eq!(chunks, vec!["# Title\n\nFirst paragraph.\n\nSecond paragraph."]);
+    }
+
+    #[test]
+    fn test_chunk_text_splits_and_overlaps() {
+        let paragraph = "word ".repeat(250);
+        let text = format!("{}\n\n{}\n\n{}", paragraph, paragraph, paragraph);
+        let chunks = chunk_text(&text);
+
+        assert!(chunks.len() > 1);
+        for chunk in &chunks {
+            assert!(chunk.chars().count() <= CHUNK_CHARS + CHUNK_OVERLAP);
+        }
+        // Each chunk after the first starts with the tail of the previous one
+        assert!(chunks[1].starts_with("word"));
+    }
+
+    #[test]
+    fn test_chunk_text_ignores_blank_input() {
+        assert!(chunk_text("\n\n   \n\n").is_empty());
+    }
+
+    #[test]
+    fn test_cosine_similarity() {
+        assert!((cosine_similarity(&[1.0, 0.0], &[1.0, 0.0]) - 1.0).abs() < 1e-6);
+        assert!(cosine_similarity(&[1.0, 0.0], &[0.0, 1.0]).abs() < 1e-6);
+        assert_eq!(cosine_similarity(&[1.0], &[1.0, 2.0]), 0.0);
+        assert_eq!(cosine_similarity(&[0.0, 0.0], &[1.0, 2.0]), 0.0);
+    }
+
+    #[test]
+    fn test_is_supported() {
+        assert!(is_supported(Path::new("/notes/todo.md")));
+        assert!(is_supported(Path::new("/notes/Report.PDF")));
+        assert!(!is_supported(Path::new("/notes/image.png")));
+        assert!(!is_supported(Path::new("/notes/README")));
+    }
+}
@@ -15,6 +15,26 @@ struct ModelData {
     created: Option<i64>,
 }
 
+/// Embeddings request
+#[derive(Debug, Serialize)]
+struct EmbeddingsRequest<'a> {
+    model: &'a str,
+    input: &'a [String],
+}
+
+/// OpenAI-compatible embeddings response
+#[derive(Debug, Deserialize)]
+struct EmbeddingsResponse {
+    data: Vec<EmbeddingData>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EmbeddingData {
+    embedding: Vec<f32>,
+    #[serde(default)]
+    index: usize,
+}
+
 /// Chat completion request
 #[derive(Debug, Serialize)]
 struct ChatCompletionRequest {
@@ -100,7 +120,8 @@ pub fn fetch_models_from_endpoint(pool: &DbPool, endpoint_url: &str, api_key: &s
         .timeout(std::time::Duration::from_secs(30))
         .build()?;
     let mut request = client.get(&url);
-    
+
+    let api_key = crate::secrets::resolve(pool, api_key)?;
     if !api_key.is_empty() {
         request = request.header("Authorization", format!("Bearer {}", api_key));
     }
@@ -151,8 +172,9 @@ pub fn chat_completion(
         .build()?;
     let mut request_builder = client.post(&url);
     
-    if !settings.api_key.is_empty() {
-        request_builder = request_builder.header("Authorization", format!("Bearer {}", settings.api_key));
+    let api_key = crate::secrets::resolve(pool, &settings.api_key)?;
+    if !api_key.is_empty() {
+        request_builder = request_builder.header("Authorization", format!("Bearer {}", api_key));
     }
     
     // Convert tool definitions to API format
@@ -192,6 +214,55 @@ pub fn chat_completion(
     Ok(completion)
 }
 
+/// Compute embeddings for a batch of inputs using the configured endpoint
+/// Returns one vector per input, in input order
+pub fn embeddings(pool: &DbPool, model: &str, inputs: &[String]) -> Result<Vec<Vec<f32>>> {
+    let settings = crate::database::get_ai_settings(pool)?;
+
+    if !settings.enabled {
+        return Err(anyhow!("AI features are not enabled"));
+    }
+
+    if inputs.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let url = format!("{}/v1/embeddings", settings.endpoint_url.trim_end_matches('/'));
+
+    let client = reqwest::blocking::Client::builder()
+        .timeout(std::time::Duration::from_secs(120))
+        .build()?;
+    let mut request_builder = client.post(&url);
+
+    let api_key = crate::secrets::resolve(pool, &settings.api_key)?;
+    if !api_key.is_empty() {
+        request_builder = request_builder.header("Authorization", format!("Bearer {}", api_key));
+    }
+
+    let response = request_builder
+        .json(&EmbeddingsRequest { model, input: inputs })
+        .send()?;
+
+    let status_code = response.status();
+    if !status_code.is_success() {
+        let error_text = response.text().unwrap_or_default();
+        return Err(anyhow!("Failed to get embeddings: {} - {}", status_code, error_text));
+    }
+
+    let mut embeddings_response: EmbeddingsResponse = response.json()?;
+
+    if embeddings_response.data.len() != inputs.len() {
+        return Err(anyhow!(
+            "Embeddings endpoint returned {} vectors for {} inputs",
+            embeddings_response.data.len(),
+            inputs.len()
+        ));
+    }
+
+    embeddings_response.data.sort_by_key(|d| d.index);
+    Ok(embeddings_response.data.into_iter().map(|d| d.embedding).collect())
+}
+
 /// Tool definition for LLM
 #[derive(Debug, Clone)]
 pub struct ToolDefinition {
@@ -212,6 +283,19 @@ impl ToolDefinition {
                     "message": {
                         "type": "string",
                         "description": "The notification message to display to the user. Should be clear, concise, and informative."
+                    },
+                    "urgency": {
+                        "type": "string",
+                        "enum": ["low", "normal", "critical"],
+                        "description": "How urgent the notification is. Defaults to 'normal'; use 'critical' only for errors or time-sensitive findings."
+                    },
+                    "title": {
+                        "type": "string",
+                        "description": "Optional short title. Defaults to the agent name."
+                    },
+                    "url": {
+                        "type": "string",
+                        "description": "Optional http(s) link related to the notification, e.g. the product page that changed."
                     }
                 },
                 "required": ["message"]
@@ -236,5 +320,77 @@ impl ToolDefinition {
             }),
         }
     }
-}
+    /// Read a value from the agent's persistent memory
+    pub fn memory_get() -> Self {
+        Self {
+            name: "memory_get".to_string(),
+            description: "Read a value previously stored in your persistent memory. Memory survives between runs of this agent, so use it to recall things like the last price or status you observed.".to_string(),
+            parameters: json!({
+                "type": "object",
+                "properties": {
+                    "key": {
+                        "type": "string",
+                        "description": "The memory key to read (e.g., 'last_price')"
+                    }
+                },
+                "required": ["key"]
+            }),
+        }
+    }
 
+    /// Store a value in the agent's persistent memory
+    pub fn memory_set() -> Self {
+        Self {
+            name: "memory_set".to_string(),
+            description: "Store a value in your persistent memory so it is available in future runs of this agent. Overwrites any existing value for the same key.".to_string(),
+            parameters: json!({
+                "type": "object",
+                "properties": {
+                    "key": {
+                        "type": "string",
+                        "description": "The memory key to write (e.g., 'last_price')"
+                    },
+                    "value": {
+                        "type": "string",
+                        "description": "The value to store"
+                    }
+                },
+                "required": ["key", "value"]
+            }),
+        }
+    }
+
+    /// List all keys in the agent's persistent memory
+    pub fn memory_list() -> Self {
+        Self {
+            name: "memory_list".to_string(),
+            description: "List all keys and values currently stored in your persistent memory.".to_string(),
+            parameters: json!({
+                "type": "object",
+                "properties": {}
+            }),
+        }
+    }
+
+    /// Search indexed local documents
+    pub fn search_documents() -> Self {
+        Self {
+            name: "search_documents".to_string(),
+            description: "Search the user's indexed local notes and documents. Returns the most relevant text passages together with the file they come from. Use this to answer questions about the user's own documents.".to_string(),
+            parameters: json!({
+                "type": "object",
+                "properties": {
+                    "query": {
+                        "type": "string",
+                        "description": "What to search for, phrased as a question or a short description"
+                    },
+                    "top_k": {
+                        "type": "integer",
+                        "description": "How many passages to return (default 5, maximum 20)"
+                    }
+                },
+                "required": ["query"]
+            }),
+        }
+    }
+}
@@ -2,4 +2,6 @@ pub mod llm_client;
 pub mod queue;
 pub mod agent;
 pub mod tools;
-
+pub mod documents;
+pub mod conditions;
+pub mod portable;
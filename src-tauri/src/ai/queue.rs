@@ -27,8 +27,8 @@ impl QueueManager {
     }
     
     /// Add a request to the queue
-    pub fn enqueue(&self, message: &str, agent_name: Option<&str>) -> Result<i64> {
-        let queue_id = crate::database::add_queue_item(&self.pool, message, agent_name)?;
+    pub fn enqueue(&self, message: &str, agent_name: Option<&str>, app_id: Option<i64>) -> Result<i64> {
+        let queue_id = crate::database::add_queue_item(&self.pool, message, agent_name, app_id)?;
         
         // Check if we can process immediately
         let can_process = {
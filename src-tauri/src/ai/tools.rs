@@ -1,35 +1,97 @@
-use crate::database::DbPool;
+use crate::database::{AgentApp, DbPool, NewNotification};
+use crate::notifications::{AgentNotification, Urgency};
 use anyhow::{anyhow, Result};
 use serde_json::Value;
 use tauri::AppHandle;
 
-/// Execute a tool call
-pub fn execute_tool(
-    pool: &DbPool,
-    app_handle: &AppHandle,
-    tool_name: &str,
-    arguments: &Value,
-) -> Result<String> {
+/// Maximum length of a memory key
+const MAX_MEMORY_KEY_LEN: usize = 128;
+
+/// Maximum length of a memory value
+const MAX_MEMORY_VALUE_LEN: usize = 4000;
+
+/// Default and maximum number of passages returned by search_documents
+const DEFAULT_SEARCH_RESULTS: usize = 5;
+const MAX_SEARCH_RESULTS: usize = 20;
+
+/// The agent run a tool call is executed for
+pub struct ToolContext<'a> {
+    pub pool: &'a DbPool,
+    pub app_handle: &'a AppHandle,
+    pub agent: &'a AgentApp,
+    pub agent_name: Option<&'a str>,
+    pub queue_id: Option<i64>, // None during dry runs
+}
+
+/// Execute a tool call on behalf of an agent run
+pub fn execute_tool(ctx: &ToolContext, tool_name: &str, arguments: &Value) -> Result<String> {
+    let app_id = ctx.agent.app_id;
     match tool_name {
-        "send_notification" => execute_notification(pool, app_handle, arguments),
+        "send_notification" => execute_notification(ctx, arguments),
         "run_command" => execute_run_command(arguments),
+        "memory_get" => execute_memory_get(ctx.pool, app_id, arguments),
+        "memory_set" => execute_memory_set(ctx.pool, app_id, arguments),
+        "memory_list" => execute_memory_list(ctx.pool, app_id),
+        "search_documents" => execute_search_documents(ctx.pool, arguments),
         _ => Err(anyhow!("Unknown tool: {}", tool_name)),
     }
 }
 
+/// Whether a tool only reads state, so it is safe to execute during a dry run
+pub fn is_read_only(tool_name: &str) -> bool {
+    matches!(tool_name, "memory_get" | "memory_list" | "search_documents")
+}
+
 /// Execute notification tool
-fn execute_notification(pool: &DbPool, app_handle: &AppHandle, arguments: &Value) -> Result<String> {
+fn execute_notification(ctx: &ToolContext, arguments: &Value) -> Result<String> {
     let message = arguments
         .get("message")
         .and_then(|v| v.as_str())
         .ok_or_else(|| anyhow!("Missing 'message' argument"))?;
 
-    crate::database::create_notification(pool, message)?;
+    let urgency = arguments
+        .get("urgency")
+        .and_then(|v| v.as_str())
+        .map(Urgency::parse)
+        .unwrap_or(Urgency::Normal);
 
-    // Open the notifications window to show the notification
-    if let Err(e) = crate::commands::open_notifications_window(app_handle.clone()) {
-        eprintln!("Failed to open notifications window: {}", e);
-    }
+    let title = arguments
+        .get("title")
+        .and_then(|v| v.as_str())
+        .filter(|t| !t.trim().is_empty())
+        .or(ctx.agent_name)
+        .unwrap_or("Agent")
+        .to_string();
+
+    // Only keep links the user can safely open from the notification
+    let url = arguments
+        .get("url")
+        .and_then(|v| v.as_str())
+        .filter(|u| u.starts_with("http://") || u.starts_with("https://"))
+        .map(|u| u.to_string());
+
+    let notification_id = crate::database::create_notification(
+        ctx.pool,
+        &NewNotification {
+            text: message.to_string(),
+            title: Some(title.clone()),
+            url: url.clone(),
+            severity: urgency.as_str().to_string(),
+            app_id: Some(ctx.agent.app_id),
+            queue_id: ctx.queue_id,
+        },
+    )?;
+
+    let notification = AgentNotification {
+        notification_id,
+        app_id: Some(ctx.agent.app_id),
+        queue_id: ctx.queue_id,
+        title,
+        body: message.to_string(),
+        url,
+        urgency,
+    };
+    crate::notifications::deliver(ctx.pool, ctx.app_handle, ctx.agent, &notification)?;
 
     Ok(format!("Notification sent: {}", message))
 }
@@ -74,3 +136,100 @@ fn execute_run_command(arguments: &Value) -> Result<String> {
     Ok(result)
 }
 
+/// Execute memory_get tool
+fn execute_memory_get(pool: &DbPool, app_id: i64, arguments: &Value) -> Result<String> {
+    let key = memory_key_argument(arguments)?;
+
+    match crate::database::get_agent_memory_value(pool, app_id, key)? {
+        Some(value) => Ok(value),
+        None => Ok(format!("No value stored for key '{}'", key)),
+    }
+}
+
+/// Execute memory_set tool
+fn execute_memory_set(pool: &DbPool, app_id: i64, arguments: &Value) -> Result<String> {
+    let key = memory_key_argument(arguments)?;
+
+    // Accept non-string values (numbers, booleans) by storing their JSON representation
+    let value = match arguments.get("value") {
+        Some(Value::String(s)) => s.clone(),
+        Some(Value::Null) | None => return Err(anyhow!("Missing 'value' argument")),
+        Some(other) => other.to_string(),
+    };
+
+    if value.len() > MAX_MEMORY_VALUE_LEN {
+        return Err(anyhow!("Memory value too long ({} > {} characters)", value.len(), MAX_MEMORY_VALUE_LEN));
+    }
+
+    crate::database::set_agent_memory_value(pool, app_id, key, &value)?;
+
+    Ok(format!("Stored memory '{}'", key))
+}
+
+/// Execute memory_list tool
+fn execute_memory_list(pool: &DbPool, app_id: i64) -> Result<String> {
+    let entries = crate::database::get_agent_memory(pool, app_id)?;
+
+    if entries.is_empty() {
+        return Ok("Memory is empty".to_string());
+    }
+
+    let mut result = String::new();
+    for entry in entries {
+        result.push_str(&format!("{} = {}\n", entry.key, entry.value));
+    }
+
+    Ok(result)
+}
+
+/// Extract and validate the 'key' argument of a memory tool
+fn memory_key_argument(arguments: &Value) -> Result<&str> {
+    let key = arguments
+        .get("key")
+        .and_then(|v| v.as_str())
+        .map(|k| k.trim())
+        .ok_or_else(|| anyhow!("Missing 'key' argument"))?;
+
+    if key.is_empty() {
+        return Err(anyhow!("Memory key must not be empty"));
+    }
+    if key.len() > MAX_MEMORY_KEY_LEN {
+        return Err(anyhow!("Memory key too long ({} > {} characters)", key.len(), MAX_MEMORY_KEY_LEN));
+    }
+
+    Ok(key)
+}
+
+/// Execute search_documents tool
+fn execute_search_documents(pool: &DbPool, arguments: &Value) -> Result<String> {
+    let query = arguments
+        .get("query")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| anyhow!("Missing 'query' argument"))?;
+
+    let top_k = arguments
+        .get("top_k")
+        .and_then(|v| v.as_u64())
+        .map(|k| (k as usize).clamp(1, MAX_SEARCH_RESULTS))
+        .unwrap_or(DEFAULT_SEARCH_RESULTS);
+
+    let matches = crate::ai::documents::search(pool, query, top_k)?;
+
+    if matches.is_empty() {
+        return Ok("No matching documents found".to_string());
+    }
+
+    let mut result = String::new();
+    for (rank, m) in matches.iter().enumerate() {
+        result.push_str(&format!(
+            "[{}] {} (passage {}, score {:.3})\n{}\n\n",
+            rank + 1,
+            m.path,
+            m.chunk_index + 1,
+            m.score,
+            m.content
+        ));
+    }
+
+    Ok(result)
+}
@@ -0,0 +1,527 @@
+use crate::database::DbPool;
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+/// Version of the backup archive layout
+pub const FORMAT_VERSION: u32 = 1;
+
+/// Folder under the app data dir where automatic backups are kept
+pub const BACKUPS_DIR: &str = "backups";
+
+const MANIFEST_NAME: &str = "manifest.json";
+const DATABASE_NAME: &str = "launcher.db";
+const ICONS_DIR: &str = "icons";
+const SESSIONS_DIR: &str = "webapps";
+
+/// File name prefixes of the backups the launcher makes on its own
+const AUTO_PREFIX: &str = "jvlauncher-auto-";
+const PRE_MIGRATION_PREFIX: &str = "jvlauncher-pre-migration-";
+const PRE_RESTORE_PREFIX: &str = "jvlauncher-pre-restore-";
+
+/// Pre-migration and pre-restore backups kept (automatic backups use the `backup_keep` setting)
+const SAFETY_BACKUPS_KEPT: usize = 3;
+
+/// How often the scheduler checks whether an automatic backup is due
+const SCHEDULE_CHECK_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// Describes the contents of a backup archive
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupManifest {
+    pub format_version: u32,
+    pub app_version: String,
+    pub schema_version: i32,
+    pub created_at: i64,
+    /// App data dir the backup was taken from, used to fix up stored paths on restore
+    pub data_dir: String,
+    pub includes_sessions: bool,
+}
+
+/// A backup archive in the backups folder
+#[derive(Debug, Clone, Serialize)]
+pub struct BackupFile {
+    pub path: String,
+    pub name: String,
+    pub kind: String, // "auto", "pre-migration", "pre-restore" or "manual"
+    pub size: u64,
+    pub modified_at: i64,
+}
+
+/// Write a backup archive of the database, icons and optionally webapp sessions
+pub fn create_backup(pool: &DbPool, data_dir: &Path, dest: &Path, include_sessions: bool) -> Result<BackupManifest> {
+    let snapshot = dest.with_extension("db.tmp");
+    crate::database::backup_database(pool, &snapshot).context("Failed to snapshot the database")?;
+
+    let result = write_archive(data_dir, &snapshot, dest, include_sessions);
+    let _ = fs::remove_file(&snapshot);
+    result
+}
+
+/// Back up the database before it is migrated to a newer schema
+///
+/// Runs before the database is opened by the pool, so it snapshots the file directly.
+pub fn backup_before_migration(data_dir: &Path) -> Result<Option<PathBuf>> {
+    let db_path = data_dir.join(DATABASE_NAME);
+    if !crate::database::needs_migration(&db_path)? {
+        return Ok(None);
+    }
+
+    let dir = backups_dir(data_dir)?;
+    let dest = dir.join(format!("{}{}.zip", PRE_MIGRATION_PREFIX, now()));
+    let snapshot = dest.with_extension("db.tmp");
+    crate::database::backup_database_file(&db_path, &snapshot).context("Failed to snapshot the database")?;
+
+    let result = write_archive(data_dir, &snapshot, &dest, false);
+    let _ = fs::remove_file(&snapshot);
+    result?;
+
+    prune(&dir, PRE_MIGRATION_PREFIX, SAFETY_BACKUPS_KEPT)?;
+    Ok(Some(dest))
+}
+
+/// Check that an archive is a usable backup without changing anything
+pub fn validate_backup(archive: &Path) -> Result<BackupManifest> {
+    let (manifest, _, database) = open_validated(archive)?;
+    let _ = fs::remove_file(database);
+    Ok(manifest)
+}
+
+/// Replace the current data with a backup
+///
+/// The archive is validated first and the current data is backed up to the backups folder,
+/// so a failed or unwanted restore can be undone.
+pub fn restore_backup(pool: &DbPool, data_dir: &Path, archive: &Path) -> Result<BackupManifest> {
+    let (manifest, mut zip, database) = open_validated(archive)?;
+    let staging = data_dir.join(".restore-staging");
+
+    let result = (|| {
+        let dir = backups_dir(data_dir)?;
+        let safety = dir.join(format!("{}{}.zip", PRE_RESTORE_PREFIX, now()));
+        create_backup(pool, data_dir, &safety, manifest.includes_sessions)
+            .context("Failed to back up the current data before restoring")?;
+        prune(&dir, PRE_RESTORE_PREFIX, SAFETY_BACKUPS_KEPT)?;
+
+        // Unpack files next to the current ones first so a failed extraction leaves them untouched
+        let _ = fs::remove_dir_all(&staging);
+        extract_files(&mut zip, &staging)?;
+
+        crate::database::restore_database(pool, &database).context("Failed to restore the database")?;
+        replace_dir(data_dir, &staging, ICONS_DIR)?;
+        if manifest.includes_sessions {
+            replace_dir(data_dir, &staging, SESSIONS_DIR)?;
+        }
+        crate::database::rebase_data_paths(pool, &manifest.data_dir, &data_dir.to_string_lossy())
+    })();
+
+    let _ = fs::remove_dir_all(&staging);
+    let _ = fs::remove_file(&database);
+    result.map(|_| manifest)
+}
+
+/// List backup archives in the backups folder, newest first
+pub fn list_backups(data_dir: &Path) -> Result<Vec<BackupFile>> {
+    let dir = data_dir.join(BACKUPS_DIR);
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut backups = Vec::new();
+    for entry in fs::read_dir(&dir)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !name.ends_with(".zip") {
+            continue;
+        }
+
+        let metadata = entry.metadata()?;
+        let kind = if name.starts_with(AUTO_PREFIX) {
+            "auto"
+        } else if name.starts_with(PRE_MIGRATION_PREFIX) {
+            "pre-migration"
+        } else if name.starts_with(PRE_RESTORE_PREFIX) {
+            "pre-restore"
+        } else {
+            "manual"
+        };
+        let modified_at = metadata
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+            .map_or(0, |d| d.as_secs() as i64);
+
+        backups.push(BackupFile {
+            path: entry.path().to_string_lossy().to_string(),
+            name,
+            kind: kind.to_string(),
+            size: metadata.len(),
+            modified_at,
+        });
+    }
+
+    backups.sort_by_key(|b| std::cmp::Reverse(b.modified_at));
+    Ok(backups)
+}
+
+/// Make rotating automatic backups in the background according to the backup settings
+pub fn start_scheduler(pool: DbPool, data_dir: PathBuf) {
+    std::thread::spawn(move || loop {
+        if let Err(e) = run_scheduled_backup(&pool, &data_dir, now()) {
+            eprintln!("[Backup] Automatic backup failed: {:#}", e);
+        }
+        std::thread::sleep(SCHEDULE_CHECK_INTERVAL);
+    });
+}
+
+/// Make an automatic backup if the last one is older than the configured interval
+fn run_scheduled_backup(pool: &DbPool, data_dir: &Path, now: i64) -> Result<Option<PathBuf>> {
+    let settings = crate::database::get_settings(pool)?;
+    if settings.backup_interval_hours <= 0 {
+        return Ok(None);
+    }
+
+    let dir = backups_dir(data_dir)?;
+    let last = fs::read_dir(&dir)?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| auto_backup_time(&entry.file_name().to_string_lossy()))
+        .max();
+    if last.is_some_and(|last| now - last < settings.backup_interval_hours * 3600) {
+        return Ok(None);
+    }
+
+    let dest = dir.join(format!("{}{}.zip", AUTO_PREFIX, now));
+    create_backup(pool, data_dir, &dest, settings.backup_include_sessions)?;
+    prune(&dir, AUTO_PREFIX, settings.backup_keep.max(1) as usize)?;
+    println!("[Backup] Wrote automatic backup {}", dest.display());
+    Ok(Some(dest))
+}
+
+/// Time an automatic backup was taken, from its file name
+fn auto_backup_time(name: &str) -> Option<i64> {
+    name.strip_prefix(AUTO_PREFIX)?.strip_suffix(".zip")?.parse().ok()
+}
+
+/// Delete the oldest backups with a prefix so that at most `keep` remain
+fn prune(dir: &Path, prefix: &str, keep: usize) -> Result<()> {
+    let mut names: Vec<String> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .filter(|name| name.starts_with(prefix) && name.ends_with(".zip"))
+        .collect();
+
+    // Names end in a Unix timestamp, so sorting by (length, name) sorts by age
+    names.sort_by(|a, b| (a.len(), a).cmp(&(b.len(), b)));
+    let excess = names.len().saturating_sub(keep);
+    for name in &names[..excess] {
+        fs::remove_file(dir.join(name))?;
+    }
+    Ok(())
+}
+
+fn backups_dir(data_dir: &Path) -> Result<PathBuf> {
+    let dir = data_dir.join(BACKUPS_DIR);
+    fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+fn write_archive(data_dir: &Path, snapshot: &Path, dest: &Path, include_sessions: bool) -> Result<BackupManifest> {
+    let manifest = BackupManifest {
+        format_version: FORMAT_VERSION,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        schema_version: crate::database::check_database_file(snapshot)?,
+        created_at: now(),
+        data_dir: data_dir.to_string_lossy().to_string(),
+        includes_sessions: include_sessions,
+    };
+
+    // Write to a temporary file first so a failed backup never leaves a truncated archive behind
+    let partial = dest.with_extension("partial");
+    let mut zip = ZipWriter::new(File::create(&partial)?);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    let written = (|| {
+        zip.start_file(MANIFEST_NAME, options)?;
+        zip.write_all(&serde_json::to_vec_pretty(&manifest)?)?;
+
+        zip.start_file(DATABASE_NAME, options)?;
+        io::copy(&mut File::open(snapshot)?, &mut zip)?;
+
+        add_dir(&mut zip, &data_dir.join(ICONS_DIR), ICONS_DIR, options)?;
+        if include_sessions {
+            add_dir(&mut zip, &data_dir.join(SESSIONS_DIR), SESSIONS_DIR, options)?;
+        }
+        zip.finish()?;
+        Ok::<_, anyhow::Error>(())
+    })();
+
+    if let Err(e) = written {
+        let _ = fs::remove_file(&partial);
+        return Err(e.context(format!("Failed to write backup {}", dest.display())));
+    }
+    fs::rename(&partial, dest)?;
+    Ok(manifest)
+}
+
+/// Add a directory to the archive recursively under `prefix`
+fn add_dir(zip: &mut ZipWriter<File>, dir: &Path, prefix: &str, options: SimpleFileOptions) -> Result<()> {
+    if !dir.is_dir() {
+        return Ok(());
+    }
+
+    let mut entries: Vec<_> = fs::read_dir(dir)?.filter_map(|entry| entry.ok()).collect();
+    entries.sort_by_key(|entry| entry.file_name());
+
+    for entry in entries {
+        let name = format!("{}/{}", prefix, entry.file_name().to_string_lossy());
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            add_dir(zip, &entry.path(), &name, options)?;
+        } else if file_type.is_file() {
+            // Browsers keep some session files locked while running; skip those rather than fail
+            match File::open(entry.path()) {
+                Ok(mut file) => {
+                    zip.start_file(name, options)?;
+                    io::copy(&mut file, zip)?;
+                }
+                Err(e) => eprintln!("[Backup] Skipping {}: {}", entry.path().display(), e),
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Open an archive and check its manifest, entries and database
+///
+/// Returns the database extracted to a temporary file, which the caller removes.
+fn open_validated(archive: &Path) -> Result<(BackupManifest, ZipArchive<File>, PathBuf)> {
+    let file = File::open(archive).with_context(|| format!("Failed to open {}", archive.display()))?;
+    let mut zip = ZipArchive::new(file).context("Not a backup archive")?;
+
+    let manifest: BackupManifest = {
+        let entry = zip
+            .by_name(MANIFEST_NAME)
+            .map_err(|_| anyhow!("Not a jvlauncher backup: {} is missing", MANIFEST_NAME))?;
+        serde_json::from_reader(entry).context("Invalid backup manifest")?
+    };
+    if manifest.format_version > FORMAT_VERSION {
+        return Err(anyhow!(
+            "The backup was made by a newer version of jvlauncher ({}); please update first",
+            manifest.app_version
+        ));
+    }
+
+    for i in 0..zip.len() {
+        let entry = zip.by_index(i)?;
+        let name = entry.name().to_string();
+        let allowed = name == MANIFEST_NAME
+            || name == DATABASE_NAME
+            || name.starts_with(&format!("{}/", ICONS_DIR))
+            || (manifest.includes_sessions && name.starts_with(&format!("{}/", SESSIONS_DIR)));
+        if !allowed || entry.enclosed_name().is_none() {
+            return Err(anyhow!("Unexpected file in backup: {}", name));
+        }
+    }
+
+    let database = std::env::temp_dir().join(format!("jvlauncher-restore-{}-{}.db", std::process::id(), now()));
+    {
+        let mut entry = zip
+            .by_name(DATABASE_NAME)
+            .map_err(|_| anyhow!("Not a jvlauncher backup: {} is missing", DATABASE_NAME))?;
+        io::copy(&mut entry, &mut File::create(&database)?)?;
+    }
+    if let Err(e) = crate::database::check_database_file(&database) {
+        let _ = fs::remove_file(&database);
+        return Err(e.context("The backup's database is not usable"));
+    }
+
+    Ok((manifest, zip, database))
+}
+
+/// Extract the icon and session files of an archive into `dest`
+fn extract_files(zip: &mut ZipArchive<File>, dest: &Path) -> Result<()> {
+    for i in 0..zip.len() {
+        let mut entry = zip.by_index(i)?;
+        let Some(relative) = entry.enclosed_name() else {
+            continue;
+        };
+        if entry.name() == MANIFEST_NAME || entry.name() == DATABASE_NAME || entry.is_dir() {
+            continue;
+        }
+
+        let path = dest.join(relative);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        io::copy(&mut entry, &mut File::create(&path)?)?;
+    }
+    Ok(())
+}
+
+/// Swap `data_dir/name` for the staged copy (or an empty folder if the backup had none)
+fn replace_dir(data_dir: &Path, staging: &Path, name: &str) -> Result<()> {
+    let target = data_dir.join(name);
+    let old = data_dir.join(format!(".{}-old", name));
+    let _ = fs::remove_dir_all(&old);
+
+    if target.exists() {
+        fs::rename(&target, &old)?;
+    }
+    let staged = staging.join(name);
+    if staged.exists() {
+        fs::rename(&staged, &target)?;
+    } else {
+        fs::create_dir_all(&target)?;
+    }
+
+    let _ = fs::remove_dir_all(&old);
+    Ok(())
+}
+
+fn now() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{test_pool, AppType, NewApp};
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("jvlauncher-backup-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn add_app(pool: &DbPool, name: &str, icon_path: Option<String>) -> i64 {
+        crate::database::create_app(
+            pool,
+            NewApp {
+                app_type: AppType::App,
+                name: name.to_string(),
+                icon_path,
+                shortcut: None,
+                global_shortcut: None,
+                binary_path: Some("/usr/bin/true".to_string()),
+                cli_params: None,
+                url: None,
+                show_nav_controls: None,
+                open_external_links: None,
+                enable_oauth: None,
+                auto_close_timeout: None,
+                always_on_top: None,
+                hide_on_shortcut: None,
+                single_instance: None,
+                working_dir: None,
+                env: Vec::new(),
+                env_file: None,
+                pre_launch_hook: None,
+                post_exit_hook: None,
+                hook_timeout: None,
+            },
+            None,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_backup_and_restore_roundtrip() {
+        let source_dir = temp_dir("source");
+        fs::create_dir_all(source_dir.join(ICONS_DIR)).unwrap();
+        let icon = source_dir.join(ICONS_DIR).join("editor.png");
+        fs::write(&icon, b"png").unwrap();
+
+        let source = test_pool();
+        add_app(&source, "Editor", Some(icon.to_string_lossy().to_string()));
+        let archive = source_dir.join("backup.zip");
+        create_backup(&source, &source_dir, &archive, false).unwrap();
+
+        let manifest = validate_backup(&archive).unwrap();
+        assert!(!manifest.includes_sessions);
+
+        // Restore into a different data dir with other apps and icons
+        let target_dir = temp_dir("target");
+        fs::create_dir_all(target_dir.join(ICONS_DIR)).unwrap();
+        fs::write(target_dir.join(ICONS_DIR).join("other.png"), b"other").unwrap();
+        let target = test_pool();
+        add_app(&target, "Other", None);
+
+        restore_backup(&target, &target_dir, &archive).unwrap();
+
+        let apps = crate::database::get_all_apps(&target).unwrap();
+        assert_eq!(apps.len(), 1);
+        assert_eq!(apps[0].name, "Editor");
+        let restored_icon = target_dir.join(ICONS_DIR).join("editor.png");
+        assert_eq!(apps[0].icon_path.as_deref(), Some(restored_icon.to_string_lossy().as_ref()));
+        assert_eq!(fs::read(&restored_icon).unwrap(), b"png");
+        assert!(!target_dir.join(ICONS_DIR).join("other.png").exists());
+
+        // The replaced data was kept as a pre-restore backup
+        let backups = list_backups(&target_dir).unwrap();
+        assert!(backups.iter().any(|b| b.kind == "pre-restore"));
+
+        let _ = fs::remove_dir_all(&source_dir);
+        let _ = fs::remove_dir_all(&target_dir);
+    }
+
+    #[test]
+    fn test_invalid_archives_are_rejected() {
+        let dir = temp_dir("invalid");
+        let options = SimpleFileOptions::default();
+
+        let not_zip = dir.join("not.zip");
+        fs::write(&not_zip, b"hello").unwrap();
+        assert!(validate_backup(&not_zip).is_err());
+
+        let no_manifest = dir.join("empty.zip");
+        let mut zip = ZipWriter::new(File::create(&no_manifest).unwrap());
+        zip.start_file("launcher.db", options).unwrap();
+        zip.finish().unwrap();
+        assert!(validate_backup(&no_manifest).unwrap_err().to_string().contains("manifest.json"));
+
+        // A valid backup with an extra file outside the known folders
+        let pool = test_pool();
+        let valid = dir.join("valid.zip");
+        create_backup(&pool, &dir, &valid, false).unwrap();
+        let tampered = dir.join("tampered.zip");
+        {
+            let mut source = ZipArchive::new(File::open(&valid).unwrap()).unwrap();
+            let mut zip = ZipWriter::new(File::create(&tampered).unwrap());
+            for i in 0..source.len() {
+                zip.raw_copy_file(source.by_index(i).unwrap()).unwrap();
+            }
+            zip.start_file("../evil.sh", options).unwrap();
+            zip.finish().unwrap();
+        }
+        assert!(validate_backup(&tampered).unwrap_err().to_string().contains("Unexpected file"));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_scheduled_backups_rotate() {
+        let dir = temp_dir("schedule");
+        let pool = test_pool();
+        crate::database::update_setting(&pool, "backup_interval_hours", "1").unwrap();
+        crate::database::update_setting(&pool, "backup_keep", "2").unwrap();
+
+        assert!(run_scheduled_backup(&pool, &dir, 1_000_000).unwrap().is_some());
+        // Not due again within the interval
+        assert!(run_scheduled_backup(&pool, &dir, 1_000_000 + 1800).unwrap().is_none());
+        assert!(run_scheduled_backup(&pool, &dir, 1_000_000 + 3600).unwrap().is_some());
+        assert!(run_scheduled_backup(&pool, &dir, 1_000_000 + 7200).unwrap().is_some());
+
+        let autos: Vec<_> = list_backups(&dir).unwrap().into_iter().filter(|b| b.kind == "auto").collect();
+        assert_eq!(autos.len(), 2);
+        assert!(!dir.join(BACKUPS_DIR).join(format!("{}1000000.zip", AUTO_PREFIX)).exists());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
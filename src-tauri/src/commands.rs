@@ -1,4 +1,5 @@
 use crate::database::{App, DbPool, NewApp, Settings};
+use crate::desktop_entries::{self, DesktopEntry};
 use crate::{database, icon_extractor, icon_fetcher, launcher, terminal};
 use tauri::{AppHandle, Emitter, Manager, State};
 
@@ -16,6 +17,9 @@ pub fn create_app(
     app_handle: AppHandle,
     new_app: NewApp,
 ) -> Result<i64, String> {
+    crate::environment::validate(&new_app.env).map_err(|e| e.to_string())?;
+    crate::hooks::validate(new_app.hook_timeout).map_err(|e| e.to_string())?;
+
     // Generate session directory for webapps
     let session_dir = if new_app.app_type == database::AppType::Webapp {
         let app_data = app_handle.path().app_data_dir()
@@ -62,6 +66,9 @@ pub fn create_app(
 /// Update an existing app
 #[tauri::command]
 pub fn update_app(pool: State<DbPool>, app_handle: AppHandle, app: App) -> Result<(), String> {
+    crate::environment::validate(&app.env).map_err(|e| e.to_string())?;
+    crate::hooks::validate(app.hook_timeout).map_err(|e| e.to_string())?;
+
     // Get the old app data to check if global shortcut changed
     let old_app = database::get_all_apps(&pool)
         .map_err(|e| format!("Failed to get apps: {}", e))?
@@ -135,9 +142,226 @@ pub fn reorder_apps(pool: State<DbPool>, app_ids: Vec<i64>) -> Result<(), String
         .map_err(|e| format!("Failed to reorder apps: {}", e))
 }
 
-/// Launch an app
+/// Get the pages of the app grid in order
+#[tauri::command]
+pub fn get_pages(pool: State<DbPool>) -> Result<Vec<crate::database::Page>, String> {
+    database::get_pages(&pool)
+        .map_err(|e| format!("Failed to get pages: {}", e))
+}
+
+/// Add a page at the end
+#[tauri::command]
+pub fn create_page(pool: State<DbPool>, name: String) -> Result<i64, String> {
+    database::create_page(&pool, item_name(&name)?)
+        .map_err(|e| format!("Failed to create page: {}", e))
+}
+
+/// Rename a page and set or clear its own grid size
+#[tauri::command]
+pub fn update_page(
+    pool: State<DbPool>,
+    page_id: i64,
+    name: String,
+    grid_cols: Option<i32>,
+    grid_rows: Option<i32>,
+) -> Result<(), String> {
+    for size in [grid_cols, grid_rows].into_iter().flatten() {
+        if !(1..=20).contains(&size) {
+            return Err("Grid size must be between 1 and 20".to_string());
+        }
+    }
+    database::update_page(&pool, page_id, item_name(&name)?, grid_cols, grid_rows)
+        .map_err(|e| format!("Failed to update page: {}", e))
+}
+
+/// Delete a page; its apps and folders move to the first remaining page
+#[tauri::command]
+pub fn delete_page(pool: State<DbPool>, page_id: i64) -> Result<(), String> {
+    database::delete_page(&pool, page_id)
+        .map_err(|e| format!("Failed to delete page: {}", e))
+}
+
+/// Reorder pages
+#[tauri::command]
+pub fn reorder_pages(pool: State<DbPool>, page_ids: Vec<i64>) -> Result<(), String> {
+    database::reorder_pages(&pool, page_ids)
+        .map_err(|e| format!("Failed to reorder pages: {}", e))
+}
+
+/// Get all folders
+#[tauri::command]
+pub fn get_groups(pool: State<DbPool>) -> Result<Vec<crate::database::AppGroup>, String> {
+    database::get_groups(&pool)
+        .map_err(|e| format!("Failed to get folders: {}", e))
+}
+
+/// Add a folder to a page, or inside another folder
+#[tauri::command]
+pub fn create_group(pool: State<DbPool>, name: String, page_id: i64, parent_id: Option<i64>) -> Result<i64, String> {
+    database::create_group(&pool, item_name(&name)?, page_id, parent_id)
+        .map_err(|e| format!("Failed to create folder: {}", e))
+}
+
+/// Rename a folder
+#[tauri::command]
+pub fn rename_group(pool: State<DbPool>, group_id: i64, name: String) -> Result<(), String> {
+    database::rename_group(&pool, group_id, item_name(&name)?)
+        .map_err(|e| format!("Failed to rename folder: {}", e))
+}
+
+/// Delete a folder; what it contains takes its place
+#[tauri::command]
+pub fn delete_group(pool: State<DbPool>, group_id: i64) -> Result<(), String> {
+    database::delete_group(&pool, group_id)
+        .map_err(|e| format!("Failed to delete folder: {}", e))
+}
+
+/// Move an app to a position in a page or folder
+#[tauri::command]
+pub fn move_app(
+    pool: State<DbPool>,
+    app_id: i64,
+    page_id: i64,
+    group_id: Option<i64>,
+    index: usize,
+) -> Result<(), String> {
+    database::move_app(&pool, app_id, page_id, group_id, index)
+        .map_err(|e| format!("Failed to move app: {}", e))
+}
+
+/// Move a folder, with its contents, to a position in a page or another folder
+#[tauri::command]
+pub fn move_group(
+    pool: State<DbPool>,
+    group_id: i64,
+    page_id: i64,
+    parent_id: Option<i64>,
+    index: usize,
+) -> Result<(), String> {
+    database::move_group(&pool, group_id, page_id, parent_id, index)
+        .map_err(|e| format!("Failed to move folder: {}", e))
+}
+
+/// Get every tag with the number of apps that have it
+#[tauri::command]
+pub fn get_tags(pool: State<DbPool>) -> Result<Vec<crate::database::TagCount>, String> {
+    database::get_tags(&pool)
+        .map_err(|e| format!("Failed to get tags: {}", e))
+}
+
+/// Tag an app
+#[tauri::command]
+pub fn add_app_tag(pool: State<DbPool>, app_handle: AppHandle, app_id: i64, tag: String) -> Result<(), String> {
+    database::add_app_tag(&pool, app_id, &tag)
+        .map_err(|e| format!("Failed to add tag: {}", e))?;
+    notify_apps_changed(&app_handle);
+    Ok(())
+}
+
+/// Remove a tag from an app
+#[tauri::command]
+pub fn remove_app_tag(pool: State<DbPool>, app_handle: AppHandle, app_id: i64, tag: String) -> Result<(), String> {
+    database::remove_app_tag(&pool, app_id, &tag)
+        .map_err(|e| format!("Failed to remove tag: {}", e))?;
+    notify_apps_changed(&app_handle);
+    Ok(())
+}
+
+/// Replace all tags of an app
+#[tauri::command]
+pub fn set_app_tags(pool: State<DbPool>, app_handle: AppHandle, app_id: i64, tags: Vec<String>) -> Result<(), String> {
+    database::set_app_tags(&pool, app_id, &tags)
+        .map_err(|e| format!("Failed to set tags: {}", e))?;
+    notify_apps_changed(&app_handle);
+    Ok(())
+}
+
+/// Rename a tag on every app and in saved filters; renaming onto an existing tag merges them
+#[tauri::command]
+pub fn rename_tag(pool: State<DbPool>, app_handle: AppHandle, old: String, new: String) -> Result<(), String> {
+    database::rename_tag(&pool, &old, &new)
+        .map_err(|e| format!("Failed to rename tag: {}", e))?;
+    notify_apps_changed(&app_handle);
+    Ok(())
+}
+
+/// Remove a tag from every app
+#[tauri::command]
+pub fn delete_tag(pool: State<DbPool>, app_handle: AppHandle, tag: String) -> Result<(), String> {
+    database::delete_tag(&pool, &tag)
+        .map_err(|e| format!("Failed to delete tag: {}", e))?;
+    notify_apps_changed(&app_handle);
+    Ok(())
+}
+
+/// Get all saved filters
+#[tauri::command]
+pub fn get_saved_filters(pool: State<DbPool>) -> Result<Vec<crate::database::SavedFilter>, String> {
+    database::get_saved_filters(&pool)
+        .map_err(|e| format!("Failed to get saved filters: {}", e))
+}
+
+/// Create or update a saved filter, returning its id
+#[tauri::command]
+pub fn save_saved_filter(
+    pool: State<DbPool>,
+    app_handle: AppHandle,
+    filter: crate::database::SavedFilter,
+) -> Result<i64, String> {
+    let mut filter = filter;
+    filter.name = item_name(&filter.name)?.to_string();
+    filter.rule.validate().map_err(|e| e.to_string())?;
+    let id = database::save_saved_filter(&pool, &filter)
+        .map_err(|e| format!("Failed to save filter: {}", e))?;
+    notify_apps_changed(&app_handle);
+    Ok(id)
+}
+
+/// Delete a saved filter
+#[tauri::command]
+pub fn delete_saved_filter(pool: State<DbPool>, app_handle: AppHandle, filter_id: i64) -> Result<(), String> {
+    database::delete_saved_filter(&pool, filter_id)
+        .map_err(|e| format!("Failed to delete filter: {}", e))?;
+    notify_apps_changed(&app_handle);
+    Ok(())
+}
+
+/// Ids of the apps a saved filter shows, in grid order
+#[tauri::command]
+pub fn get_filter_apps(pool: State<DbPool>, filter_id: i64) -> Result<Vec<i64>, String> {
+    let filter = database::get_saved_filters(&pool)
+        .map_err(|e| format!("Failed to get saved filters: {}", e))?
+        .into_iter()
+        .find(|f| f.id == filter_id)
+        .ok_or_else(|| format!("Filter with id {} not found", filter_id))?;
+    crate::filters::matching_apps(&pool, &filter.rule)
+        .map_err(|e| format!("Failed to apply filter: {}", e))
+}
+
+/// Tell the launcher window to reload its apps
+fn notify_apps_changed(app_handle: &AppHandle) {
+    if let Some(main_window) = app_handle.get_webview_window("main") {
+        let _ = main_window.emit("app-updated", ());
+    }
+}
+
+/// Trimmed page, folder or filter name, which must not be empty
+fn item_name(name: &str) -> Result<&str, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Name must not be empty".to_string());
+    }
+    Ok(name)
+}
+
+/// Launch an app; `trigger` records how it was started and defaults to a click
 #[tauri::command]
-pub fn launch(pool: State<DbPool>, app_handle: AppHandle, app_id: i64) -> Result<(), String> {
+pub fn launch(
+    pool: State<DbPool>,
+    app_handle: AppHandle,
+    app_id: i64,
+    trigger: Option<launcher::LaunchTrigger>,
+) -> Result<(), String> {
     let apps = database::get_all_apps(&pool)
         .map_err(|e| format!("Failed to get apps: {}", e))?;
 
@@ -145,7 +369,7 @@ pub fn launch(pool: State<DbPool>, app_handle: AppHandle, app_id: i64) -> Result
         .find(|a| a.id == app_id)
         .ok_or_else(|| format!("App with id {} not found", app_id))?;
 
-    launcher::launch_app(app, &app_handle, &pool)
+    launcher::launch_app(app, &app_handle, &pool, trigger.unwrap_or_default())
         .map_err(|e| format!("Failed to launch app: {}", e))?;
 
     // Hide the main launcher window after launching
@@ -156,6 +380,114 @@ pub fn launch(pool: State<DbPool>, app_handle: AppHandle, app_id: i64) -> Result
     Ok(())
 }
 
+/// Ids of the native apps with a running process
+#[tauri::command]
+pub fn get_running_apps() -> Vec<i64> {
+    crate::processes::running_apps()
+}
+
+/// Quit a running native app, or kill it when `force` is set
+#[tauri::command]
+pub fn stop_app(app_id: i64, force: Option<bool>) -> Result<(), String> {
+    crate::processes::stop(app_id, force.unwrap_or(false))
+        .map_err(|e| format!("Failed to stop app: {}", e))
+}
+
+/// Get the apps of a workspace in launch order
+#[tauri::command]
+pub fn get_workspace_items(pool: State<DbPool>, workspace_id: i64) -> Result<Vec<database::WorkspaceItem>, String> {
+    database::get_workspace_items(&pool, workspace_id)
+        .map_err(|e| format!("Failed to get workspace apps: {}", e))
+}
+
+/// Set the apps of a workspace; their order is the launch order
+#[tauri::command]
+pub fn set_workspace_items(
+    pool: State<DbPool>,
+    workspace_id: i64,
+    items: Vec<database::WorkspaceItem>,
+) -> Result<(), String> {
+    let apps = database::get_all_apps(&pool)
+        .map_err(|e| format!("Failed to get apps: {}", e))?;
+    crate::workspaces::validate(&apps, workspace_id, &items).map_err(|e| e.to_string())?;
+
+    database::set_workspace_items(&pool, workspace_id, &items)
+        .map_err(|e| format!("Failed to save workspace apps: {}", e))
+}
+
+/// Remember where a workspace's open windows are; returns how many were saved
+#[tauri::command]
+pub fn save_workspace_layout(pool: State<DbPool>, app_handle: AppHandle, workspace_id: i64) -> Result<usize, String> {
+    crate::workspaces::save_layout(&app_handle, &pool, workspace_id)
+        .map_err(|e| format!("Failed to save workspace layout: {}", e))
+}
+
+/// Save a workspace's layout and close all of its apps
+#[tauri::command]
+pub fn close_workspace(pool: State<DbPool>, app_handle: AppHandle, workspace_id: i64) -> Result<(), String> {
+    crate::workspaces::close(&app_handle, &pool, workspace_id)
+        .map_err(|e| format!("Failed to close workspace: {}", e))
+}
+
+/// Get recent launches, newest first, optionally for a single app
+#[tauri::command]
+pub fn get_launch_history(
+    pool: State<DbPool>,
+    app_id: Option<i64>,
+    limit: Option<i64>,
+) -> Result<Vec<crate::database::LaunchRecord>, String> {
+    database::get_launch_history(&pool, app_id, limit.unwrap_or(100))
+        .map_err(|e| format!("Failed to get launch history: {}", e))
+}
+
+/// Get an app's failed launches, newest first
+#[tauri::command]
+pub fn get_launch_errors(
+    pool: State<DbPool>,
+    app_id: i64,
+    limit: Option<i64>,
+) -> Result<Vec<crate::database::LaunchRecord>, String> {
+    database::get_launch_errors(&pool, app_id, limit.unwrap_or(20))
+        .map_err(|e| format!("Failed to get launch errors: {}", e))
+}
+
+/// Get launch counts and last-used times per app over the last `days` days (all time if omitted)
+#[tauri::command]
+pub fn get_launch_stats(pool: State<DbPool>, days: Option<i64>) -> Result<Vec<crate::database::LaunchStats>, String> {
+    database::get_launch_stats(&pool, days_ago(days))
+        .map_err(|e| format!("Failed to get launch statistics: {}", e))
+}
+
+/// Get launches per day over the last `days` days (all time if omitted), optionally for a single app
+#[tauri::command]
+pub fn get_launch_histogram(
+    pool: State<DbPool>,
+    app_id: Option<i64>,
+    days: Option<i64>,
+) -> Result<Vec<crate::database::LaunchDay>, String> {
+    database::get_launch_histogram(&pool, app_id, days_ago(days))
+        .map_err(|e| format!("Failed to get launch histogram: {}", e))
+}
+
+/// Fuzzy-search apps and built-in actions, ranked by match quality and launch frecency
+#[tauri::command]
+pub fn search(pool: State<DbPool>, query: String, limit: Option<usize>) -> Result<Vec<crate::search::SearchResult>, String> {
+    crate::search::search(&pool, &query, limit.unwrap_or(20), now_secs())
+        .map_err(|e| format!("Failed to search: {}", e))
+}
+
+/// Unix time `days` days ago, or 0 for no limit
+fn days_ago(days: Option<i64>) -> i64 {
+    days.filter(|d| *d > 0).map_or(0, |d| now_secs() - d * 24 * 60 * 60)
+}
+
+fn now_secs() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
 /// Extract icon from a binary file
 #[tauri::command]
 pub fn extract_icon_from_binary(
@@ -297,6 +629,75 @@ pub fn finalize_temp_icon(
     Ok(final_path.to_string_lossy().to_string())
 }
 
+/// List applications from .desktop files that aren't in the launcher yet
+#[tauri::command]
+pub fn scan_desktop_entries(pool: State<DbPool>) -> Result<Vec<DesktopEntry>, String> {
+    let apps = database::get_all_apps(&pool)
+        .map_err(|e| format!("Failed to get apps: {}", e))?;
+    let entries = desktop_entries::scan(&desktop_entries::application_dirs());
+    Ok(entries
+        .into_iter()
+        .filter(|entry| {
+            !apps.iter().any(|app| {
+                app.binary_path.as_deref() == Some(entry.binary_path.as_str()) && app.cli_params == entry.cli_params
+            })
+        })
+        .collect())
+}
+
+/// Create apps from selected .desktop entries; returns the new app ids
+#[tauri::command]
+pub fn import_desktop_entries(
+    pool: State<DbPool>,
+    app_handle: AppHandle,
+    entries: Vec<DesktopEntry>,
+) -> Result<Vec<i64>, String> {
+    let app_data = app_handle.path().app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?;
+    let icons_dir = app_data.join("icons");
+    icon_extractor::ensure_icons_dir(&icons_dir)
+        .map_err(|e| format!("Failed to create icons directory: {}", e))?;
+
+    let mut app_ids = Vec::new();
+    for entry in entries {
+        // A missing icon doesn't stop the import
+        let icon_path = entry.icon.as_deref().and_then(|icon| {
+            icon_extractor::save_desktop_icon(icon, &icons_dir, &format!("desktop-{}", entry.id))
+                .map_err(|e| eprintln!("Failed to save icon for {}: {}", entry.name, e))
+                .ok()
+        });
+        let new_app = NewApp {
+            app_type: if entry.terminal { database::AppType::Tui } else { database::AppType::App },
+            name: entry.name.clone(),
+            icon_path,
+            shortcut: None,
+            global_shortcut: None,
+            binary_path: Some(entry.binary_path),
+            cli_params: entry.cli_params,
+            url: None,
+            show_nav_controls: None,
+            open_external_links: None,
+            enable_oauth: None,
+            auto_close_timeout: None,
+            always_on_top: None,
+            hide_on_shortcut: None,
+            single_instance: None,
+            working_dir: None,
+            env: Vec::new(),
+            env_file: None,
+            pre_launch_hook: None,
+            post_exit_hook: None,
+            hook_timeout: None,
+        };
+        let app_id = database::create_app(&pool, new_app, None)
+            .map_err(|e| format!("Failed to create app {}: {}", entry.name, e))?;
+        app_ids.push(app_id);
+    }
+
+    notify_apps_changed(&app_handle);
+    Ok(app_ids)
+}
+
 /// Clean up a temporary icon file
 #[tauri::command]
 pub fn cleanup_temp_icon(temp_icon_path: String) -> Result<(), String> {
@@ -320,9 +721,102 @@ pub fn get_settings(pool: State<DbPool>) -> Result<Settings, String> {
 
 /// Update a single setting
 #[tauri::command]
-pub fn update_setting(pool: State<DbPool>, key: String, value: String) -> Result<(), String> {
-    database::update_setting(&pool, &key, &value)
-        .map_err(|e| format!("Failed to update setting: {}", e))
+pub fn update_setting(pool: State<DbPool>, app_handle: AppHandle, key: String, value: String) -> Result<(), String> {
+    let changes = crate::settings::set(&pool, &key, &value)
+        .map_err(|e| format!("Failed to update setting: {:#}", e))?;
+    apply_setting_changes(&app_handle, &pool, &changes)
+}
+
+/// Update several settings at once; nothing is saved unless every value is valid
+#[tauri::command]
+pub fn update_settings(
+    pool: State<DbPool>,
+    app_handle: AppHandle,
+    values: std::collections::HashMap<String, String>,
+) -> Result<(), String> {
+    let values: Vec<(String, String)> = values.into_iter().collect();
+    let changes = crate::settings::update(&pool, &values)
+        .map_err(|e| format!("Failed to update settings: {:#}", e))?;
+    apply_setting_changes(&app_handle, &pool, &changes)
+}
+
+/// Payload of the `settings-changed` event
+#[derive(Clone, serde::Serialize)]
+pub struct SettingsChanged {
+    pub keys: Vec<String>,
+}
+
+/// Run the side effects of changed settings and tell every window about them
+///
+/// A launcher shortcut that can't be registered is put back to its previous value.
+pub fn apply_setting_changes(
+    app_handle: &AppHandle,
+    pool: &DbPool,
+    changes: &[crate::settings::SettingChange],
+) -> Result<(), String> {
+    use crate::settings::SideEffect;
+    use tauri_plugin_autostart::ManagerExt;
+
+    if changes.is_empty() {
+        return Ok(());
+    }
+
+    let settings = database::get_settings(pool)
+        .map_err(|e| format!("Failed to get settings: {}", e))?;
+    let mut errors = Vec::new();
+    let mut resized = false;
+
+    for change in changes {
+        match change.side_effect {
+            Some(SideEffect::GlobalShortcut) => {
+                let old = change.old.clone().unwrap_or_default();
+                if let Err(e) = crate::shortcut_manager::replace_global_shortcut(app_handle, &old, &settings.global_shortcut) {
+                    let _ = database::update_setting(pool, &change.key, &old);
+                    errors.push(format!("Failed to register shortcut '{}', kept '{}': {}", settings.global_shortcut, old, e));
+                }
+            }
+            Some(SideEffect::GridSize) if !resized => {
+                resized = true;
+                // The launcher window resizes again for its active page when it reloads settings
+                let pages = database::get_pages(pool).map(|p| p.len()).unwrap_or(1);
+                if let Err(e) = resize_main_window(
+                    app_handle.clone(),
+                    settings.grid_cols,
+                    settings.grid_rows,
+                    Some(settings.separate_agent_apps || pages > 1),
+                    None,
+                ) {
+                    errors.push(e);
+                }
+            }
+            Some(SideEffect::Autostart) => {
+                let autostart = app_handle.autolaunch();
+                let result = if settings.start_at_login { autostart.enable() } else { autostart.disable() };
+                if let Err(e) = result {
+                    errors.push(format!("Failed to change start at login: {}", e));
+                }
+            }
+            Some(SideEffect::MaxConcurrentAgents) => {
+                let max = database::get_ai_settings(pool)
+                    .map_err(|e| format!("Failed to get AI settings: {}", e))?
+                    .max_concurrent_agents;
+                if let Ok(queue_manager) = crate::ai::queue::get_queue_manager() {
+                    queue_manager.set_max_concurrent(max);
+                }
+            }
+            Some(SideEffect::GridSize) | None => {}
+        }
+    }
+
+    let _ = app_handle.emit("settings-changed", SettingsChanged {
+        keys: changes.iter().map(|c| c.key.clone()).collect(),
+    });
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.join("\n"))
+    }
 }
 
 /// Update the global shortcut and re-register it
@@ -374,7 +868,7 @@ pub fn check_shortcut_conflict(
             if app_shortcut.eq_ignore_ascii_case(&shortcut) {
                 return Ok(Some(ShortcutConflict {
                     conflict_type: "app".to_string(),
-                    app_name: app.name.clone(),
+                    app_name: app.display_name(),
                     app_id: Some(app.id),
                 }));
             }
@@ -426,7 +920,7 @@ pub fn check_global_shortcut_conflict(
             if app_global_shortcut.eq_ignore_ascii_case(&shortcut) {
                 return Ok(Some(ShortcutConflict {
                     conflict_type: "app".to_string(),
-                    app_name: app.name.clone(),
+                    app_name: app.display_name(),
                     app_id: Some(app.id),
                 }));
             }
@@ -477,10 +971,28 @@ pub fn quit_app(app_handle: AppHandle) -> Result<(), String> {
 }
 
 /// Resize the main window based on grid dimensions
+///
+/// When `page_id` is given and that page has its own grid size, the page's size is used instead.
 #[tauri::command]
-pub fn resize_main_window(app_handle: AppHandle, grid_cols: i32, grid_rows: i32, tabs_visible: Option<bool>) -> Result<(), String> {
+pub fn resize_main_window(
+    app_handle: AppHandle,
+    grid_cols: i32,
+    grid_rows: i32,
+    tabs_visible: Option<bool>,
+    page_id: Option<i64>,
+) -> Result<(), String> {
     use tauri::Manager;
 
+    let page = match (page_id, app_handle.try_state::<DbPool>()) {
+        (Some(page_id), Some(pool)) => database::get_pages(&pool)
+            .map_err(|e| format!("Failed to get pages: {}", e))?
+            .into_iter()
+            .find(|p| p.id == page_id),
+        _ => None,
+    };
+    let grid_cols = page.as_ref().and_then(|p| p.grid_cols).unwrap_or(grid_cols);
+    let grid_rows = page.as_ref().and_then(|p| p.grid_rows).unwrap_or(grid_rows);
+
     // Calculate window dimensions based on grid
     // Icon item dimensions:
     // - Icon: 52px (reduced from 64px for better density)
@@ -828,20 +1340,198 @@ pub fn get_ai_settings(pool: State<DbPool>) -> Result<crate::database::AISetting
 
 /// Update AI setting
 #[tauri::command]
-pub fn update_ai_setting(pool: State<DbPool>, key: String, value: String) -> Result<(), String> {
-    database::update_ai_setting(&pool, &key, &value)
-        .map_err(|e| format!("Failed to update AI setting: {}", e))?;
-    
-    // Update queue manager max concurrent if needed
-    if key == "max_concurrent_agents" {
-        if let Ok(max) = value.parse::<i32>() {
-            if let Ok(queue_manager) = crate::ai::queue::get_queue_manager() {
-                queue_manager.set_max_concurrent(max);
+pub fn update_ai_setting(pool: State<DbPool>, app_handle: AppHandle, key: String, value: String) -> Result<(), String> {
+    let changes = crate::settings::set(&pool, &format!("ai_{}", key), &value)
+        .map_err(|e| format!("Failed to update AI setting: {:#}", e))?;
+    apply_setting_changes(&app_handle, &pool, &changes)
+}
+
+/// Write a backup archive of the database, icons and optionally webapp sessions
+#[tauri::command]
+pub async fn create_backup(
+    app_handle: AppHandle,
+    pool: State<'_, DbPool>,
+    path: String,
+    include_sessions: bool,
+) -> Result<crate::backup::BackupManifest, String> {
+    let data_dir = app_handle.path().app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?;
+    let pool = pool.inner().clone();
+
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::backup::create_backup(&pool, &data_dir, std::path::Path::new(&path), include_sessions)
+    })
+    .await
+    .map_err(|e| format!("Backup task failed: {}", e))?
+    .map_err(|e| format!("Failed to create backup: {:#}", e))
+}
+
+/// Check a backup archive before restoring it
+#[tauri::command]
+pub async fn validate_backup(path: String) -> Result<crate::backup::BackupManifest, String> {
+    tauri::async_runtime::spawn_blocking(move || crate::backup::validate_backup(std::path::Path::new(&path)))
+        .await
+        .map_err(|e| format!("Validation task failed: {}", e))?
+        .map_err(|e| format!("Invalid backup: {:#}", e))
+}
+
+/// Replace the current data with a backup and restart the launcher
+#[tauri::command]
+pub async fn restore_backup(app_handle: AppHandle, pool: State<'_, DbPool>, path: String) -> Result<(), String> {
+    let data_dir = app_handle.path().app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?;
+    let pool = pool.inner().clone();
+
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::backup::restore_backup(&pool, &data_dir, std::path::Path::new(&path))
+    })
+    .await
+    .map_err(|e| format!("Restore task failed: {}", e))?
+    .map_err(|e| format!("Failed to restore backup: {:#}", e))?;
+
+    // Restart so windows, shortcuts and background tasks pick up the restored data
+    app_handle.restart();
+}
+
+/// List backups in the app's backups folder
+#[tauri::command]
+pub fn list_backups(app_handle: AppHandle) -> Result<Vec<crate::backup::BackupFile>, String> {
+    let data_dir = app_handle.path().app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?;
+    crate::backup::list_backups(&data_dir)
+        .map_err(|e| format!("Failed to list backups: {}", e))
+}
+
+/// Export apps, settings and agents to a TOML or YAML file (chosen by extension)
+#[tauri::command]
+pub fn export_config(pool: State<DbPool>, path: String) -> Result<(), String> {
+    let config = crate::config::export(&pool)
+        .map_err(|e| format!("Failed to export configuration: {}", e))?;
+    let content = crate::config::to_string(&config, crate::config::ConfigFormat::from_path(std::path::Path::new(&path)))
+        .map_err(|e| format!("Failed to serialize configuration: {}", e))?;
+
+    std::fs::write(&path, content)
+        .map_err(|e| format!("Failed to write {}: {}", path, e))
+}
+
+/// Show what importing a configuration file would add, update and remove
+#[tauri::command]
+pub fn preview_config_import(
+    pool: State<DbPool>,
+    path: String,
+    mode: crate::config::ImportMode,
+) -> Result<crate::config::ConfigPlan, String> {
+    let config = crate::config::read(std::path::Path::new(&path)).map_err(|e| format!("{:#}", e))?;
+    crate::config::plan(&pool, &config, mode).map_err(|e| format!("{:#}", e))
+}
+
+/// Import a configuration file
+#[tauri::command]
+pub async fn apply_config_import(
+    app_handle: AppHandle,
+    pool: State<'_, DbPool>,
+    path: String,
+    mode: crate::config::ImportMode,
+) -> Result<crate::config::ConfigPlan, String> {
+    let data_dir = app_handle.path().app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?;
+    let pool = pool.inner().clone();
+
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        let config = crate::config::read(std::path::Path::new(&path))?;
+        crate::config::apply(&pool, &config, mode, &data_dir)
+    })
+    .await
+    .map_err(|e| format!("Import task failed: {}", e))?
+    .map_err(|e| format!("Failed to import configuration: {:#}", e))?;
+
+    Ok(apply_config_side_effects(&app_handle, result))
+}
+
+/// Re-register shortcuts and refresh the launcher after a configuration import
+///
+/// Shortcuts that fail to register are added to the returned plan's warnings.
+pub fn apply_config_side_effects(app_handle: &AppHandle, result: crate::config::ApplyResult) -> crate::config::ConfigPlan {
+    let mut plan = result.plan;
+
+    for change in &result.shortcut_changes {
+        if let Some(old) = change.old.as_deref().filter(|s| !s.is_empty()) {
+            let _ = crate::shortcut_manager::unregister_app_shortcut(app_handle, change.app_id, old);
+        }
+        if let Some(new) = change.new.as_deref().filter(|s| !s.is_empty()) {
+            if let Err(e) = crate::shortcut_manager::register_app_shortcut(app_handle, change.app_id, new, change.hide_on_shortcut) {
+                plan.warnings.push(format!("Failed to register global shortcut '{}': {}", new, e));
             }
         }
     }
-    
-    Ok(())
+
+    if let Some(pool) = app_handle.try_state::<DbPool>() {
+        if let Err(e) = apply_setting_changes(app_handle, &pool, &result.setting_changes) {
+            plan.warnings.push(e);
+        }
+    }
+
+    if let Some(main_window) = app_handle.get_webview_window("main") {
+        let _ = main_window.emit("app-updated", ());
+    }
+
+    plan
+}
+
+/// Get the configuration file that is applied whenever it changes
+#[tauri::command]
+pub fn get_config_watch(pool: State<DbPool>) -> Result<crate::config::WatchConfig, String> {
+    crate::config::get_watch(&pool)
+        .map_err(|e| format!("Failed to get configuration watch: {}", e))
+}
+
+/// Watch a configuration file, or stop watching when `path` is empty
+#[tauri::command]
+pub fn set_config_watch(pool: State<DbPool>, watch: crate::config::WatchConfig) -> Result<(), String> {
+    crate::config::set_watch(&pool, &watch)
+        .map_err(|e| format!("Failed to watch configuration: {:#}", e))
+}
+
+/// Get whether encrypted secrets are set up and unlocked
+#[tauri::command]
+pub fn get_secrets_status(pool: State<DbPool>) -> Result<crate::secrets::SecretsStatus, String> {
+    crate::secrets::status(&pool)
+        .map_err(|e| format!("Failed to get secrets status: {}", e))
+}
+
+/// Set up passphrase-protected secrets when the OS keyring is unavailable
+#[tauri::command]
+pub fn setup_secrets_passphrase(pool: State<DbPool>, passphrase: String) -> Result<(), String> {
+    crate::secrets::setup_passphrase(&pool, &passphrase)
+        .map_err(|e| format!("Failed to set up secrets: {}", e))
+}
+
+/// Unlock passphrase-protected secrets for this session
+#[tauri::command]
+pub fn unlock_secrets(pool: State<DbPool>, passphrase: String) -> Result<(), String> {
+    crate::secrets::unlock(&pool, &passphrase)
+        .map_err(|e| format!("Failed to unlock secrets: {}", e))
+}
+
+/// List secret names (values are never sent to the UI)
+#[tauri::command]
+pub fn list_secrets(pool: State<DbPool>) -> Result<Vec<crate::secrets::SecretInfo>, String> {
+    crate::secrets::list(&pool)
+        .map_err(|e| format!("Failed to list secrets: {}", e))
+}
+
+/// Create or replace a secret
+#[tauri::command]
+pub fn set_secret(pool: State<DbPool>, name: String, value: String) -> Result<(), String> {
+    crate::secrets::set(&pool, &name, &value)
+        .map_err(|e| format!("Failed to save secret: {}", e))
+}
+
+/// Delete a secret
+#[tauri::command]
+pub fn delete_secret(pool: State<DbPool>, name: String) -> Result<(), String> {
+    crate::secrets::delete(&pool, &name)
+        .map_err(|e| format!("Failed to delete secret: {}", e))
 }
 
 /// Fetch models from endpoint
@@ -906,18 +1596,72 @@ pub fn clear_finished_queue_items(pool: State<DbPool>) -> Result<(), String> {
 
 /// Create notification
 #[tauri::command]
-pub fn create_notification(pool: State<DbPool>, text: String) -> Result<i64, String> {
-    database::create_notification(&pool, &text)
+pub fn create_notification(
+    pool: State<DbPool>,
+    text: String,
+    title: Option<String>,
+    severity: Option<String>,
+    url: Option<String>,
+) -> Result<i64, String> {
+    let notification = crate::database::NewNotification {
+        text,
+        title,
+        url,
+        severity: crate::notifications::Urgency::parse(severity.as_deref().unwrap_or_default())
+            .as_str()
+            .to_string(),
+        app_id: None,
+        queue_id: None,
+    };
+
+    database::create_notification(&pool, &notification)
         .map_err(|e| format!("Failed to create notification: {}", e))
 }
 
-/// Get notifications
+/// Get notifications, optionally filtered by source app and severity
 #[tauri::command]
-pub fn get_notifications(pool: State<DbPool>, include_dismissed: bool) -> Result<Vec<crate::database::Notification>, String> {
-    database::get_notifications(&pool, include_dismissed)
+pub fn get_notifications(
+    pool: State<DbPool>,
+    include_dismissed: bool,
+    include_snoozed: Option<bool>,
+    app_id: Option<i64>,
+    severity: Option<String>,
+) -> Result<Vec<crate::database::Notification>, String> {
+    let filter = crate::database::NotificationFilter {
+        include_dismissed,
+        include_snoozed: include_snoozed.unwrap_or(false),
+        app_id,
+        severity,
+    };
+
+    database::get_notifications(&pool, &filter)
         .map_err(|e| format!("Failed to get notifications: {}", e))
 }
 
+/// Get the sources of active notifications
+#[tauri::command]
+pub fn get_notification_sources(pool: State<DbPool>) -> Result<Vec<crate::database::NotificationSource>, String> {
+    database::get_notification_sources(&pool)
+        .map_err(|e| format!("Failed to get notification sources: {}", e))
+}
+
+/// Hide a notification for the given number of minutes
+#[tauri::command]
+pub fn snooze_notification(pool: State<DbPool>, id: i64, minutes: i64) -> Result<(), String> {
+    if minutes <= 0 {
+        return Err("Snooze duration must be positive".to_string());
+    }
+
+    let until = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64
+        + minutes * 60;
+
+    database::snooze_notification(&pool, id, until)
+        .map_err(|e| format!("Failed to snooze notification: {}", e))
+}
+
 /// Dismiss notification
 #[tauri::command]
 pub fn dismiss_notification(pool: State<DbPool>, id: i64) -> Result<(), String> {
@@ -932,6 +1676,66 @@ pub fn dismiss_all_notifications(pool: State<DbPool>) -> Result<(), String> {
         .map_err(|e| format!("Failed to dismiss all notifications: {}", e))
 }
 
+/// Get delivery attempts for a notification
+#[tauri::command]
+pub fn get_notification_deliveries(pool: State<DbPool>, notification_id: i64) -> Result<Vec<crate::database::NotificationDelivery>, String> {
+    database::get_notification_deliveries(&pool, notification_id)
+        .map_err(|e| format!("Failed to get notification deliveries: {}", e))
+}
+
+/// Get outbound notification channels
+#[tauri::command]
+pub fn get_notification_channels(pool: State<DbPool>) -> Result<Vec<crate::database::NotificationChannel>, String> {
+    database::get_notification_channels(&pool)
+        .map_err(|e| format!("Failed to get notification channels: {}", e))
+}
+
+/// Create or update an outbound notification channel
+#[tauri::command]
+pub fn save_notification_channel(pool: State<DbPool>, channel: crate::database::NotificationChannel) -> Result<i64, String> {
+    crate::notifications::channels::validate(&channel)
+        .map_err(|e| e.to_string())?;
+
+    database::save_notification_channel(&pool, &channel)
+        .map_err(|e| format!("Failed to save notification channel: {}", e))
+}
+
+/// Delete an outbound notification channel
+#[tauri::command]
+pub fn delete_notification_channel(pool: State<DbPool>, id: i64) -> Result<(), String> {
+    database::delete_notification_channel(&pool, id)
+        .map_err(|e| format!("Failed to delete notification channel: {}", e))
+}
+
+/// Send a test message through a channel without saving it
+#[tauri::command]
+pub async fn test_notification_channel(
+    pool: State<'_, DbPool>,
+    channel: crate::database::NotificationChannel,
+) -> Result<(), String> {
+    use crate::notifications::{channels, AgentNotification, Urgency};
+
+    channels::validate(&channel).map_err(|e| e.to_string())?;
+
+    let notification = AgentNotification {
+        notification_id: 0,
+        app_id: None,
+        queue_id: None,
+        title: "jvlauncher".to_string(),
+        body: format!("Test message from channel '{}'", channel.name),
+        url: None,
+        urgency: Urgency::Normal,
+    };
+
+    let channel = channels::with_resolved_secrets(pool.inner(), &channel)
+        .map_err(|e| format!("Failed to resolve channel secrets: {}", e))?;
+
+    tauri::async_runtime::spawn_blocking(move || channels::send(&channel, &notification))
+        .await
+        .map_err(|e| format!("Test task failed: {}", e))?
+        .map_err(|e| format!("Failed to send test message: {}", e))
+}
+
 /// Open AI queue window
 #[tauri::command]
 pub fn open_ai_queue_window(app_handle: AppHandle) -> Result<(), String> {
@@ -1030,10 +1834,121 @@ pub fn open_queue_detail_window(app_handle: AppHandle, queue_id: i64) -> Result<
 /// Save agent app configuration
 #[tauri::command]
 pub fn save_agent_app(pool: State<DbPool>, agent_app: crate::database::AgentApp) -> Result<(), String> {
+    crate::ai::conditions::validate(&agent_app.conditions)
+        .map_err(|e| format!("Invalid condition: {}", e))?;
+
     database::save_agent_app(&pool, &agent_app)
         .map_err(|e| format!("Failed to save agent app: {}", e))
 }
 
+/// Run an agent configuration as a dry run (state-changing tools are recorded, not executed)
+#[tauri::command]
+pub async fn dry_run_agent(
+    app_handle: AppHandle,
+    pool: State<'_, DbPool>,
+    agent_app: crate::database::AgentApp,
+    agent_name: Option<String>,
+) -> Result<crate::ai::agent::DryRunResult, String> {
+    crate::ai::conditions::validate(&agent_app.conditions)
+        .map_err(|e| format!("Invalid condition: {}", e))?;
+
+    let pool = pool.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::ai::agent::dry_run_agent(&pool, &agent_app, agent_name.as_deref(), &app_handle)
+    })
+    .await
+    .map_err(|e| format!("Dry run task failed: {}", e))?
+    .map_err(|e| format!("Dry run failed: {}", e))
+}
+
+/// Replay a stored queue item against a different prompt and/or model
+#[tauri::command]
+pub async fn replay_queue_item(
+    app_handle: AppHandle,
+    pool: State<'_, DbPool>,
+    queue_id: i64,
+    prompt: Option<String>,
+    model: Option<String>,
+) -> Result<crate::ai::agent::ReplayResult, String> {
+    let pool = pool.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::ai::agent::replay_queue_item(&pool, queue_id, prompt.as_deref(), model.as_deref(), &app_handle)
+    })
+    .await
+    .map_err(|e| format!("Replay task failed: {}", e))?
+    .map_err(|e| format!("Replay failed: {}", e))
+}
+
+/// Export an agent to a JSON or TOML file (chosen by extension), with secrets stripped
+#[tauri::command]
+pub fn export_agent(pool: State<DbPool>, app_id: i64, path: String) -> Result<(), String> {
+    use crate::ai::portable;
+
+    let export = portable::export_agent(&pool, app_id)
+        .map_err(|e| format!("Failed to export agent: {}", e))?;
+    let content = portable::to_string(&export, portable::ExportFormat::from_path(std::path::Path::new(&path)))
+        .map_err(|e| format!("Failed to serialize agent: {}", e))?;
+
+    std::fs::write(&path, content)
+        .map_err(|e| format!("Failed to write {}: {}", path, e))
+}
+
+/// Import an agent from a JSON or TOML file, returning the new app and any warnings
+#[tauri::command]
+pub async fn import_agent(
+    app_handle: AppHandle,
+    pool: State<'_, DbPool>,
+    path: String,
+) -> Result<crate::ai::portable::ImportResult, String> {
+    use crate::ai::portable;
+
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let export = portable::parse(&content, portable::ExportFormat::from_path(std::path::Path::new(&path)))
+        .map_err(|e| format!("{:#}", e))?;
+    portable::validate(&export).map_err(|e| e.to_string())?;
+
+    let icons_dir = app_handle.path().app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?
+        .join("icons");
+
+    // Checking the model list talks to the provider, so keep it off the main thread
+    let pool = pool.inner().clone();
+    let mut result = tauri::async_runtime::spawn_blocking(move || -> anyhow::Result<portable::ImportResult> {
+        let ai_settings = database::get_ai_settings(&pool)?;
+        let available_models = if ai_settings.enabled {
+            crate::ai::llm_client::fetch_models(&pool)
+                .ok()
+                .map(|models| models.into_iter().map(|m| m.id).collect::<Vec<_>>())
+        } else {
+            None
+        };
+
+        let mut result = portable::import_agent(&pool, &export, &icons_dir)?;
+        result.warnings.extend(portable::model_warnings(
+            &ai_settings,
+            export.agent.model.as_deref(),
+            available_models.as_deref(),
+        ));
+        Ok(result)
+    })
+    .await
+    .map_err(|e| format!("Import task failed: {}", e))?
+    .map_err(|e| format!("Failed to import agent: {}", e))?;
+
+    if let Some(shortcut) = &result.global_shortcut {
+        if let Err(e) = crate::shortcut_manager::register_app_shortcut(&app_handle, result.app_id, shortcut, false) {
+            result.warnings.push(format!("Failed to register global shortcut '{}': {}", shortcut, e));
+        }
+    }
+
+    if let Some(main_window) = app_handle.get_webview_window("main") {
+        let _ = main_window.emit("app-updated", ());
+    }
+
+    Ok(result)
+}
+
 /// Get agent app configuration
 #[tauri::command]
 pub fn get_agent_app(pool: State<DbPool>, app_id: i64) -> Result<Option<crate::database::AgentApp>, String> {
@@ -1041,6 +1956,97 @@ pub fn get_agent_app(pool: State<DbPool>, app_id: i64) -> Result<Option<crate::d
         .map_err(|e| format!("Failed to get agent app: {}", e))
 }
 
+/// Get all memory entries for an agent
+#[tauri::command]
+pub fn get_agent_memory(pool: State<DbPool>, app_id: i64) -> Result<Vec<crate::database::AgentMemoryEntry>, String> {
+    database::get_agent_memory(&pool, app_id)
+        .map_err(|e| format!("Failed to get agent memory: {}", e))
+}
+
+/// Set (or overwrite) a memory entry for an agent
+#[tauri::command]
+pub fn set_agent_memory(pool: State<DbPool>, app_id: i64, key: String, value: String) -> Result<(), String> {
+    let key = key.trim();
+    if key.is_empty() {
+        return Err("Memory key must not be empty".to_string());
+    }
+
+    database::set_agent_memory_value(&pool, app_id, key, &value)
+        .map_err(|e| format!("Failed to set agent memory: {}", e))
+}
+
+/// Delete a single memory entry for an agent
+#[tauri::command]
+pub fn delete_agent_memory(pool: State<DbPool>, app_id: i64, key: String) -> Result<(), String> {
+    database::delete_agent_memory_value(&pool, app_id, &key)
+        .map_err(|e| format!("Failed to delete agent memory: {}", e))
+}
+
+/// Clear all memory for an agent
+#[tauri::command]
+pub fn clear_agent_memory(pool: State<DbPool>, app_id: i64) -> Result<(), String> {
+    database::clear_agent_memory(&pool, app_id)
+        .map_err(|e| format!("Failed to clear agent memory: {}", e))
+}
+
+/// Get all document sources used for agent document search
+#[tauri::command]
+pub fn get_document_sources(pool: State<DbPool>) -> Result<Vec<crate::database::DocumentSource>, String> {
+    database::get_document_sources(&pool)
+        .map_err(|e| format!("Failed to get document sources: {}", e))
+}
+
+/// Add a directory to be indexed for document search
+#[tauri::command]
+pub fn add_document_source(pool: State<DbPool>, path: String) -> Result<i64, String> {
+    let dir = std::path::Path::new(&path);
+    if !dir.is_dir() {
+        return Err(format!("'{}' is not a directory", path));
+    }
+
+    database::add_document_source(&pool, &path)
+        .map_err(|e| format!("Failed to add document source: {}", e))
+}
+
+/// Remove a document source and its index
+#[tauri::command]
+pub fn remove_document_source(pool: State<DbPool>, id: i64) -> Result<(), String> {
+    database::remove_document_source(&pool, id)
+        .map_err(|e| format!("Failed to remove document source: {}", e))
+}
+
+/// Enable or disable a document source
+#[tauri::command]
+pub fn set_document_source_enabled(pool: State<DbPool>, id: i64, enabled: bool) -> Result<(), String> {
+    database::set_document_source_enabled(&pool, id, enabled)
+        .map_err(|e| format!("Failed to update document source: {}", e))
+}
+
+/// Re-index all document sources (only new or changed files are embedded)
+#[tauri::command]
+pub async fn reindex_documents(pool: State<'_, DbPool>) -> Result<crate::ai::documents::IndexStats, String> {
+    let pool = pool.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || crate::ai::documents::index_all(&pool))
+        .await
+        .map_err(|e| format!("Indexing task failed: {}", e))?
+        .map_err(|e| format!("Failed to index documents: {}", e))
+}
+
+/// Search indexed documents (used to test retrieval from the UI)
+#[tauri::command]
+pub async fn search_documents(
+    pool: State<'_, DbPool>,
+    query: String,
+    top_k: Option<usize>,
+) -> Result<Vec<crate::ai::documents::DocumentMatch>, String> {
+    let pool = pool.inner().clone();
+    let top_k = top_k.unwrap_or(5);
+    tauri::async_runtime::spawn_blocking(move || crate::ai::documents::search(&pool, &query, top_k))
+        .await
+        .map_err(|e| format!("Search task failed: {}", e))?
+        .map_err(|e| format!("Failed to search documents: {}", e))
+}
+
 /// Open notifications window
 #[tauri::command]
 pub fn open_notifications_window(app_handle: AppHandle) -> Result<(), String> {
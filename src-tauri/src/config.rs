@@ -0,0 +1,1146 @@
+use crate::ai::portable::{self, ExportedAgent, REDACTED};
+use crate::database::{App, AppType, DbPool, NewApp, WindowState, WorkspaceItem};
+use crate::environment::EnvVar;
+use crate::settings::SettingChange;
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// Version of the configuration file format written by this build
+pub const FORMAT_VERSION: u32 = 1;
+
+/// How often the watched configuration file is checked for changes
+const WATCH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// File format of a configuration file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    Toml,
+    Yaml,
+}
+
+impl ConfigFormat {
+    /// Pick the format from a file extension, defaulting to TOML
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()).map(|e| e.to_lowercase()).as_deref() {
+            Some("yaml") | Some("yml") => ConfigFormat::Yaml,
+            _ => ConfigFormat::Toml,
+        }
+    }
+}
+
+/// How an imported file is combined with the current setup
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportMode {
+    /// Add and update apps from the file, keep apps that are not in it
+    Merge,
+    /// Make the launcher match the file, removing apps that are not in it
+    Replace,
+}
+
+impl ImportMode {
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "replace" => ImportMode::Replace,
+            _ => ImportMode::Merge,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImportMode::Merge => "merge",
+            ImportMode::Replace => "replace",
+        }
+    }
+}
+
+/// The launcher setup as a human-editable file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct LauncherConfig {
+    pub format_version: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub settings: Option<SettingsConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ai: Option<AiConfig>,
+    #[serde(default)]
+    pub apps: Vec<AppConfig>,
+}
+
+/// Launcher settings; settings left out of the file are not changed
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct SettingsConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub global_shortcut: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub theme: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grid_cols: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grid_rows: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_at_login: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub terminal_command: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hide_app_names: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub separate_agent_apps: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notification_retention_days: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub launch_history_retention_days: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backup_interval_hours: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backup_keep: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backup_include_sessions: Option<bool>,
+}
+
+/// AI provider settings; settings left out of the file are not changed
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct AiConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub endpoint_url: Option<String>,
+    /// Written as a `${secret:NAME}` reference or `REDACTED`, never as the key itself
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_model: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embedding_model: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_concurrent_agents: Option<i32>,
+}
+
+/// A launcher entry, identified by its name
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct AppConfig {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub app_type: AppType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shortcut: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub global_shortcut: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub binary_path: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cli_params: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub show_nav_controls: bool,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub open_external_links: bool,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub enable_oauth: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_close_timeout: Option<i32>,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub always_on_top: bool,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub hide_on_shortcut: bool,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub single_instance: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub working_dir: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub env: Vec<EnvVar>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub env_file: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pre_launch_hook: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub post_exit_hook: Option<String>,
+    /// Seconds a hook may run
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hook_timeout: Option<i32>,
+    /// Apps a workspace launches, in order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub items: Vec<WorkspaceItemConfig>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent: Option<ExportedAgent>,
+}
+
+/// An app of a workspace, referred to by its name
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct WorkspaceItemConfig {
+    pub app: String,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub delay_ms: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub geometry: Option<WindowState>,
+}
+
+fn is_false(value: &bool) -> bool {
+    !*value
+}
+
+fn is_zero(value: &i64) -> bool {
+    *value == 0
+}
+
+/// What an import would add, update or remove
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeAction {
+    Add,
+    Update,
+    Remove,
+}
+
+/// One entry of an import preview
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigChange {
+    pub action: ChangeAction,
+    pub target: String, // "setting", "ai" or "app"
+    pub name: String,
+    /// Changed fields of an updated entry
+    pub fields: Vec<String>,
+}
+
+/// Preview of an import
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigPlan {
+    pub mode: ImportMode,
+    pub changes: Vec<ConfigChange>,
+    pub warnings: Vec<String>,
+}
+
+/// A global shortcut that has to be (re-)registered after an import
+#[derive(Debug, Clone, Serialize)]
+pub struct ShortcutChange {
+    pub app_id: i64,
+    pub old: Option<String>,
+    pub new: Option<String>,
+    pub hide_on_shortcut: bool,
+}
+
+/// Outcome of an import, including what the caller needs to re-register
+#[derive(Debug, Clone, Serialize)]
+pub struct ApplyResult {
+    pub plan: ConfigPlan,
+    pub shortcut_changes: Vec<ShortcutChange>,
+    /// Settings that changed, for running their side effects
+    pub setting_changes: Vec<SettingChange>,
+}
+
+/// Watched configuration file, if any
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchConfig {
+    pub path: Option<String>,
+    pub mode: ImportMode,
+}
+
+/// Export the current setup
+pub fn export(pool: &DbPool) -> Result<LauncherConfig> {
+    let apps = crate::database::get_all_apps(pool)?
+        .into_iter()
+        .map(|app| app_to_config(pool, app))
+        .collect::<Result<_>>()?;
+
+    Ok(LauncherConfig {
+        format_version: FORMAT_VERSION,
+        settings: Some(current_settings(pool)?),
+        ai: Some(current_ai(pool)?),
+        apps,
+    })
+}
+
+/// Serialize a configuration in the given format
+pub fn to_string(config: &LauncherConfig, format: ConfigFormat) -> Result<String> {
+    Ok(match format {
+        ConfigFormat::Toml => toml::to_string_pretty(config)?,
+        ConfigFormat::Yaml => serde_yaml::to_string(config)?,
+    })
+}
+
+/// Parse a configuration file; syntax errors include the line and column
+pub fn parse(content: &str, format: ConfigFormat) -> Result<LauncherConfig> {
+    Ok(match format {
+        ConfigFormat::Toml => toml::from_str(content).map_err(|e| anyhow!("Invalid configuration TOML: {}", e))?,
+        ConfigFormat::Yaml => serde_yaml::from_str(content).map_err(|e| anyhow!("Invalid configuration YAML: {}", e))?,
+    })
+}
+
+/// Read and parse a configuration file
+pub fn read(path: &Path) -> Result<LauncherConfig> {
+    let content = std::fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    parse(&content, ConfigFormat::from_path(path))
+}
+
+/// Check a configuration, naming the entry and field of every problem found
+pub fn validate(config: &LauncherConfig) -> Result<()> {
+    let mut errors = Vec::new();
+
+    if config.format_version == 0 || config.format_version > FORMAT_VERSION {
+        errors.push(format!(
+            "formatVersion {} is not supported (this version of jvlauncher reads up to {})",
+            config.format_version, FORMAT_VERSION
+        ));
+    }
+
+    match setting_entries(config) {
+        Ok(entries) => {
+            for (field, key, value) in entries {
+                if let Err(e) = crate::settings::validate(&key, &value) {
+                    errors.push(format!("{} {}", field, e));
+                }
+            }
+        }
+        Err(e) => errors.push(e.to_string()),
+    }
+
+    let launcher_shortcut = config.settings.as_ref().and_then(|s| s.global_shortcut.as_deref());
+    for (i, app) in config.apps.iter().enumerate() {
+        let at = format!("apps[{}] ({})", i, app.name);
+        let mut error = |message: String| errors.push(format!("{}: {}", at, message));
+
+        if app.name.trim().is_empty() {
+            error("name is empty".to_string());
+        }
+        if let Some(first) = config.apps[..i].iter().position(|a| a.name == app.name) {
+            error(format!("duplicate name, also used by apps[{}]", first));
+        }
+        if let Some(shortcut) = app.shortcut.as_deref().filter(|s| !s.is_empty()) {
+            if let Some(other) = config.apps[..i].iter().position(|a| a.shortcut.as_deref() == Some(shortcut)) {
+                error(format!("shortcut '{}' is also used by apps[{}]", shortcut, other));
+            }
+        }
+        if let Some(shortcut) = app.global_shortcut.as_deref().filter(|s| !s.is_empty()) {
+            if launcher_shortcut.is_some_and(|s| s.eq_ignore_ascii_case(shortcut)) {
+                error(format!("globalShortcut '{}' is the launcher's own shortcut", shortcut));
+            }
+            if let Some(other) = config.apps[..i].iter().position(|a| {
+                a.global_shortcut.as_deref().is_some_and(|s| s.eq_ignore_ascii_case(shortcut))
+            }) {
+                error(format!("globalShortcut '{}' is also used by apps[{}]", shortcut, other));
+            }
+        }
+
+        for tag in &app.tags {
+            if tag.trim().is_empty() || tag.contains(',') {
+                error(format!("tag '{}' must not be empty or contain commas", tag));
+            }
+        }
+
+        let is_command = matches!(app.app_type, AppType::App | AppType::Tui);
+        let is_webapp = app.app_type == AppType::Webapp;
+        let is_agent = app.app_type == AppType::Agent;
+        let type_name = app.app_type.as_str();
+
+        if is_command && app.binary_path.as_deref().is_none_or(|p| p.trim().is_empty()) {
+            error(format!("binaryPath is required for {} entries", type_name));
+        }
+        if !is_command && (app.binary_path.is_some() || app.cli_params.is_some()) {
+            error(format!("binaryPath and cliParams do not apply to {} entries", type_name));
+        }
+        if !is_command && (app.working_dir.is_some() || !app.env.is_empty() || app.env_file.is_some()) {
+            error(format!("workingDir, env and envFile do not apply to {} entries", type_name));
+        }
+        if app.single_instance && app.app_type != AppType::App {
+            error(format!("singleInstance does not apply to {} entries", type_name));
+        }
+        if let Err(e) = crate::environment::validate(&app.env) {
+            error(e.to_string());
+        }
+        if let Err(e) = crate::hooks::validate(app.hook_timeout) {
+            error(e.to_string());
+        }
+        if is_webapp {
+            match app.url.as_deref().map(url::Url::parse) {
+                Some(Ok(parsed)) if parsed.scheme() == "http" || parsed.scheme() == "https" => {}
+                Some(_) => error(format!("url '{}' is not an http(s) URL", app.url.as_deref().unwrap_or_default())),
+                None => error("url is required for webapp entries".to_string()),
+            }
+        } else if app.url.is_some()
+            || app.show_nav_controls
+            || app.open_external_links
+            || app.enable_oauth
+            || app.auto_close_timeout.is_some()
+        {
+            error(format!("webapp options do not apply to {} entries", type_name));
+        }
+        if app.app_type != AppType::Workspace && !app.items.is_empty() {
+            error(format!("items do not apply to {} entries", type_name));
+        }
+        for (j, item) in app.items.iter().enumerate() {
+            if item.app == app.name || config.apps.iter().any(|a| a.name == item.app && a.app_type == AppType::Workspace) {
+                error(format!("items[{}] '{}' is a workspace; workspaces can't contain workspaces", j, item.app));
+            }
+            if app.items[..j].iter().any(|other| other.app == item.app) {
+                error(format!("items[{}] '{}' appears more than once", j, item.app));
+            }
+            if !(0..=crate::workspaces::MAX_DELAY_MS).contains(&item.delay_ms) {
+                error(format!("items[{}] delayMs must be between 0 and {}", j, crate::workspaces::MAX_DELAY_MS));
+            }
+        }
+        match (&app.agent, is_agent) {
+            (Some(agent), true) => {
+                for problem in portable::validate_agent(agent) {
+                    error(problem);
+                }
+            }
+            (None, true) => error("an [agent] section is required for agent entries".to_string()),
+            (Some(_), false) => error(format!("an [agent] section does not apply to {} entries", type_name)),
+            (None, false) => {}
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(anyhow!("Invalid configuration:\n{}", errors.join("\n")))
+    }
+}
+
+/// Work out what importing a configuration would change
+pub fn plan(pool: &DbPool, config: &LauncherConfig, mode: ImportMode) -> Result<ConfigPlan> {
+    validate(config)?;
+
+    let mut changes = Vec::new();
+    let mut warnings = Vec::new();
+
+    if let Some(desired) = &config.settings {
+        changes.extend(section_changes("setting", &current_settings(pool)?, desired)?);
+    }
+    if let Some(desired) = &config.ai {
+        if desired.api_key.as_deref() == Some(REDACTED) {
+            warnings.push("ai.apiKey is REDACTED and will not be changed".to_string());
+        }
+        let mut desired = desired.clone();
+        desired.api_key = desired.api_key.filter(|key| key != REDACTED);
+        changes.extend(section_changes("ai", &current_ai(pool)?, &desired)?);
+    }
+
+    let apps = crate::database::get_all_apps(pool)?;
+    for desired in &config.apps {
+        match apps.iter().find(|a| a.name == desired.name) {
+            Some(app) if app.app_type != desired.app_type => {
+                // The type of an entry can't be changed in place, so it is replaced
+                changes.push(app_change(ChangeAction::Remove, &app.name, Vec::new()));
+                changes.push(app_change(ChangeAction::Add, &desired.name, Vec::new()));
+            }
+            Some(app) => {
+                let current = app_to_config(pool, app.clone())?;
+                // Tags are stored sorted and without duplicates, so compare them that way
+                let desired = AppConfig {
+                    tags: sorted_tags(&desired.tags),
+                    ..desired.clone()
+                };
+                let fields = changed_fields(&serde_json::to_value(&current)?, &serde_json::to_value(&desired)?, "");
+                if !fields.is_empty() {
+                    changes.push(app_change(ChangeAction::Update, &app.name, fields));
+                }
+            }
+            None => changes.push(app_change(ChangeAction::Add, &desired.name, Vec::new())),
+        }
+
+        if let Some(icon) = &desired.icon {
+            if !Path::new(icon).exists() {
+                warnings.push(format!("Icon '{}' for '{}' does not exist", icon, desired.name));
+            }
+        }
+
+        // Workspace items can also name apps that stay from the current setup
+        for item in &desired.items {
+            let in_file = config.apps.iter().any(|a| a.name == item.app);
+            let kept = apps.iter().find(|a| a.name == item.app).filter(|_| mode == ImportMode::Merge);
+            match (in_file, kept) {
+                (false, None) => warnings.push(format!("Workspace '{}' refers to unknown app '{}'", desired.name, item.app)),
+                (false, Some(app)) if app.app_type == AppType::Workspace => warnings.push(format!(
+                    "Workspace '{}' can't contain the workspace '{}'",
+                    desired.name, item.app
+                )),
+                _ => {}
+            }
+        }
+    }
+
+    if mode == ImportMode::Replace {
+        for app in &apps {
+            if !config.apps.iter().any(|a| a.name == app.name) {
+                changes.push(app_change(ChangeAction::Remove, &app.name, Vec::new()));
+            }
+        }
+    }
+
+    Ok(ConfigPlan { mode, changes, warnings })
+}
+
+/// Import a configuration
+///
+/// `data_dir` is the app data dir, where session folders for new webapps are created.
+pub fn apply(pool: &DbPool, config: &LauncherConfig, mode: ImportMode, data_dir: &Path) -> Result<ApplyResult> {
+    let mut plan = plan(pool, config, mode)?;
+    let mut shortcut_changes = Vec::new();
+
+    let values: Vec<(String, String)> = setting_entries(config)?
+        .into_iter()
+        .map(|(_, key, value)| (key, value))
+        .collect();
+    let setting_changes = crate::settings::update(pool, &values)?;
+
+    let apps = crate::database::get_all_apps(pool)?;
+    let mut order = Vec::new();
+
+    for desired in &config.apps {
+        let existing = apps.iter().find(|a| a.name == desired.name);
+        let app_id = match existing {
+            Some(app) if app.app_type == desired.app_type => {
+                update_app(pool, app, desired, &mut plan.warnings)?;
+                shortcut_changes.push(ShortcutChange {
+                    app_id: app.id,
+                    old: app.global_shortcut.clone(),
+                    new: desired.global_shortcut.clone(),
+                    hide_on_shortcut: desired.hide_on_shortcut,
+                });
+                app.id
+            }
+            _ => {
+                if let Some(app) = existing {
+                    crate::database::delete_app(pool, app.id)?;
+                    shortcut_changes.push(ShortcutChange {
+                        app_id: app.id,
+                        old: app.global_shortcut.clone(),
+                        new: None,
+                        hide_on_shortcut: false,
+                    });
+                }
+                let app_id = create_app(pool, desired, data_dir, &mut plan.warnings)?;
+                shortcut_changes.push(ShortcutChange {
+                    app_id,
+                    old: None,
+                    new: desired.global_shortcut.clone(),
+                    hide_on_shortcut: desired.hide_on_shortcut,
+                });
+                app_id
+            }
+        };
+        order.push(app_id);
+    }
+
+    if mode == ImportMode::Replace {
+        for app in &apps {
+            if !config.apps.iter().any(|a| a.name == app.name) {
+                crate::database::delete_app(pool, app.id)?;
+                shortcut_changes.push(ShortcutChange {
+                    app_id: app.id,
+                    old: app.global_shortcut.clone(),
+                    new: None,
+                    hide_on_shortcut: false,
+                });
+            }
+        }
+        // The file's order becomes the grid order
+        crate::database::reorder_apps(pool, order)?;
+    }
+    apply_workspace_items(pool, config)?;
+
+    shortcut_changes.retain(|c| c.old != c.new || c.new.is_some());
+    Ok(ApplyResult {
+        plan,
+        shortcut_changes,
+        setting_changes,
+    })
+}
+
+/// Point the items of the file's workspaces at their apps, once every entry of the file exists.
+/// Items naming unknown apps or workspaces are left out; the plan warns about them.
+fn apply_workspace_items(pool: &DbPool, config: &LauncherConfig) -> Result<()> {
+    let apps = crate::database::get_all_apps(pool)?;
+    for desired in config.apps.iter().filter(|a| a.app_type == AppType::Workspace) {
+        let Some(workspace) = apps.iter().find(|a| a.name == desired.name && a.app_type == AppType::Workspace) else {
+            continue;
+        };
+        let items: Vec<WorkspaceItem> = desired
+            .items
+            .iter()
+            .filter_map(|item| {
+                let app = apps.iter().find(|a| a.name == item.app && a.app_type != AppType::Workspace)?;
+                Some(WorkspaceItem {
+                    app_id: app.id,
+                    delay_ms: item.delay_ms,
+                    geometry: item.geometry.clone(),
+                })
+            })
+            .collect();
+        crate::database::set_workspace_items(pool, workspace.id, &items)?;
+    }
+    Ok(())
+}
+
+/// Get the watched configuration file
+pub fn get_watch(pool: &DbPool) -> Result<WatchConfig> {
+    Ok(WatchConfig {
+        path: crate::database::get_setting(pool, "config_watch_path")?.filter(|p| !p.is_empty()),
+        mode: ImportMode::parse(&crate::database::get_setting(pool, "config_watch_mode")?.unwrap_or_default()),
+    })
+}
+
+/// Watch a configuration file (or stop watching with `None`)
+pub fn set_watch(pool: &DbPool, watch: &WatchConfig) -> Result<()> {
+    if let Some(path) = &watch.path {
+        let config = read(Path::new(path))?;
+        validate(&config)?;
+    }
+    crate::database::update_setting(pool, "config_watch_path", watch.path.as_deref().unwrap_or(""))?;
+    crate::database::update_setting(pool, "config_watch_mode", watch.mode.as_str())
+}
+
+/// Apply the watched configuration file whenever it changes
+///
+/// `on_apply` is called with the outcome of every attempt, so the caller can re-register
+/// shortcuts and refresh windows, or report an invalid file.
+pub fn start_watcher<F>(pool: DbPool, data_dir: PathBuf, on_apply: F)
+where
+    F: Fn(Result<ApplyResult>) + Send + 'static,
+{
+    std::thread::spawn(move || {
+        let mut last_seen: Option<(String, SystemTime)> = None;
+
+        loop {
+            std::thread::sleep(WATCH_INTERVAL);
+
+            let watch = match get_watch(&pool) {
+                Ok(watch) => watch,
+                Err(e) => {
+                    eprintln!("[Config] Failed to read watch settings: {}", e);
+                    continue;
+                }
+            };
+            let Some(path) = watch.path else {
+                last_seen = None;
+                continue;
+            };
+            let Ok(modified) = std::fs::metadata(&path).and_then(|m| m.modified()) else {
+                continue;
+            };
+
+            // The first look at a newly watched file only records it; it was validated when set
+            let seen = Some((path.clone(), modified));
+            let changed = last_seen.as_ref().is_some_and(|(p, _)| *p == path) && last_seen != seen;
+            last_seen = seen;
+            if !changed {
+                continue;
+            }
+
+            println!("[Config] {} changed, applying", path);
+            on_apply(read(Path::new(&path)).and_then(|config| apply(&pool, &config, watch.mode, &data_dir)));
+        }
+    });
+}
+
+fn app_change(action: ChangeAction, name: &str, fields: Vec<String>) -> ConfigChange {
+    ConfigChange {
+        action,
+        target: "app".to_string(),
+        name: name.to_string(),
+        fields,
+    }
+}
+
+/// Changes for a settings section, one per setting present in the file that differs
+fn section_changes<T: Serialize>(target: &str, current: &T, desired: &T) -> Result<Vec<ConfigChange>> {
+    let current = serde_json::to_value(current)?;
+    let desired = serde_json::to_value(desired)?;
+    let Value::Object(desired) = desired else {
+        return Ok(Vec::new());
+    };
+
+    Ok(desired
+        .iter()
+        .filter(|(key, value)| current.get(key.as_str()) != Some(*value))
+        .map(|(key, _)| ConfigChange {
+            action: ChangeAction::Update,
+            target: target.to_string(),
+            name: key.clone(),
+            fields: Vec::new(),
+        })
+        .collect())
+}
+
+/// Dotted names of the fields that differ between two entries
+fn changed_fields(current: &Value, desired: &Value, prefix: &str) -> Vec<String> {
+    let empty = serde_json::Map::new();
+    let (Value::Object(current), Value::Object(desired)) = (current, desired) else {
+        return if current == desired { Vec::new() } else { vec![prefix.to_string()] };
+    };
+
+    let mut keys: Vec<&String> = current.keys().chain(desired.keys()).collect();
+    keys.sort();
+    keys.dedup();
+
+    let mut fields = Vec::new();
+    for key in keys {
+        let name = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+        match (current.get(key), desired.get(key)) {
+            (Some(a), Some(b)) if a.is_object() && b.is_object() => fields.extend(changed_fields(a, b, &name)),
+            (Some(a), None) if a.is_object() => fields.extend(changed_fields(a, &Value::Object(empty.clone()), &name)),
+            (a, b) if a != b => fields.push(name),
+            _ => {}
+        }
+    }
+    fields
+}
+
+/// Settings that are set in the file, as (field in the file, stored key, stored value)
+///
+/// A `REDACTED` API key is left out so the stored key is kept.
+fn setting_entries(config: &LauncherConfig) -> Result<Vec<(String, String, String)>> {
+    let mut entries = Vec::new();
+    let sections = [
+        ("settings", "", serde_json::to_value(&config.settings)?),
+        ("ai", "ai_", serde_json::to_value(&config.ai)?),
+    ];
+
+    for (section, prefix, values) in sections {
+        let Value::Object(values) = values else {
+            continue;
+        };
+        for (field, value) in values {
+            let value = match value {
+                Value::String(s) => s,
+                other => other.to_string(),
+            };
+            if field == "apiKey" && value == REDACTED {
+                continue;
+            }
+            entries.push((format!("{}.{}", section, field), format!("{}{}", prefix, to_snake_case(&field)), value));
+        }
+    }
+    Ok(entries)
+}
+
+fn to_snake_case(name: &str) -> String {
+    let mut out = String::with_capacity(name.len() + 4);
+    for c in name.chars() {
+        if c.is_ascii_uppercase() {
+            out.push('_');
+            out.push(c.to_ascii_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+fn current_settings(pool: &DbPool) -> Result<SettingsConfig> {
+    let settings = crate::database::get_settings(pool)?;
+    Ok(SettingsConfig {
+        global_shortcut: Some(settings.global_shortcut),
+        theme: Some(settings.theme),
+        grid_cols: Some(settings.grid_cols),
+        grid_rows: Some(settings.grid_rows),
+        start_at_login: Some(settings.start_at_login),
+        terminal_command: settings.terminal_command,
+        hide_app_names: Some(settings.hide_app_names),
+        separate_agent_apps: Some(settings.separate_agent_apps),
+        notification_retention_days: Some(settings.notification_retention_days),
+        launch_history_retention_days: Some(settings.launch_history_retention_days),
+        backup_interval_hours: Some(settings.backup_interval_hours),
+        backup_keep: Some(settings.backup_keep),
+        backup_include_sessions: Some(settings.backup_include_sessions),
+    })
+}
+
+fn current_ai(pool: &DbPool) -> Result<AiConfig> {
+    let ai = crate::database::get_ai_settings(pool)?;
+
+    // Only secret references are exported; a key stored in plain text is never written out
+    let api_key = match ai.api_key.as_str() {
+        "" => None,
+        key if crate::secrets::has_references(key) => Some(key.to_string()),
+        _ => Some(REDACTED.to_string()),
+    };
+
+    Ok(AiConfig {
+        enabled: Some(ai.enabled),
+        endpoint_url: Some(ai.endpoint_url),
+        api_key,
+        default_model: ai.default_model,
+        embedding_model: ai.embedding_model.filter(|m| !m.is_empty()),
+        max_concurrent_agents: Some(ai.max_concurrent_agents),
+    })
+}
+
+fn app_to_config(pool: &DbPool, app: App) -> Result<AppConfig> {
+    let agent = match app.app_type {
+        AppType::Agent => match crate::database::get_agent_app(pool, app.id)? {
+            Some(agent) => Some(portable::to_exported_agent(pool, agent)?),
+            None => None,
+        },
+        _ => None,
+    };
+    let is_command = matches!(app.app_type, AppType::App | AppType::Tui);
+    let is_webapp = app.app_type == AppType::Webapp;
+    let single_instance = app.app_type == AppType::App && app.single_instance.unwrap_or(false);
+    let items = match app.app_type {
+        AppType::Workspace => {
+            let apps = crate::database::get_all_apps(pool)?;
+            crate::database::get_workspace_items(pool, app.id)?
+                .into_iter()
+                .filter_map(|item| {
+                    Some(WorkspaceItemConfig {
+                        app: apps.iter().find(|a| a.id == item.app_id)?.name.clone(),
+                        delay_ms: item.delay_ms,
+                        geometry: item.geometry,
+                    })
+                })
+                .collect()
+        }
+        _ => Vec::new(),
+    };
+
+    Ok(AppConfig {
+        name: app.name,
+        app_type: app.app_type,
+        icon: app.icon_path.filter(|p| !p.is_empty()),
+        shortcut: app.shortcut.filter(|s| !s.is_empty()),
+        global_shortcut: app.global_shortcut.filter(|s| !s.is_empty()),
+        binary_path: app.binary_path.filter(|_| is_command),
+        cli_params: app.cli_params.filter(|p| is_command && !p.is_empty()),
+        url: app.url.filter(|_| is_webapp),
+        show_nav_controls: is_webapp && app.show_nav_controls.unwrap_or(false),
+        open_external_links: is_webapp && app.open_external_links.unwrap_or(false),
+        enable_oauth: is_webapp && app.enable_oauth.unwrap_or(false),
+        auto_close_timeout: app.auto_close_timeout.filter(|_| is_webapp),
+        always_on_top: app.always_on_top.unwrap_or(false),
+        hide_on_shortcut: app.hide_on_shortcut.unwrap_or(false),
+        single_instance,
+        working_dir: app.working_dir.filter(|d| is_command && !d.is_empty()),
+        env: if is_command { app.env } else { Vec::new() },
+        env_file: app.env_file.filter(|f| is_command && !f.is_empty()),
+        pre_launch_hook: app.pre_launch_hook.filter(|h| !h.trim().is_empty()),
+        post_exit_hook: app.post_exit_hook.filter(|h| !h.trim().is_empty()),
+        hook_timeout: app.hook_timeout,
+        items,
+        tags: app.tags,
+        agent,
+    })
+}
+
+/// Tags as they are stored: trimmed, sorted and with case-insensitive duplicates removed
+fn sorted_tags(tags: &[String]) -> Vec<String> {
+    let mut tags: Vec<String> = tags.iter().map(|t| t.trim().to_string()).collect();
+    tags.sort_by_key(|t| t.to_lowercase());
+    tags.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+    tags
+}
+
+fn create_app(pool: &DbPool, desired: &AppConfig, data_dir: &Path, warnings: &mut Vec<String>) -> Result<i64> {
+    let session_dir = if desired.app_type == AppType::Webapp {
+        let unique = SystemTime::now().duration_since(std::time::UNIX_EPOCH)?.as_nanos();
+        let dir = data_dir.join("webapps").join(format!("session_{}", unique));
+        std::fs::create_dir_all(&dir)?;
+        Some(dir)
+    } else {
+        None
+    };
+
+    let app_id = crate::database::create_app(
+        pool,
+        NewApp {
+            app_type: desired.app_type.clone(),
+            name: desired.name.clone(),
+            icon_path: desired.icon.clone(),
+            shortcut: desired.shortcut.clone(),
+            global_shortcut: desired.global_shortcut.clone(),
+            binary_path: desired.binary_path.clone(),
+            cli_params: desired.cli_params.clone(),
+            url: desired.url.clone(),
+            show_nav_controls: Some(desired.show_nav_controls),
+            open_external_links: Some(desired.open_external_links),
+            enable_oauth: Some(desired.enable_oauth),
+            auto_close_timeout: desired.auto_close_timeout,
+            always_on_top: Some(desired.always_on_top),
+            hide_on_shortcut: Some(desired.hide_on_shortcut),
+            single_instance: Some(desired.single_instance),
+            working_dir: desired.working_dir.clone(),
+            env: desired.env.clone(),
+            env_file: desired.env_file.clone(),
+            pre_launch_hook: desired.pre_launch_hook.clone(),
+            post_exit_hook: desired.post_exit_hook.clone(),
+            hook_timeout: desired.hook_timeout,
+        },
+        session_dir,
+    )?;
+    crate::database::set_app_tags(pool, app_id, &desired.tags)?;
+
+    if let Some(exported) = &desired.agent {
+        let saved = portable::to_agent_app(pool, app_id, exported, warnings)
+            .and_then(|agent| crate::database::save_agent_app(pool, &agent));
+        if let Err(e) = saved {
+            // Don't leave an agent app without configuration behind
+            let _ = crate::database::delete_app(pool, app_id);
+            return Err(e.context(format!("Failed to create agent '{}'", desired.name)));
+        }
+    }
+
+    Ok(app_id)
+}
+
+fn update_app(pool: &DbPool, app: &App, desired: &AppConfig, warnings: &mut Vec<String>) -> Result<()> {
+    crate::database::update_app(
+        pool,
+        App {
+            name: desired.name.clone(),
+            icon_path: desired.icon.clone(),
+            shortcut: desired.shortcut.clone(),
+            global_shortcut: desired.global_shortcut.clone(),
+            binary_path: desired.binary_path.clone(),
+            cli_params: desired.cli_params.clone(),
+            url: desired.url.clone(),
+            show_nav_controls: Some(desired.show_nav_controls),
+            open_external_links: Some(desired.open_external_links),
+            enable_oauth: Some(desired.enable_oauth),
+            auto_close_timeout: desired.auto_close_timeout,
+            always_on_top: Some(desired.always_on_top),
+            hide_on_shortcut: Some(desired.hide_on_shortcut),
+            single_instance: Some(desired.single_instance),
+            working_dir: desired.working_dir.clone(),
+            env: desired.env.clone(),
+            env_file: desired.env_file.clone(),
+            pre_launch_hook: desired.pre_launch_hook.clone(),
+            post_exit_hook: desired.post_exit_hook.clone(),
+            hook_timeout: desired.hook_timeout,
+            ..app.clone()
+        },
+    )?;
+    crate::database::set_app_tags(pool, app.id, &desired.tags)?;
+
+    let (Some(exported), Some(stored)) = (&desired.agent, crate::database::get_agent_app(pool, app.id)?) else {
+        return Ok(());
+    };
+
+    // Values the export stripped of secrets are kept as stored unless the file changes them
+    let unchanged = portable::to_exported_agent(pool, stored.clone())?;
+    let mut agent = portable::to_agent_app(pool, app.id, exported, warnings)?;
+    if exported.prompt == unchanged.prompt {
+        agent.prompt = stored.prompt;
+    }
+    if exported.website_url == unchanged.website_url {
+        agent.website_url = stored.website_url;
+    }
+    if exported.command == unchanged.command {
+        agent.command = stored.command;
+    }
+    crate::database::save_agent_app(pool, &agent)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_pool;
+
+    const CONFIG: &str = r#"
+formatVersion = 1
+
+[settings]
+theme = "dark"
+gridCols = 5
+
+[[apps]]
+name = "Editor"
+type = "app"
+shortcut = "e"
+binaryPath = "/usr/bin/gedit"
+workingDir = "~/notes"
+tags = ["work", "Dev"]
+
+[[apps.env]]
+name = "GTK_THEME"
+value = "Adwaita:dark"
+
+[[apps.env]]
+name = "GDK_BACKEND"
+
+[[apps]]
+name = "Mail"
+type = "webapp"
+url = "https://mail.example.com"
+showNavControls = true
+preLaunchHook = "vpn-check --quiet"
+hookTimeout = 10
+
+[[apps]]
+name = "Price watcher"
+type = "agent"
+
+[apps.agent]
+prompt = "Tell me when the price drops"
+websiteUrl = "https://shop.example.com/item"
+"#;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("jvlauncher-config-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_import_then_export_roundtrip() {
+        let pool = test_pool();
+        let dir = temp_dir("roundtrip");
+        let config = parse(CONFIG, ConfigFormat::Toml).unwrap();
+
+        let result = apply(&pool, &config, ImportMode::Merge, &dir).unwrap();
+        assert_eq!(result.plan.changes.iter().filter(|c| c.action == ChangeAction::Add).count(), 3);
+        assert!(result.plan.changes.iter().any(|c| c.target == "setting" && c.name == "gridCols"));
+
+        let settings = crate::database::get_settings(&pool).unwrap();
+        assert_eq!((settings.theme.as_str(), settings.grid_cols), ("dark", 5));
+        let apps = crate::database::get_all_apps(&pool).unwrap();
+        let editor = apps.iter().find(|a| a.name == "Editor").unwrap();
+        assert_eq!(editor.tags, vec!["Dev".to_string(), "work".to_string()]);
+        assert_eq!(editor.working_dir.as_deref(), Some("~/notes"));
+        assert_eq!(editor.env.len(), 2);
+        assert_eq!(editor.env[1].value, None);
+        let mail = apps.iter().find(|a| a.name == "Mail").unwrap();
+        assert!(mail.session_data_path.as_deref().is_some_and(|p| p.starts_with(dir.to_str().unwrap())));
+        assert_eq!((mail.pre_launch_hook.as_deref(), mail.hook_timeout), (Some("vpn-check --quiet"), Some(10)));
+
+        // Importing the exported setup again changes nothing, in either format
+        let exported = export(&pool).unwrap();
+        for format in [ConfigFormat::Toml, ConfigFormat::Yaml] {
+            let reparsed = parse(&to_string(&exported, format).unwrap(), format).unwrap();
+            let plan = plan(&pool, &reparsed, ImportMode::Replace).unwrap();
+            assert!(plan.changes.is_empty(), "{:?}: {:?}", format, plan.changes);
+        }
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_plan_lists_updates_and_removals() {
+        let pool = test_pool();
+        let dir = temp_dir("plan");
+        apply(&pool, &parse(CONFIG, ConfigFormat::Toml).unwrap(), ImportMode::Merge, &dir).unwrap();
+
+        let edited = CONFIG
+            .replace("binaryPath = \"/usr/bin/gedit\"", "binaryPath = \"/usr/bin/kate\"")
+            .replace("Tell me when the price drops", "Tell me when it is cheap");
+        let mut config = parse(&edited, ConfigFormat::Toml).unwrap();
+        config.apps.retain(|a| a.name != "Mail");
+
+        let merge = plan(&pool, &config, ImportMode::Merge).unwrap();
+        assert!(!merge.changes.iter().any(|c| c.action == ChangeAction::Remove));
+        let editor = merge.changes.iter().find(|c| c.name == "Editor").unwrap();
+        assert_eq!((editor.action, editor.fields.clone()), (ChangeAction::Update, vec!["binaryPath".to_string()]));
+        let agent = merge.changes.iter().find(|c| c.name == "Price watcher").unwrap();
+        assert_eq!(agent.fields, vec!["agent.prompt".to_string()]);
+
+        let replace = apply(&pool, &config, ImportMode::Replace, &dir).unwrap();
+        assert!(replace.plan.changes.iter().any(|c| c.action == ChangeAction::Remove && c.name == "Mail"));
+        let names: Vec<String> = crate::database::get_all_apps(&pool).unwrap().into_iter().map(|a| a.name).collect();
+        assert_eq!(names, vec!["Editor".to_string(), "Price watcher".to_string()]);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_validation_points_at_entries() {
+        let bad = r#"
+formatVersion = 1
+
+[settings]
+gridCols = 50
+
+[[apps]]
+name = "Editor"
+type = "app"
+
+[[apps]]
+name = "Editor"
+type = "webapp"
+url = "not a url"
+binaryPath = "/bin/true"
+"#;
+        let error = validate(&parse(bad, ConfigFormat::Toml).unwrap()).unwrap_err().to_string();
+        assert!(error.contains("settings.gridCols must be a whole number between 1 and 20"), "{}", error);
+        assert!(error.contains("apps[0] (Editor): binaryPath is required"), "{}", error);
+        assert!(error.contains("apps[1] (Editor): duplicate name, also used by apps[0]"), "{}", error);
+        assert!(error.contains("apps[1] (Editor): url 'not a url' is not an http(s) URL"), "{}", error);
+        assert!(error.contains("apps[1] (Editor): binaryPath and cliParams do not apply"), "{}", error);
+
+        // Typos in field names are syntax errors with a position
+        let typo = "formatVersion = 1\n\n[[apps]]\nname = \"Editor\"\ntype = \"app\"\nbinaryPth = \"/bin/true\"\n";
+        let error = parse(typo, ConfigFormat::Toml).unwrap_err().to_string();
+        assert!(error.contains("binaryPth") && error.contains("line 6"), "{}", error);
+    }
+
+    #[test]
+    fn test_workspace_items_by_name() {
+        let pool = test_pool();
+        let dir = temp_dir("workspace");
+        let workspace = r#"
+formatVersion = 1
+
+[[apps]]
+name = "Morning"
+type = "workspace"
+
+[[apps.items]]
+app = "Mail"
+
+[[apps.items]]
+app = "Editor"
+delayMs = 2000
+geometry = { x = 0, y = 0, width = 960, height = 1080 }
+
+[[apps.items]]
+app = "Calendar"
+"#;
+        // Items can name entries further down the file and apps that are already set up
+        let config = parse(&format!("{}{}", workspace, &CONFIG[CONFIG.find("[[apps]]").unwrap()..]), ConfigFormat::Toml).unwrap();
+        let result = apply(&pool, &config, ImportMode::Merge, &dir).unwrap();
+        assert_eq!(result.plan.warnings, vec!["Workspace 'Morning' refers to unknown app 'Calendar'".to_string()]);
+
+        let apps = crate::database::get_all_apps(&pool).unwrap();
+        let id = |name: &str| apps.iter().find(|a| a.name == name).unwrap().id;
+        let items = crate::database::get_workspace_items(&pool, id("Morning")).unwrap();
+        assert_eq!(items.iter().map(|i| i.app_id).collect::<Vec<_>>(), vec![id("Mail"), id("Editor")]);
+        assert_eq!((items[1].delay_ms, items[1].geometry.as_ref().map(|g| g.width)), (2000, Some(960)));
+
+        let exported = export(&pool).unwrap();
+        let morning = exported.apps.iter().find(|a| a.name == "Morning").unwrap();
+        assert_eq!(morning.items.iter().map(|i| i.app.as_str()).collect::<Vec<_>>(), vec!["Mail", "Editor"]);
+        assert!(plan(&pool, &exported, ImportMode::Replace).unwrap().changes.is_empty());
+
+        let nested = workspace.replace("app = \"Calendar\"", "app = \"Morning\"");
+        let error = validate(&parse(&nested, ConfigFormat::Toml).unwrap()).unwrap_err().to_string();
+        assert!(error.contains("apps[0] (Morning): items[2] 'Morning' is a workspace"), "{}", error);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_plaintext_api_key_is_not_exported() {
+        let pool = test_pool();
+        crate::database::update_ai_setting(&pool, "api_key", "sk-live-abc").unwrap();
+
+        let exported = to_string(&export(&pool).unwrap(), ConfigFormat::Toml).unwrap();
+        assert!(!exported.contains("sk-live-abc"));
+
+        // Re-importing the redacted key leaves the stored key alone
+        let config = parse(&exported, ConfigFormat::Toml).unwrap();
+        apply(&pool, &config, ImportMode::Merge, &temp_dir("apikey")).unwrap();
+        assert_eq!(crate::database::get_ai_settings(&pool).unwrap().api_key, "sk-live-abc");
+    }
+}
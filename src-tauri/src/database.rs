@@ -3,7 +3,12 @@ use r2d2::Pool;
 use r2d2_sqlite::SqliteConnectionManager;
 use rusqlite::{params, Connection};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::environment::EnvVar;
+
+mod migrations;
 
 /// Type alias for database connection pool
 pub type DbPool = Pool<SqliteConnectionManager>;
@@ -16,6 +21,7 @@ pub enum AppType {
     Webapp,
     Tui,
     Agent,
+    Workspace,
 }
 
 impl AppType {
@@ -25,6 +31,7 @@ impl AppType {
             AppType::Webapp => "webapp",
             AppType::Tui => "tui",
             AppType::Agent => "agent",
+            AppType::Workspace => "workspace",
         }
     }
 
@@ -33,13 +40,14 @@ impl AppType {
             "webapp" => AppType::Webapp,
             "tui" => AppType::Tui,
             "agent" => AppType::Agent,
+            "workspace" => AppType::Workspace,
             _ => AppType::App,
         }
     }
 }
 
 /// Represents window position and size
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct WindowState {
     pub x: i32,
     pub y: i32,
@@ -47,6 +55,18 @@ pub struct WindowState {
     pub height: i32,
 }
 
+/// An app started by a workspace
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorkspaceItem {
+    pub app_id: i64,
+    /// Milliseconds to wait after the previous app before starting this one
+    #[serde(default)]
+    pub delay_ms: i64,
+    /// Window position and size restored after launching; None keeps the app's own placement
+    #[serde(default)]
+    pub geometry: Option<WindowState>,
+}
+
 /// Represents an application in the launcher
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct App {
@@ -68,6 +88,88 @@ pub struct App {
     pub auto_close_timeout: Option<i32>,
     pub always_on_top: Option<bool>,
     pub hide_on_shortcut: Option<bool>,
+    // Process settings for native and terminal apps
+    #[serde(default)]
+    pub single_instance: Option<bool>,
+    #[serde(default)]
+    pub working_dir: Option<String>,
+    #[serde(default)]
+    pub env: Vec<EnvVar>,
+    #[serde(default)]
+    pub env_file: Option<String>,
+    // Commands run around a launch, for every app type
+    #[serde(default)]
+    pub pre_launch_hook: Option<String>,
+    #[serde(default)]
+    pub post_exit_hook: Option<String>,
+    /// Seconds a hook may run; None uses the default
+    #[serde(default)]
+    pub hook_timeout: Option<i32>,
+    // Place in the grid; changed with `move_app`, not `update_app`
+    #[serde(default)]
+    pub page_id: i64,
+    #[serde(default)]
+    pub group_id: Option<i64>,
+    // Changed with the tag commands, not `update_app`
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+impl App {
+    /// Name with the app's tags, to tell apart apps with similar names
+    pub fn display_name(&self) -> String {
+        if self.tags.is_empty() {
+            self.name.clone()
+        } else {
+            format!("{} ({})", self.name, self.tags.join(", "))
+        }
+    }
+}
+
+/// A tag and how many apps have it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagCount {
+    pub name: String,
+    pub app_count: i64,
+}
+
+/// A named filter over tags, app types and agent tools, optionally pinned as a grid tab
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedFilter {
+    pub id: i64, // 0 when creating
+    pub name: String,
+    pub rule: crate::filters::FilterRule,
+    pub pinned: bool,
+    #[serde(default)]
+    pub position: i32,
+}
+
+/// A named page (tab) of the app grid
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Page {
+    pub id: i64,
+    pub name: String,
+    pub position: i32,
+    /// Grid size of this page, overriding the grid_cols/grid_rows settings
+    pub grid_cols: Option<i32>,
+    pub grid_rows: Option<i32>,
+}
+
+/// A folder of apps on a page, possibly inside another folder
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppGroup {
+    pub id: i64,
+    pub page_id: i64,
+    pub parent_id: Option<i64>,
+    pub name: String,
+    pub position: i32,
+}
+
+/// An entry of a page or folder; apps and folders share one ordering
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum GridItem {
+    App(i64),
+    Group(i64),
 }
 
 /// Data for creating a new app
@@ -87,6 +189,45 @@ pub struct NewApp {
     pub auto_close_timeout: Option<i32>,
     pub always_on_top: Option<bool>,
     pub hide_on_shortcut: Option<bool>,
+    #[serde(default)]
+    pub single_instance: Option<bool>,
+    #[serde(default)]
+    pub working_dir: Option<String>,
+    #[serde(default)]
+    pub env: Vec<EnvVar>,
+    #[serde(default)]
+    pub env_file: Option<String>,
+    #[serde(default)]
+    pub pre_launch_hook: Option<String>,
+    #[serde(default)]
+    pub post_exit_hook: Option<String>,
+    #[serde(default)]
+    pub hook_timeout: Option<i32>,
+}
+
+/// A recorded launch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LaunchRecord {
+    pub id: i64,
+    pub app_id: i64,
+    pub launched_at: i64,
+    pub trigger: String, // "click", "app_shortcut", "global_shortcut", "scheduler", "notification" or "workspace"
+    pub error: Option<String>,
+}
+
+/// Launch count and last use of an app
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LaunchStats {
+    pub app_id: i64,
+    pub launch_count: i64,
+    pub last_launched_at: i64,
+}
+
+/// Number of launches on a day (YYYY-MM-DD, local time)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LaunchDay {
+    pub day: String,
+    pub count: i64,
 }
 
 /// Application settings
@@ -100,6 +241,11 @@ pub struct Settings {
     pub terminal_command: Option<String>,
     pub hide_app_names: bool,
     pub separate_agent_apps: bool,
+    pub notification_retention_days: i64, // 0 keeps dismissed notifications forever
+    pub launch_history_retention_days: i64, // 0 keeps launch history forever
+    pub backup_interval_hours: i64,        // 0 disables automatic backups
+    pub backup_keep: i64,                  // number of automatic backups to keep
+    pub backup_include_sessions: bool,     // include webapp session data in automatic backups
 }
 
 /// AI settings
@@ -110,6 +256,7 @@ pub struct AISettings {
     pub api_key: String,
     pub default_model: Option<String>,
     pub max_concurrent_agents: i32,
+    pub embedding_model: Option<String>,
 }
 
 impl Default for AISettings {
@@ -120,6 +267,7 @@ impl Default for AISettings {
             api_key: String::new(),
             default_model: None,
             max_concurrent_agents: 1,
+            embedding_model: None,
         }
     }
 }
@@ -144,18 +292,77 @@ pub struct AgentApp {
     pub website_url: Option<String>,
     pub website_scrape_mode: Option<String>, // "text" or "visual"
     pub command: Option<String>,
+    #[serde(default)]
+    pub tool_memory: bool,
+    #[serde(default)]
+    pub memory_in_prompt: bool,
+    #[serde(default)]
+    pub tool_search_documents: bool,
+    #[serde(default = "default_notification_target")]
+    pub notification_target: String, // "os", "window" or "both"
+    #[serde(default)]
+    pub notification_channel_ids: Vec<i64>,
+    #[serde(default)]
+    pub conditions: Vec<crate::ai::conditions::AgentCondition>,
+    #[serde(default)]
+    pub conditions_default: crate::ai::conditions::ConditionAction, // applied when no condition holds
+}
+
+fn default_notification_target() -> String {
+    crate::notifications::TARGET_OS.to_string()
+}
+
+/// A single key/value entry in an agent's persistent memory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentMemoryEntry {
+    pub app_id: i64,
+    pub key: String,
+    pub value: String,
+    pub updated_at: i64,
+}
+
+/// A directory indexed for document retrieval
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocumentSource {
+    pub id: i64,
+    pub path: String,
+    pub enabled: bool,
+    pub last_indexed_at: Option<i64>,
+    pub file_count: i64,
+    pub chunk_count: i64,
+}
+
+/// An indexed file belonging to a document source
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocumentFile {
+    pub id: i64,
+    pub source_id: i64,
+    pub path: String,
+    pub modified_at: i64,
+    pub size: i64,
+}
+
+/// A chunk of an indexed file together with its embedding vector
+#[derive(Debug, Clone)]
+pub struct DocumentChunk {
+    pub path: String,
+    pub chunk_index: i64,
+    pub content: String,
+    pub embedding: Vec<f32>,
 }
 
 /// AI Queue item
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AIQueueItem {
     pub id: i64,
-    pub status: String, // pending, processing, completed, failed
+    pub status: String, // pending, processing, completed, failed, skipped
     pub message: String,
     pub response: Option<String>,
     pub created_at: i64,
     pub completed_at: Option<i64>,
     pub agent_name: Option<String>,
+    pub decision: Option<String>, // outcome of the agent's conditions, if it has any
+    pub app_id: Option<i64>,
 }
 
 /// Notification
@@ -165,6 +372,73 @@ pub struct Notification {
     pub text: String,
     pub created_at: i64,
     pub dismissed: bool,
+    pub app_id: Option<i64>,
+    pub queue_id: Option<i64>,
+    pub severity: String, // "low", "normal" or "critical"
+    pub title: Option<String>,
+    pub url: Option<String>,
+    pub snoozed_until: Option<i64>,
+    pub source_name: Option<String>, // name of the originating app, if it still exists
+}
+
+/// Data for creating a new notification
+#[derive(Debug, Clone)]
+pub struct NewNotification {
+    pub text: String,
+    pub title: Option<String>,
+    pub url: Option<String>,
+    pub severity: String,
+    pub app_id: Option<i64>,
+    pub queue_id: Option<i64>,
+}
+
+/// Filter for listing notifications
+#[derive(Debug, Clone, Default)]
+pub struct NotificationFilter {
+    pub include_dismissed: bool,
+    pub include_snoozed: bool,
+    pub app_id: Option<i64>,
+    pub severity: Option<String>,
+}
+
+/// A source of notifications with its number of active notifications
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationSource {
+    pub app_id: Option<i64>,
+    pub name: String,
+    pub count: i64,
+}
+
+/// Outbound notification channel (webhook, SMTP email, ntfy or Gotify push)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationChannel {
+    pub id: i64,
+    pub name: String,
+    pub kind: String, // "webhook", "smtp", "ntfy" or "gotify"
+    pub config: serde_json::Value,
+    pub enabled: bool,
+}
+
+/// An encrypted secret as stored in the database
+#[derive(Debug, Clone)]
+pub struct EncryptedSecret {
+    pub name: String,
+    pub nonce: Vec<u8>,
+    pub ciphertext: Vec<u8>,
+    pub updated_at: i64,
+}
+
+/// A single attempt to deliver a notification to an outbound channel
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationDelivery {
+    pub id: i64,
+    pub notification_id: i64,
+    pub channel_id: Option<i64>,
+    pub channel_name: String,
+    pub attempt: i64,
+    pub success: bool,
+    pub error: Option<String>,
+    pub created_at: i64,
 }
 
 impl Default for Settings {
@@ -178,6 +452,11 @@ impl Default for Settings {
             terminal_command: None,
             hide_app_names: false,
             separate_agent_apps: false,
+            notification_retention_days: 30,
+            launch_history_retention_days: 365,
+            backup_interval_hours: 24,
+            backup_keep: 7,
+            backup_include_sessions: false,
         }
     }
 }
@@ -187,146 +466,109 @@ pub fn init_database(db_path: PathBuf) -> Result<DbPool> {
     let manager = SqliteConnectionManager::file(db_path);
     let pool = Pool::new(manager)?;
     
-    let conn = pool.get()?;
-    create_schema(&conn)?;
+    let mut conn = pool.get()?;
+    migrations::migrate(&mut conn)?;
     initialize_settings(&conn)?;
     
     Ok(pool)
 }
 
-/// Create database schema
-fn create_schema(conn: &Connection) -> Result<()> {
-    // Apps table
-    conn.execute(
-        "CREATE TABLE IF NOT EXISTS apps (
-            id INTEGER PRIMARY KEY AUTOINCREMENT,
-            app_type TEXT NOT NULL,
-            name TEXT NOT NULL,
-            icon_path TEXT,
-            position INTEGER NOT NULL,
-            shortcut TEXT,
-            global_shortcut TEXT
-        )",
-        [],
-    )?;
-
-    // Add global_shortcut column to existing apps table if it doesn't exist
-    let _ = conn.execute("ALTER TABLE apps ADD COLUMN global_shortcut TEXT", []);
+/// Create an in-memory database with the full schema
+#[cfg(test)]
+pub fn test_pool() -> DbPool {
+    // In-memory databases are per-connection, so keep the pool to a single connection
+    let pool = Pool::builder()
+        .max_size(1)
+        .build(SqliteConnectionManager::memory())
+        .unwrap();
+    let mut conn = pool.get().unwrap();
+    migrations::migrate(&mut conn).unwrap();
+    initialize_settings(&conn).unwrap();
+    pool
+}
 
-    // App details table (for native apps and TUI)
-    conn.execute(
-        "CREATE TABLE IF NOT EXISTS app_details (
-            app_id INTEGER PRIMARY KEY,
-            binary_path TEXT NOT NULL,
-            cli_params TEXT,
-            FOREIGN KEY(app_id) REFERENCES apps(id) ON DELETE CASCADE
-        )",
-        [],
-    )?;
+/// Whether the database file at `db_path` exists and is behind this build's schema
+pub fn needs_migration(db_path: &Path) -> Result<bool> {
+    if !db_path.exists() {
+        return Ok(false);
+    }
+    let conn = Connection::open(db_path)?;
+    let has_tables: bool = conn.query_row("SELECT COUNT(*) > 0 FROM sqlite_master WHERE type = 'table'", [], |row| row.get(0))?;
+    Ok(has_tables && migrations::schema_version(&conn)? < migrations::latest_version())
+}
 
-    // Webapp details table
-    conn.execute(
-        "CREATE TABLE IF NOT EXISTS webapp_details (
-            app_id INTEGER PRIMARY KEY,
-            url TEXT NOT NULL,
-            session_data_path TEXT NOT NULL,
-            window_x INTEGER,
-            window_y INTEGER,
-            window_width INTEGER,
-            window_height INTEGER,
-            FOREIGN KEY(app_id) REFERENCES apps(id) ON DELETE CASCADE
-        )",
-        [],
-    )?;
+/// Schema version of a database file, and whether this build can open it
+pub fn check_database_file(db_path: &Path) -> Result<i32> {
+    let conn = Connection::open_with_flags(db_path, rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY)?;
 
-    // Add columns to existing webapp_details table if they don't exist
-    let _ = conn.execute("ALTER TABLE webapp_details ADD COLUMN window_x INTEGER", []);
-    let _ = conn.execute("ALTER TABLE webapp_details ADD COLUMN window_y INTEGER", []);
-    let _ = conn.execute("ALTER TABLE webapp_details ADD COLUMN window_width INTEGER", []);
-    let _ = conn.execute("ALTER TABLE webapp_details ADD COLUMN window_height INTEGER", []);
-    let _ = conn.execute("ALTER TABLE webapp_details ADD COLUMN show_nav_controls INTEGER DEFAULT 0", []);
-    let _ = conn.execute("ALTER TABLE webapp_details ADD COLUMN open_external_links INTEGER DEFAULT 0", []);
-    let _ = conn.execute("ALTER TABLE webapp_details ADD COLUMN enable_oauth INTEGER DEFAULT 0", []);
-    let _ = conn.execute("ALTER TABLE webapp_details ADD COLUMN auto_close_timeout INTEGER", []);
-    let _ = conn.execute("ALTER TABLE webapp_details ADD COLUMN always_on_top INTEGER DEFAULT 0", []);
-    let _ = conn.execute("ALTER TABLE webapp_details ADD COLUMN hide_on_shortcut INTEGER DEFAULT 0", []);
+    let integrity: String = conn.query_row("PRAGMA integrity_check", [], |row| row.get(0))?;
+    if integrity != "ok" {
+        return Err(anyhow::anyhow!("Database integrity check failed: {}", integrity));
+    }
 
-    // Add always_on_top and hide_on_shortcut columns to app_details table if they don't exist
-    let _ = conn.execute("ALTER TABLE app_details ADD COLUMN always_on_top INTEGER DEFAULT 0", []);
-    let _ = conn.execute("ALTER TABLE app_details ADD COLUMN hide_on_shortcut INTEGER DEFAULT 0", []);
+    let version = migrations::schema_version(&conn)?;
+    if version > migrations::latest_version() {
+        return Err(anyhow::anyhow!(
+            "The database is from a newer version of jvlauncher (schema version {}, this version supports up to {})",
+            version,
+            migrations::latest_version()
+        ));
+    }
 
-    // Settings table
-    conn.execute(
-        "CREATE TABLE IF NOT EXISTS settings (
-            key TEXT PRIMARY KEY,
-            value TEXT NOT NULL
-        )",
+    let has_apps: bool = conn.query_row(
+        "SELECT COUNT(*) > 0 FROM sqlite_master WHERE type = 'table' AND name = 'apps'",
         [],
+        |row| row.get(0),
     )?;
+    if !has_apps {
+        return Err(anyhow::anyhow!("Not a jvlauncher database"));
+    }
 
-    // AI models table
-    conn.execute(
-        "CREATE TABLE IF NOT EXISTS ai_models (
-            id TEXT PRIMARY KEY,
-            created INTEGER
-        )",
-        [],
-    )?;
+    Ok(version)
+}
 
-    // Agent apps table
-    conn.execute(
-        "CREATE TABLE IF NOT EXISTS agent_apps (
-            app_id INTEGER PRIMARY KEY,
-            model TEXT,
-            prompt TEXT NOT NULL,
-            tool_notification INTEGER DEFAULT 0,
-            tool_website_scrape INTEGER DEFAULT 0,
-            tool_run_command INTEGER DEFAULT 0,
-            website_url TEXT,
-            website_scrape_mode TEXT DEFAULT 'text',
-            command TEXT,
-            FOREIGN KEY(app_id) REFERENCES apps(id) ON DELETE CASCADE
-        )",
-        [],
-    )?;
+/// Copy a consistent snapshot of a database file using the SQLite online backup API
+pub fn backup_database_file(db_path: &Path, dest: &Path) -> Result<()> {
+    let conn = Connection::open(db_path)?;
+    conn.backup(rusqlite::DatabaseName::Main, dest, None)?;
+    Ok(())
+}
 
-    // Add website_scrape_mode column if it doesn't exist (migration)
-    let _ = conn.execute(
-        "ALTER TABLE agent_apps ADD COLUMN website_scrape_mode TEXT DEFAULT 'text'",
-        [],
-    );
+/// Copy a consistent snapshot of the open database using the SQLite online backup API
+pub fn backup_database(pool: &DbPool, dest: &Path) -> Result<()> {
+    let conn = pool.get()?;
+    conn.backup(rusqlite::DatabaseName::Main, dest, None)?;
+    Ok(())
+}
 
-    // AI queue table
-    conn.execute(
-        "CREATE TABLE IF NOT EXISTS ai_queue (
-            id INTEGER PRIMARY KEY AUTOINCREMENT,
-            status TEXT NOT NULL,
-            message TEXT NOT NULL,
-            response TEXT,
-            created_at INTEGER NOT NULL,
-            completed_at INTEGER,
-            agent_name TEXT
-        )",
-        [],
-    )?;
+/// Replace the open database's contents with a backup, then bring its schema up to date
+///
+/// The contents are swapped in place so existing pool connections stay valid.
+pub fn restore_database(pool: &DbPool, src: &Path) -> Result<()> {
+    let mut conn = pool.get()?;
+    conn.restore(rusqlite::DatabaseName::Main, src, None::<fn(rusqlite::backup::Progress)>)?;
+    migrations::migrate(&mut conn)?;
+    initialize_settings(&conn)?;
+    Ok(())
+}
 
-    // Add agent_name column if it doesn't exist (migration)
-    let _ = conn.execute(
-        "ALTER TABLE ai_queue ADD COLUMN agent_name TEXT",
-        [],
-    );
+/// Point stored icon and session paths under `old_dir` at `new_dir` (after restoring on another machine)
+pub fn rebase_data_paths(pool: &DbPool, old_dir: &str, new_dir: &str) -> Result<()> {
+    if old_dir == new_dir {
+        return Ok(());
+    }
 
-    // Notifications table
+    let conn = pool.get()?;
+    let old_len = old_dir.len() as i64;
     conn.execute(
-        "CREATE TABLE IF NOT EXISTS notifications (
-            id INTEGER PRIMARY KEY AUTOINCREMENT,
-            text TEXT NOT NULL,
-            created_at INTEGER NOT NULL,
-            dismissed INTEGER DEFAULT 0
-        )",
-        [],
+        "UPDATE apps SET icon_path = ?2 || substr(icon_path, ?3 + 1) WHERE substr(icon_path, 1, ?3) = ?1",
+        params![old_dir, new_dir, old_len],
+    )?;
+    conn.execute(
+        "UPDATE webapp_details SET session_data_path = ?2 || substr(session_data_path, ?3 + 1)
+         WHERE substr(session_data_path, 1, ?3) = ?1",
+        params![old_dir, new_dir, old_len],
     )?;
-
     Ok(())
 }
 
@@ -369,6 +611,31 @@ fn initialize_settings(conn: &Connection) -> Result<()> {
         params![if default_settings.separate_agent_apps { "true" } else { "false" }],
     )?;
 
+    conn.execute(
+        "INSERT OR IGNORE INTO settings (key, value) VALUES ('notification_retention_days', ?1)",
+        params![default_settings.notification_retention_days.to_string()],
+    )?;
+
+    conn.execute(
+        "INSERT OR IGNORE INTO settings (key, value) VALUES ('launch_history_retention_days', ?1)",
+        params![default_settings.launch_history_retention_days.to_string()],
+    )?;
+
+    conn.execute(
+        "INSERT OR IGNORE INTO settings (key, value) VALUES ('backup_interval_hours', ?1)",
+        params![default_settings.backup_interval_hours.to_string()],
+    )?;
+
+    conn.execute(
+        "INSERT OR IGNORE INTO settings (key, value) VALUES ('backup_keep', ?1)",
+        params![default_settings.backup_keep.to_string()],
+    )?;
+
+    conn.execute(
+        "INSERT OR IGNORE INTO settings (key, value) VALUES ('backup_include_sessions', ?1)",
+        params![if default_settings.backup_include_sessions { "true" } else { "false" }],
+    )?;
+
     // Initialize AI settings
     let default_ai_settings = AISettings::default();
     conn.execute(
@@ -397,14 +664,16 @@ pub fn get_all_apps(pool: &DbPool) -> Result<Vec<App>> {
     let mut stmt = conn.prepare(
         "SELECT a.id, a.app_type, a.name, a.icon_path, a.position, a.shortcut, a.global_shortcut,
                 ad.binary_path, ad.cli_params, ad.always_on_top as ad_always_on_top, ad.hide_on_shortcut as ad_hide_on_shortcut,
-                wd.url, wd.session_data_path, wd.show_nav_controls, wd.open_external_links, wd.enable_oauth, wd.auto_close_timeout, wd.always_on_top as wd_always_on_top, wd.hide_on_shortcut as wd_hide_on_shortcut
+                wd.url, wd.session_data_path, wd.show_nav_controls, wd.open_external_links, wd.enable_oauth, wd.auto_close_timeout, wd.always_on_top as wd_always_on_top, wd.hide_on_shortcut as wd_hide_on_shortcut,
+                a.page_id, a.group_id, ad.working_dir, ad.env, ad.env_file, ad.single_instance,
+                a.pre_launch_hook, a.post_exit_hook, a.hook_timeout
          FROM apps a
          LEFT JOIN app_details ad ON a.id = ad.app_id
          LEFT JOIN webapp_details wd ON a.id = wd.app_id
          ORDER BY a.position"
     )?;
 
-    let apps = stmt.query_map([], |row| {
+    let mut apps = stmt.query_map([], |row| {
         let show_nav_controls: Option<i32> = row.get(13).ok();
         let open_external_links: Option<i32> = row.get(14).ok();
         let enable_oauth: Option<i32> = row.get(15).ok();
@@ -436,10 +705,28 @@ pub fn get_all_apps(pool: &DbPool) -> Result<Vec<App>> {
             auto_close_timeout,
             always_on_top,
             hide_on_shortcut,
+            single_instance: row.get::<_, Option<i32>>(24)?.map(|v| v != 0),
+            working_dir: row.get(21)?,
+            env: row
+                .get::<_, Option<String>>(22)?
+                .and_then(|env| serde_json::from_str(&env).ok())
+                .unwrap_or_default(),
+            env_file: row.get(23)?,
+            pre_launch_hook: row.get(25)?,
+            post_exit_hook: row.get(26)?,
+            hook_timeout: row.get(27)?,
+            page_id: row.get(19)?,
+            group_id: row.get(20)?,
+            tags: Vec::new(),
         })
     })?
     .collect::<Result<Vec<_>, _>>()?;
 
+    let mut tags = load_app_tags(&conn)?;
+    for app in &mut apps {
+        app.tags = tags.remove(&app.id).unwrap_or_default();
+    }
+
     Ok(apps)
 }
 
@@ -447,17 +734,15 @@ pub fn get_all_apps(pool: &DbPool) -> Result<Vec<App>> {
 pub fn create_app(pool: &DbPool, new_app: NewApp, session_dir: Option<PathBuf>) -> Result<i64> {
     let conn = pool.get()?;
     
-    // Get the next position
-    let position: i32 = conn.query_row(
-        "SELECT COALESCE(MAX(position), -1) + 1 FROM apps",
-        [],
-        |row| row.get(0),
-    )?;
+    // New apps go at the end of the top level of the first page
+    let page_id = first_page_id(&conn)?;
+    let position = next_position(&conn, page_id, None)?;
 
     // Insert into apps table
     conn.execute(
-        "INSERT INTO apps (app_type, name, icon_path, position, shortcut, global_shortcut)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        "INSERT INTO apps (app_type, name, icon_path, position, shortcut, global_shortcut, page_id,
+                           pre_launch_hook, post_exit_hook, hook_timeout)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
         params![
             new_app.app_type.as_str(),
             new_app.name,
@@ -465,6 +750,10 @@ pub fn create_app(pool: &DbPool, new_app: NewApp, session_dir: Option<PathBuf>)
             position,
             new_app.shortcut,
             new_app.global_shortcut,
+            page_id,
+            new_app.pre_launch_hook,
+            new_app.post_exit_hook,
+            new_app.hook_timeout,
         ],
     )?;
 
@@ -477,9 +766,19 @@ pub fn create_app(pool: &DbPool, new_app: NewApp, session_dir: Option<PathBuf>)
                 let always_on_top = new_app.always_on_top.unwrap_or(false);
                 let hide_on_shortcut = new_app.hide_on_shortcut.unwrap_or(false);
                 conn.execute(
-                    "INSERT INTO app_details (app_id, binary_path, cli_params, always_on_top, hide_on_shortcut)
-                     VALUES (?1, ?2, ?3, ?4, ?5)",
-                    params![app_id, binary_path, new_app.cli_params, if always_on_top { 1 } else { 0 }, if hide_on_shortcut { 1 } else { 0 }],
+                    "INSERT INTO app_details (app_id, binary_path, cli_params, always_on_top, hide_on_shortcut, working_dir, env, env_file, single_instance)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                    params![
+                        app_id,
+                        binary_path,
+                        new_app.cli_params,
+                        if always_on_top { 1 } else { 0 },
+                        if hide_on_shortcut { 1 } else { 0 },
+                        new_app.working_dir,
+                        serde_json::to_string(&new_app.env)?,
+                        new_app.env_file,
+                        new_app.single_instance.unwrap_or(false) as i32
+                    ],
                 )?;
             }
         }
@@ -487,6 +786,9 @@ pub fn create_app(pool: &DbPool, new_app: NewApp, session_dir: Option<PathBuf>)
             // Agent apps don't need app_details, they use agent_apps table
             // Agent configuration will be saved separately via save_agent_app
         }
+        AppType::Workspace => {
+            // The apps of a workspace are saved separately via set_workspace_items
+        }
         AppType::Webapp => {
             if let Some(url) = new_app.url {
                 let session_path = session_dir
@@ -527,9 +829,19 @@ pub fn update_app(pool: &DbPool, app: App) -> Result<()> {
     let conn = pool.get()?;
 
     conn.execute(
-        "UPDATE apps SET name = ?1, icon_path = ?2, shortcut = ?3, global_shortcut = ?4
-         WHERE id = ?5",
-        params![app.name, app.icon_path, app.shortcut, app.global_shortcut, app.id],
+        "UPDATE apps SET name = ?1, icon_path = ?2, shortcut = ?3, global_shortcut = ?4,
+                pre_launch_hook = ?5, post_exit_hook = ?6, hook_timeout = ?7
+         WHERE id = ?8",
+        params![
+            app.name,
+            app.icon_path,
+            app.shortcut,
+            app.global_shortcut,
+            app.pre_launch_hook,
+            app.post_exit_hook,
+            app.hook_timeout,
+            app.id
+        ],
     )?;
 
     // Update type-specific details
@@ -538,15 +850,29 @@ pub fn update_app(pool: &DbPool, app: App) -> Result<()> {
             let always_on_top = app.always_on_top.unwrap_or(false);
             let hide_on_shortcut = app.hide_on_shortcut.unwrap_or(false);
             conn.execute(
-                "UPDATE app_details SET binary_path = ?1, cli_params = ?2, always_on_top = ?3, hide_on_shortcut = ?4
-                 WHERE app_id = ?5",
-                params![app.binary_path, app.cli_params, if always_on_top { 1 } else { 0 }, if hide_on_shortcut { 1 } else { 0 }, app.id],
+                "UPDATE app_details SET binary_path = ?1, cli_params = ?2, always_on_top = ?3, hide_on_shortcut = ?4,
+                        working_dir = ?5, env = ?6, env_file = ?7, single_instance = ?8
+                 WHERE app_id = ?9",
+                params![
+                    app.binary_path,
+                    app.cli_params,
+                    if always_on_top { 1 } else { 0 },
+                    if hide_on_shortcut { 1 } else { 0 },
+                    app.working_dir,
+                    serde_json::to_string(&app.env)?,
+                    app.env_file,
+                    app.single_instance.unwrap_or(false) as i32,
+                    app.id
+                ],
             )?;
         }
         AppType::Agent => {
             // Agent apps don't need app_details updates
             // Agent configuration is updated separately via save_agent_app
         }
+        AppType::Workspace => {
+            // The apps of a workspace are updated separately via set_workspace_items
+        }
         AppType::Webapp => {
             let show_nav_controls = app.show_nav_controls.unwrap_or(false);
             let open_external_links = app.open_external_links.unwrap_or(false);
@@ -577,6 +903,10 @@ pub fn update_app(pool: &DbPool, app: App) -> Result<()> {
 pub fn delete_app(pool: &DbPool, app_id: i64) -> Result<()> {
     let conn = pool.get()?;
     conn.execute("DELETE FROM apps WHERE id = ?1", params![app_id])?;
+    conn.execute("DELETE FROM launch_history WHERE app_id = ?1", params![app_id])?;
+    conn.execute("DELETE FROM app_tags WHERE app_id = ?1", params![app_id])?;
+    conn.execute("DELETE FROM workspace_items WHERE workspace_id = ?1 OR app_id = ?1", params![app_id])?;
+    delete_unused_tags(&conn)?;
     Ok(())
 }
 
@@ -594,169 +924,837 @@ pub fn reorder_apps(pool: &DbPool, app_ids: Vec<i64>) -> Result<()> {
     Ok(())
 }
 
-/// Get all settings
-pub fn get_settings(pool: &DbPool) -> Result<Settings> {
-    let conn = pool.get()?;
-
-    let global_shortcut: String = conn.query_row(
-        "SELECT value FROM settings WHERE key = 'global_shortcut'",
-        [],
-        |row| row.get(0),
-    ).unwrap_or_else(|_| "CommandOrControl+Shift+Space".to_string());
-
-    let theme: String = conn.query_row(
-        "SELECT value FROM settings WHERE key = 'theme'",
-        [],
-        |row| row.get(0),
-    ).unwrap_or_else(|_| "system".to_string());
-
-    let grid_cols: i32 = conn.query_row(
-        "SELECT value FROM settings WHERE key = 'grid_cols'",
-        [],
-        |row| row.get::<_, String>(0),
-    ).unwrap_or_else(|_| "4".to_string())
-    .parse()
-    .unwrap_or(4);
-
-    let grid_rows: i32 = conn.query_row(
-        "SELECT value FROM settings WHERE key = 'grid_rows'",
-        [],
-        |row| row.get::<_, String>(0),
-    ).unwrap_or_else(|_| "3".to_string())
-    .parse()
-    .unwrap_or(3);
-
-    let start_at_login: bool = conn.query_row(
-        "SELECT value FROM settings WHERE key = 'start_at_login'",
-        [],
-        |row| row.get::<_, String>(0),
-    ).unwrap_or_else(|_| "false".to_string()) == "true";
-
-    let terminal_command: Option<String> = conn.query_row(
-        "SELECT value FROM settings WHERE key = 'terminal_command'",
-        [],
-        |row| row.get(0),
-    ).ok();
+/// Tags of every app, sorted by name
+fn load_app_tags(conn: &Connection) -> Result<HashMap<i64, Vec<String>>> {
+    let mut stmt = conn.prepare(
+        "SELECT at.app_id, t.name FROM app_tags at JOIN tags t ON t.id = at.tag_id ORDER BY t.name COLLATE NOCASE",
+    )?;
+    let mut tags: HashMap<i64, Vec<String>> = HashMap::new();
+    for row in stmt.query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)))? {
+        let (app_id, name) = row?;
+        tags.entry(app_id).or_default().push(name);
+    }
+    Ok(tags)
+}
 
-    let hide_app_names: bool = conn.query_row(
-        "SELECT value FROM settings WHERE key = 'hide_app_names'",
-        [],
-        |row| row.get::<_, String>(0),
-    ).unwrap_or_else(|_| "false".to_string()) == "true";
+/// Trim a tag name and check it can be stored and listed comma-separated
+fn clean_tag(tag: &str) -> Result<&str> {
+    let tag = tag.trim();
+    if tag.is_empty() || tag.contains(',') {
+        anyhow::bail!("Tag names must not be empty or contain commas");
+    }
+    Ok(tag)
+}
 
-    let separate_agent_apps: bool = conn.query_row(
-        "SELECT value FROM settings WHERE key = 'separate_agent_apps'",
-        [],
-        |row| row.get::<_, String>(0),
-    ).unwrap_or_else(|_| "false".to_string()) == "true";
+/// Id of a tag, creating it if needed; names are case-insensitive
+fn tag_id(conn: &Connection, tag: &str) -> Result<i64> {
+    conn.execute("INSERT OR IGNORE INTO tags (name) VALUES (?1)", params![tag])?;
+    Ok(conn.query_row("SELECT id FROM tags WHERE name = ?1", params![tag], |row| row.get(0))?)
+}
 
-    Ok(Settings {
-        global_shortcut,
-        theme,
-        grid_cols,
-        grid_rows,
-        start_at_login,
-        terminal_command,
-        hide_app_names,
-        separate_agent_apps,
-    })
+/// Remove tags no app has any more
+fn delete_unused_tags(conn: &Connection) -> Result<()> {
+    conn.execute("DELETE FROM tags WHERE id NOT IN (SELECT tag_id FROM app_tags)", [])?;
+    Ok(())
 }
 
-/// Update a single setting
-pub fn update_setting(pool: &DbPool, key: &str, value: &str) -> Result<()> {
+/// Get every tag with the number of apps that have it
+pub fn get_tags(pool: &DbPool) -> Result<Vec<TagCount>> {
     let conn = pool.get()?;
+    let mut stmt = conn.prepare(
+        "SELECT t.name, COUNT(at.app_id) FROM tags t LEFT JOIN app_tags at ON at.tag_id = t.id
+         GROUP BY t.id ORDER BY t.name COLLATE NOCASE",
+    )?;
+    let tags = stmt
+        .query_map([], |row| {
+            Ok(TagCount {
+                name: row.get(0)?,
+                app_count: row.get(1)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(tags)
+}
 
+/// Tag an app
+pub fn add_app_tag(pool: &DbPool, app_id: i64, tag: &str) -> Result<()> {
+    let conn = pool.get()?;
+    let tag_id = tag_id(&conn, clean_tag(tag)?)?;
     conn.execute(
-        "INSERT OR REPLACE INTO settings (key, value) VALUES (?1, ?2)",
-        params![key, value],
+        "INSERT OR IGNORE INTO app_tags (app_id, tag_id) VALUES (?1, ?2)",
+        params![app_id, tag_id],
     )?;
-
     Ok(())
 }
 
-/// Save window state for a webapp
-pub fn save_window_state(pool: &DbPool, app_id: i64, state: &WindowState) -> Result<()> {
+/// Remove a tag from an app
+pub fn remove_app_tag(pool: &DbPool, app_id: i64, tag: &str) -> Result<()> {
     let conn = pool.get()?;
-
     conn.execute(
-        "UPDATE webapp_details SET window_x = ?1, window_y = ?2, window_width = ?3, window_height = ?4
-         WHERE app_id = ?5",
-        params![state.x, state.y, state.width, state.height, app_id],
+        "DELETE FROM app_tags WHERE app_id = ?1 AND tag_id IN (SELECT id FROM tags WHERE name = ?2)",
+        params![app_id, tag.trim()],
     )?;
+    delete_unused_tags(&conn)
+}
 
+/// Replace all tags of an app
+pub fn set_app_tags(pool: &DbPool, app_id: i64, tags: &[String]) -> Result<()> {
+    let tags = tags.iter().map(|t| clean_tag(t)).collect::<Result<Vec<_>>>()?;
+    let mut conn = pool.get()?;
+    let tx = conn.transaction()?;
+    tx.execute("DELETE FROM app_tags WHERE app_id = ?1", params![app_id])?;
+    for tag in tags {
+        let tag_id = tag_id(&tx, tag)?;
+        tx.execute(
+            "INSERT OR IGNORE INTO app_tags (app_id, tag_id) VALUES (?1, ?2)",
+            params![app_id, tag_id],
+        )?;
+    }
+    delete_unused_tags(&tx)?;
+    tx.commit()?;
     Ok(())
 }
 
-/// Load window state for a webapp
-pub fn load_window_state(pool: &DbPool, app_id: i64) -> Result<Option<WindowState>> {
-    let conn = pool.get()?;
-
-    let result = conn.query_row(
-        "SELECT window_x, window_y, window_width, window_height FROM webapp_details WHERE app_id = ?1",
-        params![app_id],
-        |row| {
-            let x: Option<i32> = row.get(0)?;
-            let y: Option<i32> = row.get(1)?;
-            let width: Option<i32> = row.get(2)?;
-            let height: Option<i32> = row.get(3)?;
+/// Rename a tag everywhere, including in saved filters; renaming onto an existing tag merges them
+pub fn rename_tag(pool: &DbPool, old: &str, new: &str) -> Result<()> {
+    let old = old.trim();
+    let new = clean_tag(new)?;
+    let mut conn = pool.get()?;
+    let tx = conn.transaction()?;
+
+    let old_id: i64 = tx
+        .query_row("SELECT id FROM tags WHERE name = ?1", params![old], |row| row.get(0))
+        .map_err(|_| anyhow::anyhow!("Tag '{}' not found", old))?;
+    let existing: Option<i64> = tx
+        .query_row("SELECT id FROM tags WHERE name = ?1", params![new], |row| row.get(0))
+        .ok();
+
+    match existing {
+        Some(new_id) if new_id != old_id => {
+            tx.execute(
+                "INSERT OR IGNORE INTO app_tags (app_id, tag_id) SELECT app_id, ?1 FROM app_tags WHERE tag_id = ?2",
+                params![new_id, old_id],
+            )?;
+            tx.execute("DELETE FROM app_tags WHERE tag_id = ?1", params![old_id])?;
+            tx.execute("DELETE FROM tags WHERE id = ?1", params![old_id])?;
+        }
+        _ => {
+            tx.execute("UPDATE tags SET name = ?1 WHERE id = ?2", params![new, old_id])?;
+        }
+    }
 
-            // Only return WindowState if all values are present
-            match (x, y, width, height) {
-                (Some(x), Some(y), Some(w), Some(h)) => Ok(Some(WindowState { x, y, width: w, height: h })),
-                _ => Ok(None),
+    for mut filter in load_saved_filters(&tx)? {
+        if filter.rule.tags.iter().any(|t| t.eq_ignore_ascii_case(old)) {
+            let has_new = filter.rule.tags.iter().any(|t| t.eq_ignore_ascii_case(new) && !t.eq_ignore_ascii_case(old));
+            filter.rule.tags.retain(|t| !t.eq_ignore_ascii_case(old));
+            if !has_new {
+                filter.rule.tags.push(new.to_string());
             }
-        },
-    );
-
-    match result {
-        Ok(state) => Ok(state),
-        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
-        Err(e) => Err(e.into()),
+            tx.execute(
+                "UPDATE saved_filters SET rule = ?1 WHERE id = ?2",
+                params![serde_json::to_string(&filter.rule)?, filter.id],
+            )?;
+        }
     }
+
+    tx.commit()?;
+    Ok(())
 }
 
-/// Get AI settings
-pub fn get_ai_settings(pool: &DbPool) -> Result<AISettings> {
+/// Remove a tag from every app
+pub fn delete_tag(pool: &DbPool, tag: &str) -> Result<()> {
     let conn = pool.get()?;
-    
-    let enabled: bool = conn.query_row(
-        "SELECT value FROM settings WHERE key = 'ai_enabled'",
-        [],
-        |row| row.get::<_, String>(0),
-    ).unwrap_or_else(|_| "false".to_string()) == "true";
-
-    let endpoint_url: String = conn.query_row(
-        "SELECT value FROM settings WHERE key = 'ai_endpoint_url'",
-        [],
-        |row| row.get(0),
-    ).unwrap_or_else(|_| "http://192.168.1.113:1234".to_string());
+    conn.execute(
+        "DELETE FROM app_tags WHERE tag_id IN (SELECT id FROM tags WHERE name = ?1)",
+        params![tag.trim()],
+    )?;
+    conn.execute("DELETE FROM tags WHERE name = ?1", params![tag.trim()])?;
+    Ok(())
+}
 
-    let api_key: String = conn.query_row(
-        "SELECT value FROM settings WHERE key = 'ai_api_key'",
-        [],
-        |row| row.get(0),
-    ).unwrap_or_else(|_| String::new());
+fn load_saved_filters(conn: &Connection) -> Result<Vec<SavedFilter>> {
+    let mut stmt = conn.prepare("SELECT id, name, rule, pinned, position FROM saved_filters ORDER BY position, id")?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, i32>(3)? != 0,
+                row.get::<_, i32>(4)?,
+            ))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut filters = Vec::new();
+    for (id, name, rule, pinned, position) in rows {
+        let rule = serde_json::from_str(&rule).unwrap_or_else(|e| {
+            eprintln!("[Database] Ignoring invalid rule of saved filter '{}': {}", name, e);
+            crate::filters::FilterRule::default()
+        });
+        filters.push(SavedFilter { id, name, rule, pinned, position });
+    }
+    Ok(filters)
+}
 
-    let default_model: Option<String> = conn.query_row(
-        "SELECT value FROM settings WHERE key = 'ai_default_model'",
-        [],
+/// Get all saved filters in order
+pub fn get_saved_filters(pool: &DbPool) -> Result<Vec<SavedFilter>> {
+    let conn = pool.get()?;
+    load_saved_filters(&conn)
+}
+
+/// Create (id 0) or update a saved filter, returning its id
+pub fn save_saved_filter(pool: &DbPool, filter: &SavedFilter) -> Result<i64> {
+    let conn = pool.get()?;
+    let rule = serde_json::to_string(&filter.rule)?;
+
+    if filter.id == 0 {
+        conn.execute(
+            "INSERT INTO saved_filters (name, rule, pinned, position)
+             VALUES (?1, ?2, ?3, (SELECT COALESCE(MAX(position), -1) + 1 FROM saved_filters))",
+            params![filter.name, rule, if filter.pinned { 1 } else { 0 }],
+        )?;
+        Ok(conn.last_insert_rowid())
+    } else {
+        conn.execute(
+            "UPDATE saved_filters SET name = ?1, rule = ?2, pinned = ?3 WHERE id = ?4",
+            params![filter.name, rule, if filter.pinned { 1 } else { 0 }, filter.id],
+        )?;
+        Ok(filter.id)
+    }
+}
+
+/// Delete a saved filter
+pub fn delete_saved_filter(pool: &DbPool, id: i64) -> Result<()> {
+    let conn = pool.get()?;
+    conn.execute("DELETE FROM saved_filters WHERE id = ?1", params![id])?;
+    Ok(())
+}
+
+/// Get all pages in order
+pub fn get_pages(pool: &DbPool) -> Result<Vec<Page>> {
+    let conn = pool.get()?;
+    let mut stmt = conn.prepare("SELECT id, name, position, grid_cols, grid_rows FROM pages ORDER BY position, id")?;
+    let pages = stmt
+        .query_map([], |row| {
+            Ok(Page {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                position: row.get(2)?,
+                grid_cols: row.get(3)?,
+                grid_rows: row.get(4)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(pages)
+}
+
+/// Add a page at the end
+pub fn create_page(pool: &DbPool, name: &str) -> Result<i64> {
+    let conn = pool.get()?;
+    conn.execute(
+        "INSERT INTO pages (name, position) VALUES (?1, (SELECT COALESCE(MAX(position), -1) + 1 FROM pages))",
+        params![name],
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+/// Rename a page and set or clear its own grid size
+pub fn update_page(pool: &DbPool, id: i64, name: &str, grid_cols: Option<i32>, grid_rows: Option<i32>) -> Result<()> {
+    let conn = pool.get()?;
+    let updated = conn.execute(
+        "UPDATE pages SET name = ?1, grid_cols = ?2, grid_rows = ?3 WHERE id = ?4",
+        params![name, grid_cols, grid_rows, id],
+    )?;
+    if updated == 0 {
+        anyhow::bail!("Page {} not found", id);
+    }
+    Ok(())
+}
+
+/// Delete a page, moving its apps and folders to the end of the first remaining page
+pub fn delete_page(pool: &DbPool, id: i64) -> Result<()> {
+    let mut conn = pool.get()?;
+    let tx = conn.transaction()?;
+
+    let target: Option<i64> = tx
+        .query_row(
+            "SELECT id FROM pages WHERE id != ?1 ORDER BY position, id LIMIT 1",
+            params![id],
+            |row| row.get(0),
+        )
+        .ok();
+    let Some(target) = target else {
+        anyhow::bail!("The last page can't be deleted");
+    };
+
+    let mut items = container_items(&tx, target, None)?;
+    items.extend(container_items(&tx, id, None)?);
+    tx.execute("UPDATE apps SET page_id = ?1 WHERE page_id = ?2", params![target, id])?;
+    tx.execute("UPDATE app_groups SET page_id = ?1 WHERE page_id = ?2", params![target, id])?;
+    write_positions(&tx, &items)?;
+    tx.execute("DELETE FROM pages WHERE id = ?1", params![id])?;
+
+    tx.commit()?;
+    Ok(())
+}
+
+/// Reorder pages by updating their positions
+pub fn reorder_pages(pool: &DbPool, page_ids: Vec<i64>) -> Result<()> {
+    let conn = pool.get()?;
+    for (position, page_id) in page_ids.iter().enumerate() {
+        conn.execute(
+            "UPDATE pages SET position = ?1 WHERE id = ?2",
+            params![position as i32, page_id],
+        )?;
+    }
+    Ok(())
+}
+
+/// Get all folders, ordered by position within their page or parent folder
+pub fn get_groups(pool: &DbPool) -> Result<Vec<AppGroup>> {
+    let conn = pool.get()?;
+    let mut stmt = conn.prepare("SELECT id, page_id, parent_id, name, position FROM app_groups ORDER BY position, id")?;
+    let groups = stmt
+        .query_map([], |row| {
+            Ok(AppGroup {
+                id: row.get(0)?,
+                page_id: row.get(1)?,
+                parent_id: row.get(2)?,
+                name: row.get(3)?,
+                position: row.get(4)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(groups)
+}
+
+/// Add a folder at the end of a page or of another folder
+pub fn create_group(pool: &DbPool, name: &str, page_id: i64, parent_id: Option<i64>) -> Result<i64> {
+    let conn = pool.get()?;
+    check_container(&conn, page_id, parent_id)?;
+    let position = next_position(&conn, page_id, parent_id)?;
+    conn.execute(
+        "INSERT INTO app_groups (page_id, parent_id, name, position) VALUES (?1, ?2, ?3, ?4)",
+        params![page_id, parent_id, name, position],
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+/// Rename a folder
+pub fn rename_group(pool: &DbPool, id: i64, name: &str) -> Result<()> {
+    let conn = pool.get()?;
+    let updated = conn.execute("UPDATE app_groups SET name = ?1 WHERE id = ?2", params![name, id])?;
+    if updated == 0 {
+        anyhow::bail!("Folder {} not found", id);
+    }
+    Ok(())
+}
+
+/// Delete a folder, moving what it contains to where the folder was
+pub fn delete_group(pool: &DbPool, id: i64) -> Result<()> {
+    let mut conn = pool.get()?;
+    let tx = conn.transaction()?;
+
+    let (page_id, parent_id) = group_location(&tx, id)?;
+    let children = container_items(&tx, page_id, Some(id))?;
+    let mut items = container_items(&tx, page_id, parent_id)?;
+    match items.iter().position(|item| *item == GridItem::Group(id)) {
+        Some(index) => {
+            items.splice(index..=index, children);
+        }
+        None => items.extend(children),
+    }
+
+    tx.execute("UPDATE apps SET group_id = ?1 WHERE group_id = ?2", params![parent_id, id])?;
+    tx.execute("UPDATE app_groups SET parent_id = ?1 WHERE parent_id = ?2", params![parent_id, id])?;
+    tx.execute("DELETE FROM app_groups WHERE id = ?1", params![id])?;
+    write_positions(&tx, &items)?;
+
+    tx.commit()?;
+    Ok(())
+}
+
+/// Move an app to `index` within a page or folder; this also reorders within the same one
+pub fn move_app(pool: &DbPool, app_id: i64, page_id: i64, group_id: Option<i64>, index: usize) -> Result<()> {
+    let mut conn = pool.get()?;
+    let tx = conn.transaction()?;
+    check_container(&tx, page_id, group_id)?;
+
+    let (old_page, old_group): (i64, Option<i64>) = tx
+        .query_row("SELECT page_id, group_id FROM apps WHERE id = ?1", params![app_id], |row| {
+            Ok((row.get(0)?, row.get(1)?))
+        })
+        .map_err(|_| anyhow::anyhow!("App {} not found", app_id))?;
+
+    move_item(&tx, GridItem::App(app_id), (old_page, old_group), (page_id, group_id), index)?;
+    tx.execute(
+        "UPDATE apps SET page_id = ?1, group_id = ?2 WHERE id = ?3",
+        params![page_id, group_id, app_id],
+    )?;
+
+    tx.commit()?;
+    Ok(())
+}
+
+/// Move a folder, with everything in it, to `index` within a page or another folder
+pub fn move_group(pool: &DbPool, id: i64, page_id: i64, parent_id: Option<i64>, index: usize) -> Result<()> {
+    let mut conn = pool.get()?;
+    let tx = conn.transaction()?;
+    check_container(&tx, page_id, parent_id)?;
+
+    // A folder can't end up inside itself
+    let mut ancestor = parent_id;
+    while let Some(current) = ancestor {
+        if current == id {
+            anyhow::bail!("A folder can't be moved into itself");
+        }
+        ancestor = group_location(&tx, current)?.1;
+    }
+
+    let old = group_location(&tx, id)?;
+    move_item(&tx, GridItem::Group(id), old, (page_id, parent_id), index)?;
+    tx.execute(
+        "UPDATE app_groups SET page_id = ?1, parent_id = ?2 WHERE id = ?3",
+        params![page_id, parent_id, id],
+    )?;
+
+    // Everything inside follows the folder to its new page
+    if old.0 != page_id {
+        tx.execute(
+            "WITH RECURSIVE inner(id) AS (
+                SELECT ?1 UNION ALL SELECT g.id FROM app_groups g JOIN inner ON g.parent_id = inner.id
+             )
+             UPDATE app_groups SET page_id = ?2 WHERE id IN inner",
+            params![id, page_id],
+        )?;
+        tx.execute(
+            "WITH RECURSIVE inner(id) AS (
+                SELECT ?1 UNION ALL SELECT g.id FROM app_groups g JOIN inner ON g.parent_id = inner.id
+             )
+             UPDATE apps SET page_id = ?2 WHERE group_id IN inner",
+            params![id, page_id],
+        )?;
+    }
+
+    tx.commit()?;
+    Ok(())
+}
+
+/// Id of the first page
+fn first_page_id(conn: &Connection) -> Result<i64> {
+    Ok(conn.query_row("SELECT id FROM pages ORDER BY position, id LIMIT 1", [], |row| row.get(0))?)
+}
+
+/// Page and parent folder of a folder
+fn group_location(conn: &Connection, id: i64) -> Result<(i64, Option<i64>)> {
+    conn.query_row("SELECT page_id, parent_id FROM app_groups WHERE id = ?1", params![id], |row| {
+        Ok((row.get(0)?, row.get(1)?))
+    })
+    .map_err(|_| anyhow::anyhow!("Folder {} not found", id))
+}
+
+/// Check that a page exists and, if given, that the folder is on it
+fn check_container(conn: &Connection, page_id: i64, group_id: Option<i64>) -> Result<()> {
+    let page_exists: bool =
+        conn.query_row("SELECT COUNT(*) > 0 FROM pages WHERE id = ?1", params![page_id], |row| row.get(0))?;
+    if !page_exists {
+        anyhow::bail!("Page {} not found", page_id);
+    }
+    if let Some(group_id) = group_id {
+        if group_location(conn, group_id)?.0 != page_id {
+            anyhow::bail!("Folder {} is not on page {}", group_id, page_id);
+        }
+    }
+    Ok(())
+}
+
+/// Apps and folders directly in a page (`group_id` None) or folder, in order
+fn container_items(conn: &Connection, page_id: i64, group_id: Option<i64>) -> Result<Vec<GridItem>> {
+    let mut stmt = conn.prepare(
+        "SELECT 'app', id, position FROM apps WHERE page_id = ?1 AND group_id IS ?2
+         UNION ALL
+         SELECT 'group', id, position FROM app_groups WHERE page_id = ?1 AND parent_id IS ?2
+         ORDER BY 3, 1, 2",
+    )?;
+    let items = stmt
+        .query_map(params![page_id, group_id], |row| {
+            let kind: String = row.get(0)?;
+            let id: i64 = row.get(1)?;
+            Ok(if kind == "app" { GridItem::App(id) } else { GridItem::Group(id) })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(items)
+}
+
+/// Position after the last item of a page or folder
+fn next_position(conn: &Connection, page_id: i64, group_id: Option<i64>) -> Result<i32> {
+    Ok(conn.query_row(
+        "SELECT COALESCE(MAX(position), -1) + 1 FROM (
+            SELECT position FROM apps WHERE page_id = ?1 AND group_id IS ?2
+            UNION ALL
+            SELECT position FROM app_groups WHERE page_id = ?1 AND parent_id IS ?2
+         )",
+        params![page_id, group_id],
         |row| row.get(0),
-    ).ok();
+    )?)
+}
 
-    let max_concurrent_agents: i32 = conn.query_row(
-        "SELECT value FROM settings WHERE key = 'ai_max_concurrent_agents'",
-        [],
-        |row| row.get::<_, String>(0),
-    ).unwrap_or_else(|_| "1".to_string())
-    .parse()
-    .unwrap_or(1);
+/// Number items 0.. in the given order
+fn write_positions(conn: &Connection, items: &[GridItem]) -> Result<()> {
+    for (position, item) in items.iter().enumerate() {
+        match item {
+            GridItem::App(id) => conn.execute("UPDATE apps SET position = ?1 WHERE id = ?2", params![position as i32, id])?,
+            GridItem::Group(id) => {
+                conn.execute("UPDATE app_groups SET position = ?1 WHERE id = ?2", params![position as i32, id])?
+            }
+        };
+    }
+    Ok(())
+}
+
+/// Take an item out of its old page or folder and insert it at `index` in the new one
+fn move_item(
+    conn: &Connection,
+    item: GridItem,
+    from: (i64, Option<i64>),
+    to: (i64, Option<i64>),
+    index: usize,
+) -> Result<()> {
+    let mut source = container_items(conn, from.0, from.1)?;
+    source.retain(|i| *i != item);
+    if from != to {
+        write_positions(conn, &source)?;
+        source = container_items(conn, to.0, to.1)?;
+    }
+    source.insert(index.min(source.len()), item);
+    write_positions(conn, &source)
+}
+
+/// Load all stored setting values
+fn load_setting_values(pool: &DbPool) -> Result<HashMap<String, String>> {
+    let conn = pool.get()?;
+    let mut stmt = conn.prepare("SELECT key, value FROM settings")?;
+    let values = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<Result<HashMap<_, _>, _>>()?;
+    Ok(values)
+}
+
+/// Record a launch; `error` is set when the launch failed
+pub fn record_launch(pool: &DbPool, app_id: i64, trigger: &str, launched_at: i64, error: Option<&str>) -> Result<i64> {
+    let conn = pool.get()?;
+    conn.execute(
+        "INSERT INTO launch_history (app_id, launched_at, trigger, error) VALUES (?1, ?2, ?3, ?4)",
+        params![app_id, launched_at, trigger, error],
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+/// Get recent launches, newest first, optionally for a single app
+pub fn get_launch_history(pool: &DbPool, app_id: Option<i64>, limit: i64) -> Result<Vec<LaunchRecord>> {
+    let conn = pool.get()?;
+    let mut stmt = conn.prepare(
+        "SELECT id, app_id, launched_at, trigger, error FROM launch_history
+         WHERE ?1 IS NULL OR app_id = ?1
+         ORDER BY launched_at DESC, id DESC
+         LIMIT ?2",
+    )?;
+
+    let records = stmt
+        .query_map(params![app_id, limit], |row| {
+            Ok(LaunchRecord {
+                id: row.get(0)?,
+                app_id: row.get(1)?,
+                launched_at: row.get(2)?,
+                trigger: row.get(3)?,
+                error: row.get(4)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(records)
+}
+
+/// Mark an app's most recent launch as failed, e.g. when the process exited right after starting
+pub fn set_last_launch_error(pool: &DbPool, app_id: i64, error: &str) -> Result<()> {
+    let conn = pool.get()?;
+    conn.execute(
+        "UPDATE launch_history SET error = ?2
+         WHERE id = (SELECT id FROM launch_history WHERE app_id = ?1 ORDER BY launched_at DESC, id DESC LIMIT 1)",
+        params![app_id, error],
+    )?;
+    Ok(())
+}
+
+/// Get an app's failed launches, newest first
+pub fn get_launch_errors(pool: &DbPool, app_id: i64, limit: i64) -> Result<Vec<LaunchRecord>> {
+    let conn = pool.get()?;
+    let mut stmt = conn.prepare(
+        "SELECT id, app_id, launched_at, trigger, error FROM launch_history
+         WHERE app_id = ?1 AND error IS NOT NULL
+         ORDER BY launched_at DESC, id DESC
+         LIMIT ?2",
+    )?;
+
+    let records = stmt
+        .query_map(params![app_id, limit], |row| {
+            Ok(LaunchRecord {
+                id: row.get(0)?,
+                app_id: row.get(1)?,
+                launched_at: row.get(2)?,
+                trigger: row.get(3)?,
+                error: row.get(4)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(records)
+}
+
+/// Get successful launch counts and last-used times per app since a time (all time for 0)
+pub fn get_launch_stats(pool: &DbPool, since: i64) -> Result<Vec<LaunchStats>> {
+    let conn = pool.get()?;
+    let mut stmt = conn.prepare(
+        "SELECT app_id, COUNT(*), MAX(launched_at) FROM launch_history
+         WHERE launched_at >= ?1 AND error IS NULL
+         GROUP BY app_id
+         ORDER BY COUNT(*) DESC, MAX(launched_at) DESC",
+    )?;
+
+    let stats = stmt
+        .query_map(params![since], |row| {
+            Ok(LaunchStats {
+                app_id: row.get(0)?,
+                launch_count: row.get(1)?,
+                last_launched_at: row.get(2)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(stats)
+}
+
+/// Get successful launches per local calendar day since a time, optionally for a single app
+pub fn get_launch_histogram(pool: &DbPool, app_id: Option<i64>, since: i64) -> Result<Vec<LaunchDay>> {
+    let conn = pool.get()?;
+    let mut stmt = conn.prepare(
+        "SELECT date(launched_at, 'unixepoch', 'localtime') AS day, COUNT(*) FROM launch_history
+         WHERE launched_at >= ?1 AND error IS NULL AND (?2 IS NULL OR app_id = ?2)
+         GROUP BY day
+         ORDER BY day",
+    )?;
+
+    let days = stmt
+        .query_map(params![since, app_id], |row| {
+            Ok(LaunchDay {
+                day: row.get(0)?,
+                count: row.get(1)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(days)
+}
+
+/// Get a frecency score per app: successful launches weighted by how recent they are
+pub fn get_launch_frecency(pool: &DbPool, now: i64) -> Result<HashMap<i64, f64>> {
+    let conn = pool.get()?;
+    let day = 24 * 60 * 60;
+    let mut stmt = conn.prepare(
+        "SELECT app_id, SUM(CASE
+                WHEN launched_at >= ?1 THEN 100
+                WHEN launched_at >= ?2 THEN 70
+                WHEN launched_at >= ?3 THEN 50
+                WHEN launched_at >= ?4 THEN 30
+                ELSE 10
+            END)
+         FROM launch_history
+         WHERE error IS NULL
+         GROUP BY app_id",
+    )?;
+
+    let frecency = stmt
+        .query_map(params![now - 4 * day, now - 14 * day, now - 31 * day, now - 90 * day], |row| {
+            Ok((row.get(0)?, row.get::<_, i64>(1)? as f64))
+        })?
+        .collect::<Result<HashMap<_, _>, _>>()?;
+
+    Ok(frecency)
+}
+
+/// Delete launch history older than a cutoff, returning how many launches were removed
+pub fn delete_launch_history_before(pool: &DbPool, cutoff: i64) -> Result<usize> {
+    let conn = pool.get()?;
+    let deleted = conn.execute("DELETE FROM launch_history WHERE launched_at < ?1", params![cutoff])?;
+    Ok(deleted)
+}
+
+/// Get all settings
+pub fn get_settings(pool: &DbPool) -> Result<Settings> {
+    use crate::settings::parse_or;
+
+    let values = load_setting_values(pool)?;
+    let defaults = Settings::default();
+
+    Ok(Settings {
+        global_shortcut: values.get("global_shortcut").cloned().unwrap_or(defaults.global_shortcut),
+        theme: values.get("theme").cloned().unwrap_or(defaults.theme),
+        grid_cols: parse_or(&values, "grid_cols", defaults.grid_cols),
+        grid_rows: parse_or(&values, "grid_rows", defaults.grid_rows),
+        start_at_login: parse_or(&values, "start_at_login", defaults.start_at_login),
+        terminal_command: values.get("terminal_command").cloned(),
+        hide_app_names: parse_or(&values, "hide_app_names", defaults.hide_app_names),
+        separate_agent_apps: parse_or(&values, "separate_agent_apps", defaults.separate_agent_apps),
+        notification_retention_days: parse_or(&values, "notification_retention_days", defaults.notification_retention_days),
+        launch_history_retention_days: parse_or(&values, "launch_history_retention_days", defaults.launch_history_retention_days),
+        backup_interval_hours: parse_or(&values, "backup_interval_hours", defaults.backup_interval_hours),
+        backup_keep: parse_or(&values, "backup_keep", defaults.backup_keep),
+        backup_include_sessions: parse_or(&values, "backup_include_sessions", defaults.backup_include_sessions),
+    })
+}
+
+/// Get a single raw setting value
+pub fn get_setting(pool: &DbPool, key: &str) -> Result<Option<String>> {
+    let conn = pool.get()?;
+
+    match conn.query_row("SELECT value FROM settings WHERE key = ?1", params![key], |row| row.get(0)) {
+        Ok(value) => Ok(Some(value)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Update a single setting
+pub fn update_setting(pool: &DbPool, key: &str, value: &str) -> Result<()> {
+    let conn = pool.get()?;
+
+    conn.execute(
+        "INSERT OR REPLACE INTO settings (key, value) VALUES (?1, ?2)",
+        params![key, value],
+    )?;
+
+    Ok(())
+}
+
+/// Get the apps of a workspace in launch order
+pub fn get_workspace_items(pool: &DbPool, workspace_id: i64) -> Result<Vec<WorkspaceItem>> {
+    let conn = pool.get()?;
+    let mut stmt = conn.prepare(
+        "SELECT app_id, delay_ms, window_x, window_y, window_width, window_height FROM workspace_items
+         WHERE workspace_id = ?1
+         ORDER BY position",
+    )?;
+
+    let items = stmt
+        .query_map(params![workspace_id], |row| {
+            let geometry = match (row.get(2)?, row.get(3)?, row.get(4)?, row.get(5)?) {
+                (Some(x), Some(y), Some(width), Some(height)) => Some(WindowState { x, y, width, height }),
+                _ => None,
+            };
+            Ok(WorkspaceItem {
+                app_id: row.get(0)?,
+                delay_ms: row.get(1)?,
+                geometry,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(items)
+}
+
+/// Replace the apps of a workspace; their order is the launch order
+pub fn set_workspace_items(pool: &DbPool, workspace_id: i64, items: &[WorkspaceItem]) -> Result<()> {
+    let mut conn = pool.get()?;
+    let tx = conn.transaction()?;
+    tx.execute("DELETE FROM workspace_items WHERE workspace_id = ?1", params![workspace_id])?;
+    for (position, item) in items.iter().enumerate() {
+        let geometry = item.geometry.as_ref();
+        tx.execute(
+            "INSERT INTO workspace_items (workspace_id, app_id, position, delay_ms, window_x, window_y, window_width, window_height)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                workspace_id,
+                item.app_id,
+                position as i64,
+                item.delay_ms,
+                geometry.map(|g| g.x),
+                geometry.map(|g| g.y),
+                geometry.map(|g| g.width),
+                geometry.map(|g| g.height),
+            ],
+        )?;
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+/// Save where a workspace's app window was, to be restored on the next launch of the workspace
+pub fn save_workspace_geometry(pool: &DbPool, workspace_id: i64, app_id: i64, state: &WindowState) -> Result<()> {
+    let conn = pool.get()?;
+    conn.execute(
+        "UPDATE workspace_items SET window_x = ?1, window_y = ?2, window_width = ?3, window_height = ?4
+         WHERE workspace_id = ?5 AND app_id = ?6",
+        params![state.x, state.y, state.width, state.height, workspace_id, app_id],
+    )?;
+    Ok(())
+}
+
+/// Save window state for a webapp
+pub fn save_window_state(pool: &DbPool, app_id: i64, state: &WindowState) -> Result<()> {
+    let conn = pool.get()?;
+
+    conn.execute(
+        "UPDATE webapp_details SET window_x = ?1, window_y = ?2, window_width = ?3, window_height = ?4
+         WHERE app_id = ?5",
+        params![state.x, state.y, state.width, state.height, app_id],
+    )?;
+
+    Ok(())
+}
+
+/// Load window state for a webapp
+pub fn load_window_state(pool: &DbPool, app_id: i64) -> Result<Option<WindowState>> {
+    let conn = pool.get()?;
+
+    let result = conn.query_row(
+        "SELECT window_x, window_y, window_width, window_height FROM webapp_details WHERE app_id = ?1",
+        params![app_id],
+        |row| {
+            let x: Option<i32> = row.get(0)?;
+            let y: Option<i32> = row.get(1)?;
+            let width: Option<i32> = row.get(2)?;
+            let height: Option<i32> = row.get(3)?;
+
+            // Only return WindowState if all values are present
+            match (x, y, width, height) {
+                (Some(x), Some(y), Some(w), Some(h)) => Ok(Some(WindowState { x, y, width: w, height: h })),
+                _ => Ok(None),
+            }
+        },
+    );
+
+    match result {
+        Ok(state) => Ok(state),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Get AI settings
+pub fn get_ai_settings(pool: &DbPool) -> Result<AISettings> {
+    use crate::settings::parse_or;
+
+    let values = load_setting_values(pool)?;
+    let defaults = AISettings::default();
 
     Ok(AISettings {
-        enabled,
-        endpoint_url,
-        api_key,
-        default_model,
-        max_concurrent_agents,
+        enabled: parse_or(&values, "ai_enabled", defaults.enabled),
+        endpoint_url: values.get("ai_endpoint_url").cloned().unwrap_or(defaults.endpoint_url),
+        api_key: values.get("ai_api_key").cloned().unwrap_or(defaults.api_key),
+        default_model: values.get("ai_default_model").cloned(),
+        max_concurrent_agents: parse_or(&values, "ai_max_concurrent_agents", defaults.max_concurrent_agents),
+        embedding_model: values.get("ai_embedding_model").cloned().filter(|m| !m.is_empty()),
     })
 }
 
@@ -814,61 +1812,343 @@ pub fn save_models(pool: &DbPool, models: Vec<AIModel>) -> Result<()> {
     Ok(())
 }
 
-/// Get agent app configuration
-pub fn get_agent_app(pool: &DbPool, app_id: i64) -> Result<Option<AgentApp>> {
+/// Get agent app configuration
+pub fn get_agent_app(pool: &DbPool, app_id: i64) -> Result<Option<AgentApp>> {
+    let conn = pool.get()?;
+    
+    let result = conn.query_row(
+        "SELECT app_id, model, prompt, tool_notification, tool_website_scrape, tool_run_command, website_url, website_scrape_mode, command,
+                tool_memory, memory_in_prompt, tool_search_documents, notification_target, conditions, conditions_default
+         FROM agent_apps WHERE app_id = ?1",
+        params![app_id],
+        |row| {
+            Ok(AgentApp {
+                app_id: row.get(0)?,
+                model: row.get(1)?,
+                prompt: row.get(2)?,
+                tool_notification: row.get::<_, i32>(3)? != 0,
+                tool_website_scrape: row.get::<_, i32>(4)? != 0,
+                tool_run_command: row.get::<_, i32>(5)? != 0,
+                website_url: row.get(6)?,
+                website_scrape_mode: row.get(7)?,
+                command: row.get(8)?,
+                tool_memory: row.get::<_, Option<i32>>(9)?.unwrap_or(0) != 0,
+                memory_in_prompt: row.get::<_, Option<i32>>(10)?.unwrap_or(0) != 0,
+                tool_search_documents: row.get::<_, Option<i32>>(11)?.unwrap_or(0) != 0,
+                notification_target: row.get::<_, Option<String>>(12)?.unwrap_or_else(default_notification_target),
+                notification_channel_ids: Vec::new(),
+                conditions: row
+                    .get::<_, Option<String>>(13)?
+                    .and_then(|json| serde_json::from_str(&json).ok())
+                    .unwrap_or_default(),
+                conditions_default: row
+                    .get::<_, Option<String>>(14)?
+                    .map(|value| crate::ai::conditions::ConditionAction::parse(&value))
+                    .unwrap_or_default(),
+            })
+        },
+    );
+    
+    match result {
+        Ok(mut agent) => {
+            let mut stmt = conn.prepare(
+                "SELECT channel_id FROM agent_notification_channels WHERE app_id = ?1 ORDER BY channel_id"
+            )?;
+            agent.notification_channel_ids = stmt
+                .query_map(params![app_id], |row| row.get(0))?
+                .collect::<Result<Vec<i64>, _>>()?;
+            Ok(Some(agent))
+        }
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Get the prompt of every agent app, for search
+pub fn get_agent_prompts(pool: &DbPool) -> Result<HashMap<i64, String>> {
+    let conn = pool.get()?;
+    let mut stmt = conn.prepare("SELECT app_id, prompt FROM agent_apps")?;
+    let prompts = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<Result<HashMap<_, _>, _>>()?;
+    Ok(prompts)
+}
+
+/// Save agent app configuration
+pub fn save_agent_app(pool: &DbPool, agent: &AgentApp) -> Result<()> {
+    let mut conn = pool.get()?;
+    let tx = conn.transaction()?;
+    
+    tx.execute(
+        "INSERT OR REPLACE INTO agent_apps (app_id, model, prompt, tool_notification, tool_website_scrape, tool_run_command, website_url, website_scrape_mode, command, tool_memory, memory_in_prompt, tool_search_documents, notification_target, conditions, conditions_default)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
+        params![
+            agent.app_id,
+            agent.model,
+            agent.prompt,
+            if agent.tool_notification { 1 } else { 0 },
+            if agent.tool_website_scrape { 1 } else { 0 },
+            if agent.tool_run_command { 1 } else { 0 },
+            agent.website_url,
+            agent.website_scrape_mode,
+            agent.command,
+            if agent.tool_memory { 1 } else { 0 },
+            if agent.memory_in_prompt { 1 } else { 0 },
+            if agent.tool_search_documents { 1 } else { 0 },
+            agent.notification_target,
+            serde_json::to_string(&agent.conditions)?,
+            agent.conditions_default.as_str(),
+        ],
+    )?;
+
+    tx.execute("DELETE FROM agent_notification_channels WHERE app_id = ?1", params![agent.app_id])?;
+    for channel_id in &agent.notification_channel_ids {
+        tx.execute(
+            "INSERT OR IGNORE INTO agent_notification_channels (app_id, channel_id) VALUES (?1, ?2)",
+            params![agent.app_id, channel_id],
+        )?;
+    }
+
+    tx.commit()?;
+    Ok(())
+}
+
+/// Get all memory entries for an agent, ordered by key
+pub fn get_agent_memory(pool: &DbPool, app_id: i64) -> Result<Vec<AgentMemoryEntry>> {
+    let conn = pool.get()?;
+    let mut stmt = conn.prepare(
+        "SELECT app_id, key, value, updated_at FROM agent_memory WHERE app_id = ?1 ORDER BY key"
+    )?;
+
+    let entries = stmt.query_map(params![app_id], |row| {
+        Ok(AgentMemoryEntry {
+            app_id: row.get(0)?,
+            key: row.get(1)?,
+            value: row.get(2)?,
+            updated_at: row.get(3)?,
+        })
+    })?
+    .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(entries)
+}
+
+/// Get a single memory value for an agent
+pub fn get_agent_memory_value(pool: &DbPool, app_id: i64, key: &str) -> Result<Option<String>> {
+    let conn = pool.get()?;
+
+    let result = conn.query_row(
+        "SELECT value FROM agent_memory WHERE app_id = ?1 AND key = ?2",
+        params![app_id, key],
+        |row| row.get(0),
+    );
+
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Set (insert or overwrite) a memory value for an agent
+pub fn set_agent_memory_value(pool: &DbPool, app_id: i64, key: &str, value: &str) -> Result<()> {
+    let conn = pool.get()?;
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+
+    conn.execute(
+        "INSERT OR REPLACE INTO agent_memory (app_id, key, value, updated_at) VALUES (?1, ?2, ?3, ?4)",
+        params![app_id, key, value, timestamp],
+    )?;
+
+    Ok(())
+}
+
+/// Delete a single memory value for an agent
+pub fn delete_agent_memory_value(pool: &DbPool, app_id: i64, key: &str) -> Result<()> {
+    let conn = pool.get()?;
+    conn.execute(
+        "DELETE FROM agent_memory WHERE app_id = ?1 AND key = ?2",
+        params![app_id, key],
+    )?;
+    Ok(())
+}
+
+/// Clear all memory for an agent
+pub fn clear_agent_memory(pool: &DbPool, app_id: i64) -> Result<()> {
+    let conn = pool.get()?;
+    conn.execute("DELETE FROM agent_memory WHERE app_id = ?1", params![app_id])?;
+    Ok(())
+}
+
+/// Get all document sources with their file and chunk counts
+pub fn get_document_sources(pool: &DbPool) -> Result<Vec<DocumentSource>> {
+    let conn = pool.get()?;
+    let mut stmt = conn.prepare(
+        "SELECT s.id, s.path, s.enabled, s.last_indexed_at,
+                (SELECT COUNT(*) FROM document_files f WHERE f.source_id = s.id),
+                (SELECT COUNT(*) FROM document_chunks c JOIN document_files f ON c.file_id = f.id WHERE f.source_id = s.id)
+         FROM document_sources s ORDER BY s.path"
+    )?;
+
+    let sources = stmt.query_map([], |row| {
+        Ok(DocumentSource {
+            id: row.get(0)?,
+            path: row.get(1)?,
+            enabled: row.get::<_, Option<i32>>(2)?.unwrap_or(1) != 0,
+            last_indexed_at: row.get(3)?,
+            file_count: row.get(4)?,
+            chunk_count: row.get(5)?,
+        })
+    })?
+    .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(sources)
+}
+
+/// Add a directory as a document source
+pub fn add_document_source(pool: &DbPool, path: &str) -> Result<i64> {
+    let conn = pool.get()?;
+    conn.execute(
+        "INSERT INTO document_sources (path, enabled) VALUES (?1, 1)",
+        params![path],
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+/// Remove a document source and everything indexed from it
+pub fn remove_document_source(pool: &DbPool, id: i64) -> Result<()> {
+    let conn = pool.get()?;
+    conn.execute("DELETE FROM document_sources WHERE id = ?1", params![id])?;
+    Ok(())
+}
+
+/// Enable or disable a document source
+pub fn set_document_source_enabled(pool: &DbPool, id: i64, enabled: bool) -> Result<()> {
+    let conn = pool.get()?;
+    conn.execute(
+        "UPDATE document_sources SET enabled = ?1 WHERE id = ?2",
+        params![if enabled { 1 } else { 0 }, id],
+    )?;
+    Ok(())
+}
+
+/// Record when a document source was last indexed
+pub fn mark_document_source_indexed(pool: &DbPool, id: i64) -> Result<()> {
+    let conn = pool.get()?;
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+    conn.execute(
+        "UPDATE document_sources SET last_indexed_at = ?1 WHERE id = ?2",
+        params![timestamp, id],
+    )?;
+    Ok(())
+}
+
+/// Get all indexed files of a document source
+pub fn get_document_files(pool: &DbPool, source_id: i64) -> Result<Vec<DocumentFile>> {
+    let conn = pool.get()?;
+    let mut stmt = conn.prepare(
+        "SELECT id, source_id, path, modified_at, size FROM document_files WHERE source_id = ?1"
+    )?;
+
+    let files = stmt.query_map(params![source_id], |row| {
+        Ok(DocumentFile {
+            id: row.get(0)?,
+            source_id: row.get(1)?,
+            path: row.get(2)?,
+            modified_at: row.get(3)?,
+            size: row.get(4)?,
+        })
+    })?
+    .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(files)
+}
+
+/// Replace the indexed chunks of a file (inserting the file if it is new)
+pub fn save_document_file(
+    pool: &DbPool,
+    source_id: i64,
+    path: &str,
+    modified_at: i64,
+    size: i64,
+    chunks: &[(String, Vec<f32>)],
+) -> Result<()> {
+    let mut conn = pool.get()?;
+    let tx = conn.transaction()?;
+
+    // Deleting the old row cascades to its chunks
+    tx.execute("DELETE FROM document_files WHERE path = ?1", params![path])?;
+    tx.execute(
+        "INSERT INTO document_files (source_id, path, modified_at, size) VALUES (?1, ?2, ?3, ?4)",
+        params![source_id, path, modified_at, size],
+    )?;
+    let file_id = tx.last_insert_rowid();
+
+    for (index, (content, embedding)) in chunks.iter().enumerate() {
+        tx.execute(
+            "INSERT INTO document_chunks (file_id, chunk_index, content, embedding) VALUES (?1, ?2, ?3, ?4)",
+            params![file_id, index as i64, content, embedding_to_blob(embedding)],
+        )?;
+    }
+
+    tx.commit()?;
+    Ok(())
+}
+
+/// Remove an indexed file and its chunks
+pub fn delete_document_file(pool: &DbPool, id: i64) -> Result<()> {
     let conn = pool.get()?;
-    
-    let result = conn.query_row(
-        "SELECT app_id, model, prompt, tool_notification, tool_website_scrape, tool_run_command, website_url, website_scrape_mode, command
-         FROM agent_apps WHERE app_id = ?1",
-        params![app_id],
-        |row| {
-            Ok(AgentApp {
-                app_id: row.get(0)?,
-                model: row.get(1)?,
-                prompt: row.get(2)?,
-                tool_notification: row.get::<_, i32>(3)? != 0,
-                tool_website_scrape: row.get::<_, i32>(4)? != 0,
-                tool_run_command: row.get::<_, i32>(5)? != 0,
-                website_url: row.get(6)?,
-                website_scrape_mode: row.get(7)?,
-                command: row.get(8)?,
-            })
-        },
-    );
-    
-    match result {
-        Ok(agent) => Ok(Some(agent)),
-        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
-        Err(e) => Err(e.into()),
-    }
+    conn.execute("DELETE FROM document_files WHERE id = ?1", params![id])?;
+    Ok(())
 }
 
-/// Save agent app configuration
-pub fn save_agent_app(pool: &DbPool, agent: &AgentApp) -> Result<()> {
+/// Get all chunks from enabled document sources
+pub fn get_document_chunks(pool: &DbPool) -> Result<Vec<DocumentChunk>> {
     let conn = pool.get()?;
-    
-    conn.execute(
-        "INSERT OR REPLACE INTO agent_apps (app_id, model, prompt, tool_notification, tool_website_scrape, tool_run_command, website_url, website_scrape_mode, command)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
-        params![
-            agent.app_id,
-            agent.model,
-            agent.prompt,
-            if agent.tool_notification { 1 } else { 0 },
-            if agent.tool_website_scrape { 1 } else { 0 },
-            if agent.tool_run_command { 1 } else { 0 },
-            agent.website_url,
-            agent.website_scrape_mode,
-            agent.command,
-        ],
+    let mut stmt = conn.prepare(
+        "SELECT f.path, c.chunk_index, c.content, c.embedding
+         FROM document_chunks c
+         JOIN document_files f ON c.file_id = f.id
+         JOIN document_sources s ON f.source_id = s.id
+         WHERE s.enabled = 1"
     )?;
-    
-    Ok(())
+
+    let chunks = stmt.query_map([], |row| {
+        let blob: Vec<u8> = row.get(3)?;
+        Ok(DocumentChunk {
+            path: row.get(0)?,
+            chunk_index: row.get(1)?,
+            content: row.get(2)?,
+            embedding: blob_to_embedding(&blob),
+        })
+    })?
+    .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(chunks)
+}
+
+/// Encode an embedding vector as little-endian f32 bytes
+fn embedding_to_blob(embedding: &[f32]) -> Vec<u8> {
+    embedding.iter().flat_map(|v| v.to_le_bytes()).collect()
+}
+
+/// Decode an embedding vector stored by `embedding_to_blob`
+fn blob_to_embedding(blob: &[u8]) -> Vec<f32> {
+    blob.chunks_exact(4)
+        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect()
 }
 
 /// Add item to AI queue
-pub fn add_queue_item(pool: &DbPool, message: &str, agent_name: Option<&str>) -> Result<i64> {
+pub fn add_queue_item(pool: &DbPool, message: &str, agent_name: Option<&str>, app_id: Option<i64>) -> Result<i64> {
+    // Transcripts are stored with secret values replaced by their references
+    let message = crate::secrets::redact(pool, message);
     let conn = pool.get()?;
     let timestamp = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
@@ -876,8 +2156,8 @@ pub fn add_queue_item(pool: &DbPool, message: &str, agent_name: Option<&str>) ->
         .as_secs() as i64;
 
     conn.execute(
-        "INSERT INTO ai_queue (status, message, created_at, agent_name) VALUES ('pending', ?1, ?2, ?3)",
-        params![message, timestamp, agent_name],
+        "INSERT INTO ai_queue (status, message, created_at, agent_name, app_id) VALUES ('pending', ?1, ?2, ?3, ?4)",
+        params![message, timestamp, agent_name, app_id],
     )?;
 
     Ok(conn.last_insert_rowid())
@@ -892,6 +2172,7 @@ pub fn update_queue_item_status(pool: &DbPool, id: i64, status: &str, response:
         .as_secs() as i64;
     
     if let Some(resp) = response {
+        let resp = crate::secrets::redact(pool, resp);
         conn.execute(
             "UPDATE ai_queue SET status = ?1, response = ?2, completed_at = ?3 WHERE id = ?4",
             params![status, resp, timestamp, id],
@@ -906,11 +2187,21 @@ pub fn update_queue_item_status(pool: &DbPool, id: i64, status: &str, response:
     Ok(())
 }
 
+/// Record the outcome of an agent's conditions on a queue item
+pub fn set_queue_item_decision(pool: &DbPool, id: i64, decision: &str) -> Result<()> {
+    let conn = pool.get()?;
+    conn.execute(
+        "UPDATE ai_queue SET decision = ?1 WHERE id = ?2",
+        params![decision, id],
+    )?;
+    Ok(())
+}
+
 /// Get AI queue items
 pub fn get_queue_items(pool: &DbPool) -> Result<Vec<AIQueueItem>> {
     let conn = pool.get()?;
     let mut stmt = conn.prepare(
-        "SELECT id, status, message, response, created_at, completed_at, agent_name FROM ai_queue ORDER BY created_at DESC LIMIT 100"
+        "SELECT id, status, message, response, created_at, completed_at, agent_name, decision, app_id FROM ai_queue ORDER BY created_at DESC LIMIT 100"
     )?;
 
     let items = stmt.query_map([], |row| {
@@ -922,6 +2213,8 @@ pub fn get_queue_items(pool: &DbPool) -> Result<Vec<AIQueueItem>> {
             created_at: row.get(4)?,
             completed_at: row.get(5)?,
             agent_name: row.get(6)?,
+            decision: row.get(7)?,
+            app_id: row.get(8)?,
         })
     })?
     .collect::<Result<Vec<_>, _>>()?;
@@ -934,7 +2227,7 @@ pub fn get_queue_item(pool: &DbPool, id: i64) -> Result<Option<AIQueueItem>> {
     let conn = pool.get()?;
 
     let result = conn.query_row(
-        "SELECT id, status, message, response, created_at, completed_at, agent_name FROM ai_queue WHERE id = ?1",
+        "SELECT id, status, message, response, created_at, completed_at, agent_name, decision, app_id FROM ai_queue WHERE id = ?1",
         params![id],
         |row| {
             Ok(AIQueueItem {
@@ -945,6 +2238,8 @@ pub fn get_queue_item(pool: &DbPool, id: i64) -> Result<Option<AIQueueItem>> {
                 created_at: row.get(4)?,
                 completed_at: row.get(5)?,
                 agent_name: row.get(6)?,
+                decision: row.get(7)?,
+                app_id: row.get(8)?,
             })
         },
     );
@@ -956,18 +2251,18 @@ pub fn get_queue_item(pool: &DbPool, id: i64) -> Result<Option<AIQueueItem>> {
     }
 }
 
-/// Clear finished queue items (completed and failed)
+/// Clear finished queue items (completed, failed and skipped)
 pub fn clear_finished_queue_items(pool: &DbPool) -> Result<()> {
     let conn = pool.get()?;
     conn.execute(
-        "DELETE FROM ai_queue WHERE status IN ('completed', 'failed')",
+        "DELETE FROM ai_queue WHERE status IN ('completed', 'failed', 'skipped')",
         [],
     )?;
     Ok(())
 }
 
 /// Create notification
-pub fn create_notification(pool: &DbPool, text: &str) -> Result<i64> {
+pub fn create_notification(pool: &DbPool, notification: &NewNotification) -> Result<i64> {
     let conn = pool.get()?;
     let timestamp = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
@@ -975,35 +2270,162 @@ pub fn create_notification(pool: &DbPool, text: &str) -> Result<i64> {
         .as_secs() as i64;
     
     conn.execute(
-        "INSERT INTO notifications (text, created_at, dismissed) VALUES (?1, ?2, 0)",
-        params![text, timestamp],
+        "INSERT INTO notifications (text, created_at, dismissed, app_id, queue_id, severity, title, url)
+         VALUES (?1, ?2, 0, ?3, ?4, ?5, ?6, ?7)",
+        params![
+            notification.text,
+            timestamp,
+            notification.app_id,
+            notification.queue_id,
+            notification.severity,
+            notification.title,
+            notification.url,
+        ],
     )?;
     
     Ok(conn.last_insert_rowid())
 }
 
-/// Get notifications
-pub fn get_notifications(pool: &DbPool, include_dismissed: bool) -> Result<Vec<Notification>> {
+const NOTIFICATION_COLUMNS: &str =
+    "n.id, n.text, n.created_at, n.dismissed, n.app_id, n.queue_id, n.severity, n.title, n.url, n.snoozed_until, a.name";
+
+fn row_to_notification(row: &rusqlite::Row) -> rusqlite::Result<Notification> {
+    Ok(Notification {
+        id: row.get(0)?,
+        text: row.get(1)?,
+        created_at: row.get(2)?,
+        dismissed: row.get::<_, i32>(3)? != 0,
+        app_id: row.get(4)?,
+        queue_id: row.get(5)?,
+        severity: row.get::<_, Option<String>>(6)?.unwrap_or_else(|| "normal".to_string()),
+        title: row.get(7)?,
+        url: row.get(8)?,
+        snoozed_until: row.get(9)?,
+        source_name: row.get(10)?,
+    })
+}
+
+/// Get notifications matching a filter, newest first
+pub fn get_notifications(pool: &DbPool, filter: &NotificationFilter) -> Result<Vec<Notification>> {
     let conn = pool.get()?;
-    let query = if include_dismissed {
-        "SELECT id, text, created_at, dismissed FROM notifications ORDER BY created_at DESC"
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+
+    let mut conditions = Vec::new();
+    let mut values: Vec<rusqlite::types::Value> = Vec::new();
+    if !filter.include_dismissed {
+        conditions.push("n.dismissed = 0".to_string());
+    }
+    if !filter.include_snoozed {
+        values.push(now.into());
+        conditions.push(format!("(n.snoozed_until IS NULL OR n.snoozed_until <= ?{})", values.len()));
+    }
+    if let Some(app_id) = filter.app_id {
+        values.push(app_id.into());
+        conditions.push(format!("n.app_id = ?{}", values.len()));
+    }
+    if let Some(severity) = &filter.severity {
+        values.push(severity.clone().into());
+        conditions.push(format!("n.severity = ?{}", values.len()));
+    }
+
+    let where_clause = if conditions.is_empty() {
+        String::new()
     } else {
-        "SELECT id, text, created_at, dismissed FROM notifications WHERE dismissed = 0 ORDER BY created_at DESC"
+        format!("WHERE {}", conditions.join(" AND "))
     };
+    let query = format!(
+        "SELECT {} FROM notifications n LEFT JOIN apps a ON a.id = n.app_id {} ORDER BY n.created_at DESC, n.id DESC",
+        NOTIFICATION_COLUMNS, where_clause
+    );
     
-    let mut stmt = conn.prepare(query)?;
+    let mut stmt = conn.prepare(&query)?;
+    let notifications = stmt.query_map(rusqlite::params_from_iter(values), row_to_notification)?
+        .collect::<Result<Vec<_>, _>>()?;
     
-    let notifications = stmt.query_map([], |row| {
-        Ok(Notification {
-            id: row.get(0)?,
-            text: row.get(1)?,
-            created_at: row.get(2)?,
-            dismissed: row.get::<_, i32>(3)? != 0,
+    Ok(notifications)
+}
+
+/// Get a single notification
+pub fn get_notification(pool: &DbPool, id: i64) -> Result<Option<Notification>> {
+    let conn = pool.get()?;
+    let query = format!(
+        "SELECT {} FROM notifications n LEFT JOIN apps a ON a.id = n.app_id WHERE n.id = ?1",
+        NOTIFICATION_COLUMNS
+    );
+
+    match conn.query_row(&query, params![id], row_to_notification) {
+        Ok(notification) => Ok(Some(notification)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Get the sources of active notifications, for grouping and filtering
+pub fn get_notification_sources(pool: &DbPool) -> Result<Vec<NotificationSource>> {
+    let conn = pool.get()?;
+    let mut stmt = conn.prepare(
+        "SELECT n.app_id, a.name, COUNT(*) FROM notifications n
+         LEFT JOIN apps a ON a.id = n.app_id
+         WHERE n.dismissed = 0
+         GROUP BY n.app_id
+         ORDER BY a.name"
+    )?;
+
+    let sources = stmt.query_map([], |row| {
+        Ok(NotificationSource {
+            app_id: row.get(0)?,
+            name: row.get::<_, Option<String>>(1)?.unwrap_or_else(|| "Other".to_string()),
+            count: row.get(2)?,
         })
     })?
     .collect::<Result<Vec<_>, _>>()?;
-    
-    Ok(notifications)
+
+    Ok(sources)
+}
+
+/// Hide a notification until the given time
+pub fn snooze_notification(pool: &DbPool, id: i64, until: i64) -> Result<()> {
+    let conn = pool.get()?;
+    conn.execute(
+        "UPDATE notifications SET snoozed_until = ?1 WHERE id = ?2",
+        params![until, id],
+    )?;
+    Ok(())
+}
+
+/// Clear the snooze on notifications whose snooze has expired and return them
+pub fn take_due_snoozed_notifications(pool: &DbPool, now: i64) -> Result<Vec<Notification>> {
+    let conn = pool.get()?;
+    let query = format!(
+        "SELECT {} FROM notifications n LEFT JOIN apps a ON a.id = n.app_id
+         WHERE n.dismissed = 0 AND n.snoozed_until IS NOT NULL AND n.snoozed_until <= ?1",
+        NOTIFICATION_COLUMNS
+    );
+    let mut stmt = conn.prepare(&query)?;
+    let due = stmt.query_map(params![now], row_to_notification)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    for notification in &due {
+        conn.execute(
+            "UPDATE notifications SET snoozed_until = NULL WHERE id = ?1",
+            params![notification.id],
+        )?;
+    }
+
+    Ok(due)
+}
+
+/// Delete dismissed notifications created before the cutoff, returning how many were removed
+pub fn delete_dismissed_notifications_before(pool: &DbPool, cutoff: i64) -> Result<usize> {
+    let conn = pool.get()?;
+    let deleted = conn.execute(
+        "DELETE FROM notifications WHERE dismissed = 1 AND created_at < ?1",
+        params![cutoff],
+    )?;
+    Ok(deleted)
 }
 
 /// Dismiss notification
@@ -1023,10 +2445,230 @@ pub fn dismiss_all_notifications(pool: &DbPool) -> Result<()> {
     Ok(())
 }
 
+/// Get all outbound notification channels
+pub fn get_notification_channels(pool: &DbPool) -> Result<Vec<NotificationChannel>> {
+    let conn = pool.get()?;
+    let mut stmt = conn.prepare(
+        "SELECT id, name, kind, config, enabled FROM notification_channels ORDER BY name"
+    )?;
+
+    let channels = stmt.query_map([], row_to_notification_channel)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(channels)
+}
+
+/// Get a single outbound notification channel
+pub fn get_notification_channel(pool: &DbPool, id: i64) -> Result<Option<NotificationChannel>> {
+    let conn = pool.get()?;
+
+    let result = conn.query_row(
+        "SELECT id, name, kind, config, enabled FROM notification_channels WHERE id = ?1",
+        params![id],
+        row_to_notification_channel,
+    );
+
+    match result {
+        Ok(channel) => Ok(Some(channel)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn row_to_notification_channel(row: &rusqlite::Row) -> rusqlite::Result<NotificationChannel> {
+    let config: String = row.get(3)?;
+    Ok(NotificationChannel {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        kind: row.get(2)?,
+        config: serde_json::from_str(&config).unwrap_or_else(|_| serde_json::json!({})),
+        enabled: row.get::<_, i32>(4)? != 0,
+    })
+}
+
+/// Create (id 0) or update an outbound notification channel, returning its id
+pub fn save_notification_channel(pool: &DbPool, channel: &NotificationChannel) -> Result<i64> {
+    let conn = pool.get()?;
+    let config = serde_json::to_string(&channel.config)?;
+
+    if channel.id == 0 {
+        conn.execute(
+            "INSERT INTO notification_channels (name, kind, config, enabled) VALUES (?1, ?2, ?3, ?4)",
+            params![channel.name, channel.kind, config, if channel.enabled { 1 } else { 0 }],
+        )?;
+        Ok(conn.last_insert_rowid())
+    } else {
+        conn.execute(
+            "UPDATE notification_channels SET name = ?1, kind = ?2, config = ?3, enabled = ?4 WHERE id = ?5",
+            params![channel.name, channel.kind, config, if channel.enabled { 1 } else { 0 }, channel.id],
+        )?;
+        Ok(channel.id)
+    }
+}
+
+/// Delete an outbound notification channel
+pub fn delete_notification_channel(pool: &DbPool, id: i64) -> Result<()> {
+    let conn = pool.get()?;
+    conn.execute("DELETE FROM notification_channels WHERE id = ?1", params![id])?;
+    Ok(())
+}
+
+/// Record an attempt to deliver a notification to a channel (`error` is None on success)
+pub fn log_notification_delivery(
+    pool: &DbPool,
+    notification_id: i64,
+    channel: &NotificationChannel,
+    attempt: i64,
+    error: Option<&str>,
+) -> Result<()> {
+    let conn = pool.get()?;
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+
+    conn.execute(
+        "INSERT INTO notification_deliveries (notification_id, channel_id, channel_name, attempt, success, error, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        params![
+            notification_id,
+            channel.id,
+            channel.name,
+            attempt,
+            if error.is_none() { 1 } else { 0 },
+            error,
+            timestamp,
+        ],
+    )?;
+
+    Ok(())
+}
+
+/// Get the delivery log for a notification, oldest attempt first
+pub fn get_notification_deliveries(pool: &DbPool, notification_id: i64) -> Result<Vec<NotificationDelivery>> {
+    let conn = pool.get()?;
+    let mut stmt = conn.prepare(
+        "SELECT id, notification_id, channel_id, channel_name, attempt, success, error, created_at
+         FROM notification_deliveries WHERE notification_id = ?1 ORDER BY id"
+    )?;
+
+    let deliveries = stmt.query_map(params![notification_id], |row| {
+        Ok(NotificationDelivery {
+            id: row.get(0)?,
+            notification_id: row.get(1)?,
+            channel_id: row.get(2)?,
+            channel_name: row.get(3)?,
+            attempt: row.get(4)?,
+            success: row.get::<_, i32>(5)? != 0,
+            error: row.get(6)?,
+            created_at: row.get(7)?,
+        })
+    })?
+    .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(deliveries)
+}
+
+/// Insert or replace an encrypted secret
+pub fn save_secret(pool: &DbPool, name: &str, nonce: &[u8], ciphertext: &[u8]) -> Result<()> {
+    let conn = pool.get()?;
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+
+    conn.execute(
+        "INSERT OR REPLACE INTO secrets (name, nonce, ciphertext, updated_at) VALUES (?1, ?2, ?3, ?4)",
+        params![name, nonce, ciphertext, timestamp],
+    )?;
+    Ok(())
+}
+
+/// Get all encrypted secrets, ordered by name
+pub fn get_secrets(pool: &DbPool) -> Result<Vec<EncryptedSecret>> {
+    let conn = pool.get()?;
+    let mut stmt = conn.prepare("SELECT name, nonce, ciphertext, updated_at FROM secrets ORDER BY name")?;
+
+    let secrets = stmt.query_map([], |row| {
+        Ok(EncryptedSecret {
+            name: row.get(0)?,
+            nonce: row.get(1)?,
+            ciphertext: row.get(2)?,
+            updated_at: row.get(3)?,
+        })
+    })?
+    .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(secrets)
+}
+
+/// Get a single encrypted secret by name
+pub fn get_secret(pool: &DbPool, name: &str) -> Result<Option<EncryptedSecret>> {
+    let conn = pool.get()?;
+
+    let result = conn.query_row(
+        "SELECT name, nonce, ciphertext, updated_at FROM secrets WHERE name = ?1",
+        params![name],
+        |row| {
+            Ok(EncryptedSecret {
+                name: row.get(0)?,
+                nonce: row.get(1)?,
+                ciphertext: row.get(2)?,
+                updated_at: row.get(3)?,
+            })
+        },
+    );
+
+    match result {
+        Ok(secret) => Ok(Some(secret)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Delete a secret
+pub fn delete_secret(pool: &DbPool, name: &str) -> Result<()> {
+    let conn = pool.get()?;
+    conn.execute("DELETE FROM secrets WHERE name = ?1", params![name])?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// Insert an app of the given type and return its id
+    fn insert_test_app(pool: &DbPool, app_type: AppType, name: &str) -> i64 {
+        create_app(
+            pool,
+            NewApp {
+                app_type,
+                name: name.to_string(),
+                icon_path: None,
+                shortcut: None,
+                global_shortcut: None,
+                binary_path: Some("/bin/true".to_string()),
+                cli_params: None,
+                url: Some("https://example.com".to_string()),
+                show_nav_controls: None,
+                open_external_links: None,
+                enable_oauth: None,
+                auto_close_timeout: None,
+                always_on_top: None,
+                hide_on_shortcut: None,
+                single_instance: None,
+                working_dir: None,
+                env: Vec::new(),
+                env_file: None,
+                pre_launch_hook: None,
+                post_exit_hook: None,
+                hook_timeout: None,
+            },
+            None,
+        )
+        .unwrap()
+    }
+
     #[test]
     fn test_window_state_serialization() {
         let state = WindowState {
@@ -1058,5 +2700,396 @@ mod tests {
         assert_eq!(state1.width, state2.width);
         assert_eq!(state1.height, state2.height);
     }
-}
 
+    #[test]
+    fn test_agent_memory_roundtrip() {
+        let pool = test_pool();
+        let first = insert_test_app(&pool, AppType::Agent, "Price watcher");
+        let second = insert_test_app(&pool, AppType::Agent, "Stock watcher");
+
+        set_agent_memory_value(&pool, first, "last_price", "19.99").unwrap();
+        set_agent_memory_value(&pool, first, "last_price", "17.49").unwrap();
+        set_agent_memory_value(&pool, first, "currency", "EUR").unwrap();
+        set_agent_memory_value(&pool, second, "last_price", "5").unwrap();
+
+        assert_eq!(get_agent_memory_value(&pool, first, "last_price").unwrap().as_deref(), Some("17.49"));
+        assert_eq!(get_agent_memory_value(&pool, first, "missing").unwrap(), None);
+
+        let keys: Vec<String> = get_agent_memory(&pool, first).unwrap().into_iter().map(|e| e.key).collect();
+        assert_eq!(keys, vec!["currency", "last_price"]);
+
+        clear_agent_memory(&pool, first).unwrap();
+        assert!(get_agent_memory(&pool, first).unwrap().is_empty());
+
+        // Deleting the agent removes its memory too
+        delete_app(&pool, second).unwrap();
+        assert!(get_agent_memory(&pool, second).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_agent_notification_channels() {
+        let pool = test_pool();
+        let app_id = insert_test_app(&pool, AppType::Agent, "Price watcher");
+
+        let mut channel = NotificationChannel {
+            id: 0,
+            name: "Phone".to_string(),
+            kind: "ntfy".to_string(),
+            config: serde_json::json!({ "server_url": "https://ntfy.sh", "topic": "alerts" }),
+            enabled: true,
+        };
+        channel.id = save_notification_channel(&pool, &channel).unwrap();
+
+        let agent = AgentApp {
+            app_id,
+            model: None,
+            prompt: "Watch the price".to_string(),
+            tool_notification: true,
+            tool_website_scrape: false,
+            tool_run_command: false,
+            website_url: None,
+            website_scrape_mode: None,
+            command: None,
+            tool_memory: false,
+            memory_in_prompt: false,
+            tool_search_documents: false,
+            notification_target: "both".to_string(),
+            notification_channel_ids: vec![channel.id],
+            conditions: Vec::new(),
+            conditions_default: Default::default(),
+        };
+        save_agent_app(&pool, &agent).unwrap();
+        // Saving again must not duplicate the channel links
+        save_agent_app(&pool, &agent).unwrap();
+
+        let loaded = get_agent_app(&pool, app_id).unwrap().unwrap();
+        assert_eq!(loaded.notification_target, "both");
+        assert_eq!(loaded.notification_channel_ids, vec![channel.id]);
+
+        // The delivery log outlives the channel it was sent through
+        let notification_id = create_notification(&pool, &test_notification(None, "Now in stock")).unwrap();
+        log_notification_delivery(&pool, notification_id, &channel, 1, Some("HTTP 500")).unwrap();
+        delete_notification_channel(&pool, channel.id).unwrap();
+
+        let deliveries = get_notification_deliveries(&pool, notification_id).unwrap();
+        assert_eq!(deliveries.len(), 1);
+        assert_eq!(deliveries[0].channel_id, None);
+        assert_eq!(deliveries[0].channel_name, "Phone");
+        assert!(get_agent_app(&pool, app_id).unwrap().unwrap().notification_channel_ids.is_empty());
+    }
+
+    #[test]
+    fn test_agent_conditions_and_queue_decision() {
+        use crate::ai::conditions::{AgentCondition, ConditionAction, ConditionCheck, ConditionSource};
+
+        let pool = test_pool();
+        let app_id = insert_test_app(&pool, AppType::Agent, "Stock watcher");
+
+        let agent = AgentApp {
+            app_id,
+            model: None,
+            prompt: "Tell me when it is back".to_string(),
+            tool_notification: true,
+            tool_website_scrape: true,
+            tool_run_command: false,
+            website_url: Some("https://example.com".to_string()),
+            website_scrape_mode: None,
+            command: None,
+            tool_memory: false,
+            memory_in_prompt: false,
+            tool_search_documents: false,
+            notification_target: "os".to_string(),
+            notification_channel_ids: Vec::new(),
+            conditions: vec![AgentCondition {
+                source: ConditionSource::Website,
+                check: ConditionCheck::Matches { pattern: "In stock".to_string() },
+                action: ConditionAction::Notify,
+                message: Some("Back in stock".to_string()),
+            }],
+            conditions_default: ConditionAction::Skip,
+        };
+        save_agent_app(&pool, &agent).unwrap();
+
+        let loaded = get_agent_app(&pool, app_id).unwrap().unwrap();
+        assert_eq!(loaded.conditions.len(), 1);
+        assert_eq!(loaded.conditions[0].action, ConditionAction::Notify);
+        assert_eq!(loaded.conditions_default, ConditionAction::Skip);
+
+        let queue_id = add_queue_item(&pool, "[]", Some("Stock watcher"), Some(app_id)).unwrap();
+        set_queue_item_decision(&pool, queue_id, "No condition held: skipped the run").unwrap();
+        update_queue_item_status(&pool, queue_id, "skipped", Some("No condition held: skipped the run")).unwrap();
+
+        let item = get_queue_item(&pool, queue_id).unwrap().unwrap();
+        assert_eq!(item.status, "skipped");
+        assert_eq!(item.decision.as_deref(), Some("No condition held: skipped the run"));
+        assert_eq!(item.app_id, Some(app_id));
+
+        clear_finished_queue_items(&pool).unwrap();
+        assert!(get_queue_item(&pool, queue_id).unwrap().is_none());
+    }
+
+    fn test_notification(app_id: Option<i64>, text: &str) -> NewNotification {
+        NewNotification {
+            text: text.to_string(),
+            title: None,
+            url: None,
+            severity: "normal".to_string(),
+            app_id,
+            queue_id: None,
+        }
+    }
+
+    #[test]
+    fn test_notification_filters_snooze_and_retention() {
+        let pool = test_pool();
+        let agent = insert_test_app(&pool, AppType::Agent, "Price watcher");
+
+        let from_agent = create_notification(&pool, &NewNotification {
+            title: Some("Price drop".to_string()),
+            url: Some("https://shop.example.com".to_string()),
+            severity: "critical".to_string(),
+            queue_id: Some(9),
+            ..test_notification(Some(agent), "Now 17.49")
+        }).unwrap();
+        let other = create_notification(&pool, &test_notification(None, "Backup finished")).unwrap();
+
+        let all = get_notifications(&pool, &NotificationFilter::default()).unwrap();
+        assert_eq!(all.len(), 2);
+        let stored = all.iter().find(|n| n.id == from_agent).unwrap();
+        assert_eq!(stored.source_name.as_deref(), Some("Price watcher"));
+        assert_eq!(stored.severity, "critical");
+        assert_eq!(stored.queue_id, Some(9));
+
+        let by_source = get_notifications(&pool, &NotificationFilter { app_id: Some(agent), ..Default::default() }).unwrap();
+        assert_eq!(by_source.len(), 1);
+        let critical = get_notifications(&pool, &NotificationFilter { severity: Some("critical".to_string()), ..Default::default() }).unwrap();
+        assert_eq!(critical.len(), 1);
+
+        let sources = get_notification_sources(&pool).unwrap();
+        assert_eq!(sources.len(), 2);
+
+        // Snoozed notifications are hidden until they are due again
+        snooze_notification(&pool, other, 1_000).unwrap();
+        assert!(take_due_snoozed_notifications(&pool, 500).unwrap().is_empty());
+        let due = take_due_snoozed_notifications(&pool, 1_000).unwrap();
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].id, other);
+        assert!(take_due_snoozed_notifications(&pool, 1_000).unwrap().is_empty());
+
+        snooze_notification(&pool, other, i64::MAX).unwrap();
+        assert_eq!(get_notifications(&pool, &NotificationFilter::default()).unwrap().len(), 1);
+        let with_snoozed = NotificationFilter { include_snoozed: true, ..Default::default() };
+        assert_eq!(get_notifications(&pool, &with_snoozed).unwrap().len(), 2);
+
+        // Retention only removes dismissed notifications
+        dismiss_notification(&pool, from_agent).unwrap();
+        assert_eq!(delete_dismissed_notifications_before(&pool, i64::MAX).unwrap(), 1);
+        assert!(get_notification(&pool, from_agent).unwrap().is_none());
+        assert!(get_notification(&pool, other).unwrap().is_some());
+
+        // Deleting the source app keeps its notifications
+        let kept = create_notification(&pool, &test_notification(Some(agent), "Still here")).unwrap();
+        delete_app(&pool, agent).unwrap();
+        let orphan = get_notification(&pool, kept).unwrap().unwrap();
+        assert_eq!(orphan.app_id, None);
+    }
+
+    #[test]
+    fn test_document_chunks_roundtrip() {
+        let pool = test_pool();
+        let source_id = add_document_source(&pool, "/notes").unwrap();
+
+        let chunks = vec![
+            ("first chunk".to_string(), vec![0.5, -1.25, 3.0]),
+            ("second chunk".to_string(), vec![0.0, 1.0, 0.0]),
+        ];
+        save_document_file(&pool, source_id, "/notes/a.md", 100, 42, &chunks).unwrap();
+        // Re-saving a file replaces its chunks instead of duplicating them
+        save_document_file(&pool, source_id, "/notes/a.md", 200, 42, &chunks).unwrap();
+
+        let stored = get_document_chunks(&pool).unwrap();
+        assert_eq!(stored.len(), 2);
+        assert_eq!(stored[0].embedding, vec![0.5, -1.25, 3.0]);
+
+        let sources = get_document_sources(&pool).unwrap();
+        assert_eq!(sources[0].file_count, 1);
+        assert_eq!(sources[0].chunk_count, 2);
+
+        set_document_source_enabled(&pool, source_id, false).unwrap();
+        assert!(get_document_chunks(&pool).unwrap().is_empty());
+
+        remove_document_source(&pool, source_id).unwrap();
+        assert!(get_document_files(&pool, source_id).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_launch_history_stats_and_retention() {
+        let pool = test_pool();
+        let editor = insert_test_app(&pool, AppType::App, "Editor");
+        let mail = insert_test_app(&pool, AppType::Webapp, "Mail");
+        let day = 24 * 60 * 60;
+        let now = 100 * day;
+
+        record_launch(&pool, editor, "click", now - 2 * day, None).unwrap();
+        record_launch(&pool, editor, "app_shortcut", now, None).unwrap();
+        record_launch(&pool, mail, "global_shortcut", now - day, None).unwrap();
+        record_launch(&pool, mail, "click", now, Some("No URL specified for webapp")).unwrap();
+
+        // Failed launches are kept in the history but not counted as usage
+        let stats = get_launch_stats(&pool, 0).unwrap();
+        assert_eq!(stats.len(), 2);
+        assert_eq!((stats[0].app_id, stats[0].launch_count, stats[0].last_launched_at), (editor, 2, now));
+        assert_eq!((stats[1].app_id, stats[1].launch_count), (mail, 1));
+        assert_eq!(get_launch_stats(&pool, now - day / 2).unwrap().len(), 1);
+
+        let history = get_launch_history(&pool, Some(mail), 10).unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].error.as_deref(), Some("No URL specified for webapp"));
+        assert_eq!(history[1].trigger, "global_shortcut");
+
+        let histogram = get_launch_histogram(&pool, None, 0).unwrap();
+        assert_eq!(histogram.iter().map(|d| d.count).sum::<i64>(), 3);
+        assert_eq!(get_launch_histogram(&pool, Some(editor), 0).unwrap().len(), 2);
+
+        // A crash right after starting marks the latest launch as failed
+        set_last_launch_error(&pool, editor, "Exited with code 1 after 0.3s").unwrap();
+        let errors = get_launch_errors(&pool, editor, 10).unwrap();
+        assert_eq!(errors.len(), 1);
+        assert_eq!((errors[0].launched_at, errors[0].trigger.as_str()), (now, "app_shortcut"));
+        assert_eq!(get_launch_errors(&pool, mail, 10).unwrap()[0].error.as_deref(), Some("No URL specified for webapp"));
+        assert_eq!(get_launch_stats(&pool, 0).unwrap()[0].launch_count, 1);
+
+        assert_eq!(delete_launch_history_before(&pool, now - day).unwrap(), 1);
+        delete_app(&pool, mail).unwrap();
+        assert_eq!(get_launch_history(&pool, None, 10).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_workspace_items() {
+        let pool = test_pool();
+        let morning = insert_test_app(&pool, AppType::Workspace, "Morning");
+        let editor = insert_test_app(&pool, AppType::App, "Editor");
+        let mail = insert_test_app(&pool, AppType::Webapp, "Mail");
+        let chat = insert_test_app(&pool, AppType::Webapp, "Chat");
+
+        let placed = WindowState { x: 10, y: 20, width: 800, height: 600 };
+        let items = vec![
+            WorkspaceItem { app_id: mail, delay_ms: 0, geometry: Some(placed.clone()) },
+            WorkspaceItem { app_id: editor, delay_ms: 1500, geometry: None },
+            WorkspaceItem { app_id: chat, delay_ms: 0, geometry: None },
+        ];
+        set_workspace_items(&pool, morning, &items).unwrap();
+        assert_eq!(get_workspace_items(&pool, morning).unwrap(), items);
+        assert_eq!(get_all_apps(&pool).unwrap()[0].app_type, AppType::Workspace);
+
+        // Saving the layout only touches the app's own entry
+        let moved = WindowState { x: 0, y: 0, width: 1024, height: 768 };
+        save_workspace_geometry(&pool, morning, editor, &moved).unwrap();
+        let saved = get_workspace_items(&pool, morning).unwrap();
+        assert_eq!(saved[1].geometry, Some(moved));
+        assert_eq!(saved[0].geometry, Some(placed));
+
+        // Deleted apps leave the workspace, and a deleted workspace takes its items along
+        delete_app(&pool, chat).unwrap();
+        let ids: Vec<_> = get_workspace_items(&pool, morning).unwrap().iter().map(|i| i.app_id).collect();
+        assert_eq!(ids, vec![mail, editor]);
+        delete_app(&pool, morning).unwrap();
+        assert!(get_workspace_items(&pool, morning).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_pages_and_folders() {
+        let pool = test_pool();
+        let editor = insert_test_app(&pool, AppType::App, "Editor");
+        let mail = insert_test_app(&pool, AppType::Webapp, "Mail");
+        let notes = insert_test_app(&pool, AppType::App, "Notes");
+        let first = get_pages(&pool).unwrap()[0].id;
+
+        // Top level of a page: apps and folders share one ordering
+        let dev = create_group(&pool, "Dev", first, None).unwrap();
+        let tools = create_group(&pool, "Tools", first, Some(dev)).unwrap();
+        move_app(&pool, editor, first, Some(dev), 0).unwrap();
+        move_app(&pool, notes, first, None, 0).unwrap();
+        let top = || container_items(&pool.get().unwrap(), first, None).unwrap();
+        assert_eq!(top(), vec![GridItem::App(notes), GridItem::App(mail), GridItem::Group(dev)]);
+        assert_eq!(
+            container_items(&pool.get().unwrap(), first, Some(dev)).unwrap(),
+            vec![GridItem::App(editor), GridItem::Group(tools)]
+        );
+
+        // Folders can't be moved into themselves or onto a page they aren't on
+        assert!(move_group(&pool, dev, first, Some(tools), 0).is_err());
+        let work = create_page(&pool, "Work").unwrap();
+        assert!(move_app(&pool, mail, work, Some(dev), 0).is_err());
+
+        // Moving a folder to another page takes its contents along
+        move_group(&pool, dev, work, None, 0).unwrap();
+        let apps = get_all_apps(&pool).unwrap();
+        let editor_app = apps.iter().find(|a| a.id == editor).unwrap();
+        assert_eq!((editor_app.page_id, editor_app.group_id), (work, Some(dev)));
+        assert!(get_groups(&pool).unwrap().iter().all(|g| g.page_id == work));
+        assert_eq!(top(), vec![GridItem::App(notes), GridItem::App(mail)]);
+
+        // Deleting a folder puts its contents where it was
+        delete_group(&pool, dev).unwrap();
+        assert_eq!(
+            container_items(&pool.get().unwrap(), work, None).unwrap(),
+            vec![GridItem::App(editor), GridItem::Group(tools)]
+        );
+
+        // Deleting a page moves everything to the first remaining one; the last page stays
+        delete_page(&pool, work).unwrap();
+        assert_eq!(
+            top(),
+            vec![GridItem::App(notes), GridItem::App(mail), GridItem::App(editor), GridItem::Group(tools)]
+        );
+        assert!(delete_page(&pool, first).is_err());
+
+        // New apps go to the end of the first page
+        let later = insert_test_app(&pool, AppType::App, "Later");
+        assert_eq!(top().last(), Some(&GridItem::App(later)));
+    }
+
+    #[test]
+    fn test_tags_rename_merge_and_cleanup() {
+        let pool = test_pool();
+        let mail = insert_test_app(&pool, AppType::Webapp, "Mail");
+        let chat = insert_test_app(&pool, AppType::Webapp, "Chat");
+
+        set_app_tags(&pool, mail, &["Work".to_string(), " email ".to_string(), "work".to_string()]).unwrap();
+        add_app_tag(&pool, chat, "office").unwrap();
+        assert!(add_app_tag(&pool, chat, "a,b").is_err());
+        let tags_of = |id: i64| get_all_apps(&pool).unwrap().into_iter().find(|a| a.id == id).unwrap().tags;
+        assert_eq!(tags_of(mail), vec!["email", "Work"]);
+
+        let filter_id = save_saved_filter(
+            &pool,
+            &SavedFilter {
+                id: 0,
+                name: "Office".to_string(),
+                rule: crate::filters::FilterRule {
+                    tags: vec!["office".to_string()],
+                    ..Default::default()
+                },
+                pinned: true,
+                position: 0,
+            },
+        )
+        .unwrap();
+
+        // Renaming onto an existing tag merges them, and saved filters follow
+        rename_tag(&pool, "office", "work").unwrap();
+        assert_eq!(tags_of(chat), vec!["Work"]);
+        let counts: Vec<(String, i64)> = get_tags(&pool).unwrap().into_iter().map(|t| (t.name, t.app_count)).collect();
+        assert_eq!(counts, vec![("email".to_string(), 1), ("Work".to_string(), 2)]);
+        let filter = get_saved_filters(&pool).unwrap().into_iter().find(|f| f.id == filter_id).unwrap();
+        assert_eq!(filter.rule.tags, vec!["work"]);
+
+        // Tags nobody uses any more disappear
+        remove_app_tag(&pool, mail, "EMAIL").unwrap();
+        delete_app(&pool, chat).unwrap();
+        assert_eq!(get_tags(&pool).unwrap().len(), 1);
+        delete_tag(&pool, "work").unwrap();
+        assert!(tags_of(mail).is_empty());
+    }
+}
@@ -0,0 +1,135 @@
+-- launcher.db as written before versioned migrations (PRAGMA user_version = 0), with sample rows.
+-- Used by the upgrade test in database/migrations.rs; do not change it when the schema changes.
+CREATE TABLE apps (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            app_type TEXT NOT NULL,
+            name TEXT NOT NULL,
+            icon_path TEXT,
+            position INTEGER NOT NULL,
+            shortcut TEXT,
+            global_shortcut TEXT
+        );
+INSERT INTO apps VALUES(1,'app','Editor',NULL,0,'e','CommandOrControl+Alt+E');
+INSERT INTO apps VALUES(2,'webapp','Mail',NULL,1,'m',NULL);
+INSERT INTO apps VALUES(3,'agent','Price watcher',NULL,2,NULL,NULL);
+CREATE TABLE app_details (
+            app_id INTEGER PRIMARY KEY,
+            binary_path TEXT NOT NULL,
+            cli_params TEXT, always_on_top INTEGER DEFAULT 0, hide_on_shortcut INTEGER DEFAULT 0,
+            FOREIGN KEY(app_id) REFERENCES apps(id) ON DELETE CASCADE
+        );
+INSERT INTO app_details VALUES(1,'/usr/bin/gedit','--new-window',0,0);
+CREATE TABLE webapp_details (
+            app_id INTEGER PRIMARY KEY,
+            url TEXT NOT NULL,
+            session_data_path TEXT NOT NULL,
+            window_x INTEGER,
+            window_y INTEGER,
+            window_width INTEGER,
+            window_height INTEGER, show_nav_controls INTEGER DEFAULT 0, open_external_links INTEGER DEFAULT 0, enable_oauth INTEGER DEFAULT 0, auto_close_timeout INTEGER, always_on_top INTEGER DEFAULT 0, hide_on_shortcut INTEGER DEFAULT 0,
+            FOREIGN KEY(app_id) REFERENCES apps(id) ON DELETE CASCADE
+        );
+INSERT INTO webapp_details VALUES(2,'https://mail.example.com','/tmp/sessions/mail',NULL,NULL,1024,768,1,0,0,NULL,0,0);
+CREATE TABLE settings (
+            key TEXT PRIMARY KEY,
+            value TEXT NOT NULL
+        );
+INSERT INTO settings VALUES('global_shortcut','CommandOrControl+Shift+Space');
+INSERT INTO settings VALUES('theme','dark');
+INSERT INTO settings VALUES('grid_cols','5');
+CREATE TABLE ai_models (
+            id TEXT PRIMARY KEY,
+            created INTEGER
+        );
+CREATE TABLE agent_apps (
+            app_id INTEGER PRIMARY KEY,
+            model TEXT,
+            prompt TEXT NOT NULL,
+            tool_notification INTEGER DEFAULT 0,
+            tool_website_scrape INTEGER DEFAULT 0,
+            tool_run_command INTEGER DEFAULT 0,
+            website_url TEXT,
+            website_scrape_mode TEXT DEFAULT 'text',
+            command TEXT, tool_memory INTEGER DEFAULT 0, memory_in_prompt INTEGER DEFAULT 0, tool_search_documents INTEGER DEFAULT 0, notification_target TEXT DEFAULT 'os', conditions TEXT, conditions_default TEXT DEFAULT 'run',
+            FOREIGN KEY(app_id) REFERENCES apps(id) ON DELETE CASCADE
+        );
+INSERT INTO agent_apps VALUES(3,'local-model','Watch the price',1,0,0,'https://shop.example.com','text',NULL,0,0,0,'os',NULL,'run');
+CREATE TABLE agent_memory (
+            app_id INTEGER NOT NULL,
+            key TEXT NOT NULL,
+            value TEXT NOT NULL,
+            updated_at INTEGER NOT NULL,
+            PRIMARY KEY(app_id, key),
+            FOREIGN KEY(app_id) REFERENCES apps(id) ON DELETE CASCADE
+        );
+CREATE TABLE document_sources (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            path TEXT NOT NULL UNIQUE,
+            enabled INTEGER DEFAULT 1,
+            last_indexed_at INTEGER
+        );
+CREATE TABLE document_files (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            source_id INTEGER NOT NULL,
+            path TEXT NOT NULL UNIQUE,
+            modified_at INTEGER NOT NULL,
+            size INTEGER NOT NULL,
+            FOREIGN KEY(source_id) REFERENCES document_sources(id) ON DELETE CASCADE
+        );
+CREATE TABLE document_chunks (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            file_id INTEGER NOT NULL,
+            chunk_index INTEGER NOT NULL,
+            content TEXT NOT NULL,
+            embedding BLOB NOT NULL,
+            FOREIGN KEY(file_id) REFERENCES document_files(id) ON DELETE CASCADE
+        );
+CREATE TABLE ai_queue (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            status TEXT NOT NULL,
+            message TEXT NOT NULL,
+            response TEXT,
+            created_at INTEGER NOT NULL,
+            completed_at INTEGER,
+            agent_name TEXT
+        , decision TEXT, app_id INTEGER REFERENCES apps(id) ON DELETE SET NULL);
+INSERT INTO ai_queue VALUES(1,'completed','[]','Price is 42',1760000000,1760000005,'Price watcher',NULL,3);
+CREATE TABLE notifications (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            text TEXT NOT NULL,
+            created_at INTEGER NOT NULL,
+            dismissed INTEGER DEFAULT 0
+        , app_id INTEGER REFERENCES apps(id) ON DELETE SET NULL, queue_id INTEGER, severity TEXT DEFAULT 'normal', title TEXT, url TEXT, snoozed_until INTEGER);
+INSERT INTO notifications VALUES(1,'Price dropped',1760000005,0,3,NULL,'high','Price watcher',NULL,NULL);
+CREATE TABLE notification_channels (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL,
+            kind TEXT NOT NULL,
+            config TEXT NOT NULL DEFAULT '{}',
+            enabled INTEGER DEFAULT 1
+        );
+CREATE TABLE agent_notification_channels (
+            app_id INTEGER NOT NULL,
+            channel_id INTEGER NOT NULL,
+            PRIMARY KEY(app_id, channel_id),
+            FOREIGN KEY(app_id) REFERENCES apps(id) ON DELETE CASCADE,
+            FOREIGN KEY(channel_id) REFERENCES notification_channels(id) ON DELETE CASCADE
+        );
+CREATE TABLE notification_deliveries (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            notification_id INTEGER NOT NULL,
+            channel_id INTEGER,
+            channel_name TEXT NOT NULL,
+            attempt INTEGER NOT NULL,
+            success INTEGER NOT NULL,
+            error TEXT,
+            created_at INTEGER NOT NULL,
+            FOREIGN KEY(notification_id) REFERENCES notifications(id) ON DELETE CASCADE,
+            FOREIGN KEY(channel_id) REFERENCES notification_channels(id) ON DELETE SET NULL
+        );
+CREATE TABLE secrets (
+            name TEXT PRIMARY KEY,
+            nonce BLOB NOT NULL,
+            ciphertext BLOB NOT NULL,
+            updated_at INTEGER NOT NULL
+        );
@@ -0,0 +1,605 @@
+use anyhow::{anyhow, Context, Result};
+use rusqlite::{params, Connection};
+
+/// A schema change, applied once and in order inside its own transaction
+pub struct Migration {
+    pub version: i32,
+    pub description: &'static str,
+    pub up: fn(&Connection) -> Result<()>,
+}
+
+/// All migrations in order; append new ones and never change one that has shipped
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "Baseline schema",
+        up: baseline,
+    },
+    Migration {
+        version: 2,
+        description: "Launch history",
+        up: launch_history,
+    },
+    Migration {
+        version: 3,
+        description: "Pages and folders",
+        up: pages_and_folders,
+    },
+    Migration {
+        version: 4,
+        description: "Tags and saved filters",
+        up: tags_and_filters,
+    },
+    Migration {
+        version: 5,
+        description: "App working directory and environment",
+        up: app_environment,
+    },
+    Migration {
+        version: 6,
+        description: "Single instance apps",
+        up: single_instance,
+    },
+    Migration {
+        version: 7,
+        description: "Workspaces",
+        up: workspaces,
+    },
+    Migration {
+        version: 8,
+        description: "App hooks",
+        up: app_hooks,
+    },
+];
+
+/// Schema version this build expects
+pub fn latest_version() -> i32 {
+    MIGRATIONS.last().map_or(0, |m| m.version)
+}
+
+/// Schema version the database is at
+pub fn schema_version(conn: &Connection) -> Result<i32> {
+    Ok(conn.pragma_query_value(None, "user_version", |row| row.get(0))?)
+}
+
+/// Bring the database schema up to date
+pub fn migrate(conn: &mut Connection) -> Result<()> {
+    run(conn, MIGRATIONS)
+}
+
+/// Apply the migrations newer than the database's version, each in its own transaction
+///
+/// A failed migration is rolled back and leaves the version at the last one that succeeded.
+fn run(conn: &mut Connection, migrations: &[Migration]) -> Result<()> {
+    let current = schema_version(conn)?;
+    let latest = migrations.last().map_or(0, |m| m.version);
+
+    if current > latest {
+        return Err(anyhow!(
+            "The database was created by a newer version of jvlauncher (schema version {}, this version supports up to {}). Please update jvlauncher.",
+            current,
+            latest
+        ));
+    }
+
+    for migration in migrations.iter().filter(|m| m.version > current) {
+        let tx = conn.transaction()?;
+        (migration.up)(&tx).with_context(|| {
+            format!("Database migration {} ({}) failed", migration.version, migration.description)
+        })?;
+        tx.pragma_update(None, "user_version", migration.version)?;
+        tx.commit()?;
+        println!("[Database] Applied migration {}: {}", migration.version, migration.description);
+    }
+
+    Ok(())
+}
+
+/// Add a column unless the table already has it
+fn add_column(conn: &Connection, table: &str, definition: &str) -> Result<()> {
+    let column = definition.split_whitespace().next().unwrap_or_default();
+    let exists: bool = conn.query_row(
+        "SELECT COUNT(*) > 0 FROM pragma_table_info(?1) WHERE name = ?2",
+        params![table, column],
+        |row| row.get(0),
+    )?;
+
+    if !exists {
+        conn.execute(&format!("ALTER TABLE {} ADD COLUMN {}", table, definition), [])?;
+    }
+    Ok(())
+}
+
+/// Version 1: the schema as it was before versioned migrations
+///
+/// Databases created before `user_version` was tracked may be missing any of the columns that
+/// used to be added on startup, so this adds only the ones that are missing.
+fn baseline(conn: &Connection) -> Result<()> {
+    // Apps table
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS apps (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            app_type TEXT NOT NULL,
+            name TEXT NOT NULL,
+            icon_path TEXT,
+            position INTEGER NOT NULL,
+            shortcut TEXT,
+            global_shortcut TEXT
+        )",
+        [],
+    )?;
+
+    // Add global_shortcut column to existing apps table if it doesn't exist
+    add_column(conn, "apps", "global_shortcut TEXT")?;
+
+    // App details table (for native apps and TUI)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS app_details (
+            app_id INTEGER PRIMARY KEY,
+            binary_path TEXT NOT NULL,
+            cli_params TEXT,
+            FOREIGN KEY(app_id) REFERENCES apps(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+
+    // Webapp details table
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS webapp_details (
+            app_id INTEGER PRIMARY KEY,
+            url TEXT NOT NULL,
+            session_data_path TEXT NOT NULL,
+            window_x INTEGER,
+            window_y INTEGER,
+            window_width INTEGER,
+            window_height INTEGER,
+            FOREIGN KEY(app_id) REFERENCES apps(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+
+    // Add columns to existing webapp_details table if they don't exist
+    add_column(conn, "webapp_details", "window_x INTEGER")?;
+    add_column(conn, "webapp_details", "window_y INTEGER")?;
+    add_column(conn, "webapp_details", "window_width INTEGER")?;
+    add_column(conn, "webapp_details", "window_height INTEGER")?;
+    add_column(conn, "webapp_details", "show_nav_controls INTEGER DEFAULT 0")?;
+    add_column(conn, "webapp_details", "open_external_links INTEGER DEFAULT 0")?;
+    add_column(conn, "webapp_details", "enable_oauth INTEGER DEFAULT 0")?;
+    add_column(conn, "webapp_details", "auto_close_timeout INTEGER")?;
+    add_column(conn, "webapp_details", "always_on_top INTEGER DEFAULT 0")?;
+    add_column(conn, "webapp_details", "hide_on_shortcut INTEGER DEFAULT 0")?;
+
+    // Add always_on_top and hide_on_shortcut columns to app_details table if they don't exist
+    add_column(conn, "app_details", "always_on_top INTEGER DEFAULT 0")?;
+    add_column(conn, "app_details", "hide_on_shortcut INTEGER DEFAULT 0")?;
+
+    // Settings table
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS settings (
+            key TEXT PRIMARY KEY,
+            value TEXT NOT NULL
+        )",
+        [],
+    )?;
+
+    // AI models table
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS ai_models (
+            id TEXT PRIMARY KEY,
+            created INTEGER
+        )",
+        [],
+    )?;
+
+    // Agent apps table
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS agent_apps (
+            app_id INTEGER PRIMARY KEY,
+            model TEXT,
+            prompt TEXT NOT NULL,
+            tool_notification INTEGER DEFAULT 0,
+            tool_website_scrape INTEGER DEFAULT 0,
+            tool_run_command INTEGER DEFAULT 0,
+            website_url TEXT,
+            website_scrape_mode TEXT DEFAULT 'text',
+            command TEXT,
+            FOREIGN KEY(app_id) REFERENCES apps(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+
+    // Add website_scrape_mode column if it doesn't exist (migration)
+    add_column(conn, "agent_apps", "website_scrape_mode TEXT DEFAULT 'text'")?;
+
+    // Add memory columns if they don't exist (migration)
+    add_column(conn, "agent_apps", "tool_memory INTEGER DEFAULT 0")?;
+    add_column(conn, "agent_apps", "memory_in_prompt INTEGER DEFAULT 0")?;
+
+    // Agent memory table (persistent key/value store per agent)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS agent_memory (
+            app_id INTEGER NOT NULL,
+            key TEXT NOT NULL,
+            value TEXT NOT NULL,
+            updated_at INTEGER NOT NULL,
+            PRIMARY KEY(app_id, key),
+            FOREIGN KEY(app_id) REFERENCES apps(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+
+    // Add tool_search_documents column if it doesn't exist (migration)
+    add_column(conn, "agent_apps", "tool_search_documents INTEGER DEFAULT 0")?;
+
+    // Add notification_target column if it doesn't exist (migration)
+    add_column(conn, "agent_apps", "notification_target TEXT DEFAULT 'os'")?;
+
+    // Add conditions columns if they don't exist (migration)
+    add_column(conn, "agent_apps", "conditions TEXT")?;
+    add_column(conn, "agent_apps", "conditions_default TEXT DEFAULT 'run'")?;
+
+    // Document retrieval tables
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS document_sources (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            path TEXT NOT NULL UNIQUE,
+            enabled INTEGER DEFAULT 1,
+            last_indexed_at INTEGER
+        )",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS document_files (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            source_id INTEGER NOT NULL,
+            path TEXT NOT NULL UNIQUE,
+            modified_at INTEGER NOT NULL,
+            size INTEGER NOT NULL,
+            FOREIGN KEY(source_id) REFERENCES document_sources(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS document_chunks (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            file_id INTEGER NOT NULL,
+            chunk_index INTEGER NOT NULL,
+            content TEXT NOT NULL,
+            embedding BLOB NOT NULL,
+            FOREIGN KEY(file_id) REFERENCES document_files(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+
+    // AI queue table
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS ai_queue (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            status TEXT NOT NULL,
+            message TEXT NOT NULL,
+            response TEXT,
+            created_at INTEGER NOT NULL,
+            completed_at INTEGER,
+            agent_name TEXT
+        )",
+        [],
+    )?;
+
+    // Add agent_name column if it doesn't exist (migration)
+    add_column(conn, "ai_queue", "agent_name TEXT")?;
+
+    // Add decision column if it doesn't exist (migration)
+    add_column(conn, "ai_queue", "decision TEXT")?;
+
+    // Add app_id column if it doesn't exist (migration)
+    add_column(conn, "ai_queue", "app_id INTEGER REFERENCES apps(id) ON DELETE SET NULL")?;
+
+    // Notifications table
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS notifications (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            text TEXT NOT NULL,
+            created_at INTEGER NOT NULL,
+            dismissed INTEGER DEFAULT 0
+        )",
+        [],
+    )?;
+
+    // Add notification source, severity, link and snooze columns if they don't exist (migration)
+    add_column(conn, "notifications", "app_id INTEGER REFERENCES apps(id) ON DELETE SET NULL")?;
+    add_column(conn, "notifications", "queue_id INTEGER")?;
+    add_column(conn, "notifications", "severity TEXT DEFAULT 'normal'")?;
+    add_column(conn, "notifications", "title TEXT")?;
+    add_column(conn, "notifications", "url TEXT")?;
+    add_column(conn, "notifications", "snoozed_until INTEGER")?;
+
+    // Outbound notification channels
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS notification_channels (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL,
+            kind TEXT NOT NULL,
+            config TEXT NOT NULL DEFAULT '{}',
+            enabled INTEGER DEFAULT 1
+        )",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS agent_notification_channels (
+            app_id INTEGER NOT NULL,
+            channel_id INTEGER NOT NULL,
+            PRIMARY KEY(app_id, channel_id),
+            FOREIGN KEY(app_id) REFERENCES apps(id) ON DELETE CASCADE,
+            FOREIGN KEY(channel_id) REFERENCES notification_channels(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+
+    // Delivery log for outbound channels (kept when the channel is deleted)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS notification_deliveries (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            notification_id INTEGER NOT NULL,
+            channel_id INTEGER,
+            channel_name TEXT NOT NULL,
+            attempt INTEGER NOT NULL,
+            success INTEGER NOT NULL,
+            error TEXT,
+            created_at INTEGER NOT NULL,
+            FOREIGN KEY(notification_id) REFERENCES notifications(id) ON DELETE CASCADE,
+            FOREIGN KEY(channel_id) REFERENCES notification_channels(id) ON DELETE SET NULL
+        )",
+        [],
+    )?;
+
+    // Encrypted secrets (values are never stored in plain text)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS secrets (
+            name TEXT PRIMARY KEY,
+            nonce BLOB NOT NULL,
+            ciphertext BLOB NOT NULL,
+            updated_at INTEGER NOT NULL
+        )",
+        [],
+    )?;
+
+    Ok(())
+}
+
+/// Version 2: one row per launch, for usage statistics
+fn launch_history(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "CREATE TABLE launch_history (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            app_id INTEGER NOT NULL,
+            launched_at INTEGER NOT NULL,
+            trigger TEXT NOT NULL,
+            error TEXT,
+            FOREIGN KEY(app_id) REFERENCES apps(id) ON DELETE CASCADE
+        );
+        CREATE INDEX idx_launch_history_app ON launch_history(app_id, launched_at);
+        CREATE INDEX idx_launch_history_time ON launch_history(launched_at);",
+    )?;
+    Ok(())
+}
+
+/// Version 3: named pages and nested folders in the app grid
+///
+/// Existing apps end up at the top level of a single page.
+fn pages_and_folders(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "CREATE TABLE pages (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL,
+            position INTEGER NOT NULL,
+            grid_cols INTEGER,
+            grid_rows INTEGER
+        );
+        INSERT INTO pages (id, name, position) VALUES (1, 'Apps', 0);
+        CREATE TABLE app_groups (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            page_id INTEGER NOT NULL,
+            parent_id INTEGER,
+            name TEXT NOT NULL,
+            position INTEGER NOT NULL,
+            FOREIGN KEY(page_id) REFERENCES pages(id) ON DELETE CASCADE,
+            FOREIGN KEY(parent_id) REFERENCES app_groups(id) ON DELETE CASCADE
+        );
+        ALTER TABLE apps ADD COLUMN page_id INTEGER NOT NULL DEFAULT 1;
+        ALTER TABLE apps ADD COLUMN group_id INTEGER REFERENCES app_groups(id);",
+    )?;
+    Ok(())
+}
+
+/// Version 4: free-form app tags and saved filters over tags, types and agent tools
+fn tags_and_filters(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "CREATE TABLE tags (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL UNIQUE COLLATE NOCASE
+        );
+        CREATE TABLE app_tags (
+            app_id INTEGER NOT NULL,
+            tag_id INTEGER NOT NULL,
+            PRIMARY KEY(app_id, tag_id),
+            FOREIGN KEY(app_id) REFERENCES apps(id) ON DELETE CASCADE,
+            FOREIGN KEY(tag_id) REFERENCES tags(id) ON DELETE CASCADE
+        );
+        CREATE INDEX idx_app_tags_tag ON app_tags(tag_id);
+        CREATE TABLE saved_filters (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL,
+            rule TEXT NOT NULL DEFAULT '{}',
+            pinned INTEGER DEFAULT 0,
+            position INTEGER NOT NULL
+        );",
+    )?;
+    Ok(())
+}
+
+fn app_environment(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "ALTER TABLE app_details ADD COLUMN working_dir TEXT;
+        ALTER TABLE app_details ADD COLUMN env TEXT NOT NULL DEFAULT '[]';
+        ALTER TABLE app_details ADD COLUMN env_file TEXT;",
+    )?;
+    Ok(())
+}
+
+fn single_instance(conn: &Connection) -> Result<()> {
+    conn.execute_batch("ALTER TABLE app_details ADD COLUMN single_instance INTEGER DEFAULT 0;")?;
+    Ok(())
+}
+
+/// Version 7: the apps a workspace launches, in order, with their window layout
+fn workspaces(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "CREATE TABLE workspace_items (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            workspace_id INTEGER NOT NULL,
+            app_id INTEGER NOT NULL,
+            position INTEGER NOT NULL,
+            delay_ms INTEGER NOT NULL DEFAULT 0,
+            window_x INTEGER,
+            window_y INTEGER,
+            window_width INTEGER,
+            window_height INTEGER,
+            FOREIGN KEY(workspace_id) REFERENCES apps(id) ON DELETE CASCADE,
+            FOREIGN KEY(app_id) REFERENCES apps(id) ON DELETE CASCADE
+        );
+        CREATE INDEX idx_workspace_items_workspace ON workspace_items(workspace_id, position);",
+    )?;
+    Ok(())
+}
+
+/// Version 8: commands run before an app starts and after it ends, for every app type
+fn app_hooks(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "ALTER TABLE apps ADD COLUMN pre_launch_hook TEXT;
+        ALTER TABLE apps ADD COLUMN post_exit_hook TEXT;
+        ALTER TABLE apps ADD COLUMN hook_timeout INTEGER;",
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn columns(conn: &Connection, table: &str) -> Vec<String> {
+        let mut stmt = conn.prepare("SELECT name FROM pragma_table_info(?1)").unwrap();
+        stmt.query_map(params![table], |row| row.get(0))
+            .unwrap()
+            .collect::<rusqlite::Result<_>>()
+            .unwrap()
+    }
+
+    fn tables(conn: &Connection) -> Vec<String> {
+        let mut stmt = conn
+            .prepare("SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name")
+            .unwrap();
+        stmt.query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<rusqlite::Result<_>>()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_fresh_database_is_at_latest_version() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        migrate(&mut conn).unwrap();
+        assert_eq!(schema_version(&conn).unwrap(), latest_version());
+
+        // Running again is a no-op
+        migrate(&mut conn).unwrap();
+        assert_eq!(schema_version(&conn).unwrap(), latest_version());
+    }
+
+    #[test]
+    fn test_upgrade_from_unversioned_snapshot() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(include_str!("fixtures/schema_unversioned.sql")).unwrap();
+        assert_eq!(schema_version(&conn).unwrap(), 0);
+
+        migrate(&mut conn).unwrap();
+        assert_eq!(schema_version(&conn).unwrap(), latest_version());
+
+        // The upgraded database has the same tables and columns as a fresh one
+        let mut fresh = Connection::open_in_memory().unwrap();
+        migrate(&mut fresh).unwrap();
+        assert_eq!(tables(&conn), tables(&fresh));
+        for table in tables(&fresh) {
+            let mut upgraded = columns(&conn, &table);
+            let mut expected = columns(&fresh, &table);
+            upgraded.sort();
+            expected.sort();
+            assert_eq!(upgraded, expected, "columns of {}", table);
+        }
+
+        // Existing rows survive
+        let name: String = conn.query_row("SELECT name FROM apps WHERE id = 1", [], |row| row.get(0)).unwrap();
+        assert_eq!(name, "Editor");
+        let response: String = conn
+            .query_row("SELECT response FROM ai_queue WHERE app_id = 3", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(response, "Price is 42");
+    }
+
+    #[test]
+    fn test_upgrade_adds_missing_legacy_columns() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE apps (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                app_type TEXT NOT NULL,
+                name TEXT NOT NULL,
+                icon_path TEXT,
+                position INTEGER NOT NULL,
+                shortcut TEXT
+            );
+            INSERT INTO apps (app_type, name, position) VALUES ('app', 'Old', 0);",
+        )
+        .unwrap();
+
+        migrate(&mut conn).unwrap();
+        assert!(columns(&conn, "apps").contains(&"global_shortcut".to_string()));
+        let count: i64 = conn.query_row("SELECT COUNT(*) FROM apps", [], |row| row.get(0)).unwrap();
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn test_newer_database_is_rejected() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        conn.pragma_update(None, "user_version", latest_version() + 1).unwrap();
+
+        let error = migrate(&mut conn).unwrap_err().to_string();
+        assert!(error.contains("newer version"), "{}", error);
+    }
+
+    #[test]
+    fn test_failed_migration_is_rolled_back() {
+        fn create(conn: &Connection) -> Result<()> {
+            conn.execute("CREATE TABLE first (id INTEGER)", [])?;
+            Ok(())
+        }
+        fn fail(conn: &Connection) -> Result<()> {
+            conn.execute("CREATE TABLE second (id INTEGER)", [])?;
+            conn.execute("ALTER TABLE missing ADD COLUMN x TEXT", [])?;
+            Ok(())
+        }
+        let migrations = [
+            Migration { version: 1, description: "create", up: create },
+            Migration { version: 2, description: "fail", up: fail },
+        ];
+
+        let mut conn = Connection::open_in_memory().unwrap();
+        let error = run(&mut conn, &migrations).unwrap_err();
+        assert!(format!("{:#}", error).contains("migration 2 (fail)"));
+        assert_eq!(schema_version(&conn).unwrap(), 1);
+        assert_eq!(tables(&conn), vec!["first".to_string()]);
+    }
+}
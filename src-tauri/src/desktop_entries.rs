@@ -0,0 +1,310 @@
+use anyhow::{anyhow, bail, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// An application found in a freedesktop .desktop file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DesktopEntry {
+    /// Desktop file id, e.g. `org.gnome.Calculator` or `kde-konsole` for files in subfolders
+    pub id: String,
+    pub path: String,
+    pub name: String,
+    pub binary_path: String,
+    /// Remaining Exec arguments, quoted the way the launcher splits them
+    pub cli_params: Option<String>,
+    pub icon: Option<String>,
+    pub terminal: bool,
+    /// NoDisplay or Hidden is set; such entries aren't offered for import
+    pub hidden: bool,
+}
+
+/// Folders with .desktop files, most important first:
+/// `$XDG_DATA_HOME/applications` (default `~/.local/share/applications`), then `$XDG_DATA_DIRS`
+pub fn application_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    match std::env::var("XDG_DATA_HOME") {
+        Ok(data_home) if !data_home.is_empty() => dirs.push(PathBuf::from(data_home)),
+        _ => {
+            if let Ok(home) = std::env::var("HOME") {
+                dirs.push(Path::new(&home).join(".local/share"));
+            }
+        }
+    }
+    let data_dirs = std::env::var("XDG_DATA_DIRS")
+        .ok()
+        .filter(|d| !d.is_empty())
+        .unwrap_or_else(|| "/usr/local/share:/usr/share".to_string());
+    dirs.extend(data_dirs.split(':').filter(|d| !d.is_empty()).map(PathBuf::from));
+    dirs.into_iter().map(|dir| dir.join("applications")).collect()
+}
+
+/// Importable applications in the given folders, sorted by name.
+/// A desktop file id found in an earlier folder shadows the same id in later ones.
+pub fn scan(dirs: &[PathBuf]) -> Vec<DesktopEntry> {
+    let mut seen = HashSet::new();
+    let mut entries = Vec::new();
+    for dir in dirs {
+        let mut files = Vec::new();
+        collect_desktop_files(dir, dir, &mut files);
+        files.sort();
+        for (id, path) in files {
+            if !seen.insert(id.clone()) {
+                continue;
+            }
+            match fs::read_to_string(&path).map_err(anyhow::Error::from).and_then(|content| parse(&id, &path, &content)) {
+                Ok(Some(entry)) if !entry.hidden => entries.push(entry),
+                Ok(_) => {}
+                Err(e) => eprintln!("Skipping desktop entry {}: {}", path.display(), e),
+            }
+        }
+    }
+    entries.sort_by_key(|entry| entry.name.to_lowercase());
+    entries
+}
+
+fn collect_desktop_files(root: &Path, dir: &Path, files: &mut Vec<(String, PathBuf)>) {
+    let Ok(read_dir) = fs::read_dir(dir) else { return };
+    for entry in read_dir.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_desktop_files(root, &path, files);
+        } else if path.extension().is_some_and(|ext| ext == "desktop") {
+            // Files in subfolders get ids like `kde-konsole`
+            let id = path
+                .strip_prefix(root)
+                .unwrap_or(&path)
+                .with_extension("")
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy().into_owned())
+                .collect::<Vec<_>>()
+                .join("-");
+            files.push((id, path));
+        }
+    }
+}
+
+/// Parse a .desktop file; returns None for entries that aren't applications
+pub fn parse(id: &str, path: &Path, content: &str) -> Result<Option<DesktopEntry>> {
+    let mut in_main_group = false;
+    let mut fields = std::collections::HashMap::new();
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if line.starts_with('[') {
+            in_main_group = line == "[Desktop Entry]";
+            continue;
+        }
+        if !in_main_group {
+            continue;
+        }
+        // Localized keys such as Name[de] are skipped
+        if let Some((key, value)) = line.split_once('=') {
+            let key = key.trim();
+            if !key.contains('[') {
+                fields.entry(key.to_string()).or_insert_with(|| unescape_value(value.trim()));
+            }
+        }
+    }
+
+    if fields.get("Type").map(String::as_str) != Some("Application") {
+        return Ok(None);
+    }
+    let name = fields.get("Name").filter(|n| !n.is_empty()).ok_or_else(|| anyhow!("missing Name"))?;
+    let exec = fields.get("Exec").ok_or_else(|| anyhow!("missing Exec"))?;
+    let mut args = split_exec(exec)?;
+    if args.is_empty() {
+        bail!("empty Exec");
+    }
+    let binary_path = args.remove(0);
+    let is_true = |key: &str| fields.get(key).is_some_and(|v| v == "true");
+
+    Ok(Some(DesktopEntry {
+        id: id.to_string(),
+        path: path.to_string_lossy().to_string(),
+        name: name.clone(),
+        binary_path,
+        cli_params: (!args.is_empty()).then(|| args.iter().map(|a| quote_arg(a)).collect::<Vec<_>>().join(" ")),
+        icon: fields.get("Icon").filter(|i| !i.is_empty()).cloned(),
+        terminal: is_true("Terminal"),
+        hidden: is_true("NoDisplay") || is_true("Hidden"),
+    }))
+}
+
+/// Resolve the `\s`, `\n`, `\t`, `\r` and `\\` escapes of a value
+fn unescape_value(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            out.push(ch);
+            continue;
+        }
+        match chars.next() {
+            Some('s') => out.push(' '),
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some('r') => out.push('\r'),
+            Some(other) => {
+                // Keep unknown escapes for the Exec quoting rules, e.g. `\"`
+                if other != '\\' {
+                    out.push('\\');
+                }
+                out.push(other);
+            }
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+/// Split an Exec value into arguments and strip field codes (`%f`, `%U`, `%i`, ...).
+/// Arguments that are only a field code are dropped and `%%` becomes `%`.
+fn split_exec(exec: &str) -> Result<Vec<String>> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut started = false;
+    let mut chars = exec.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '"' => {
+                started = true;
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c @ ('"' | '`' | '$' | '\\')) => current.push(c),
+                            Some(c) => {
+                                current.push('\\');
+                                current.push(c);
+                            }
+                            None => bail!("unterminated quote in Exec"),
+                        },
+                        Some(c) => current.push(c),
+                        None => bail!("unterminated quote in Exec"),
+                    }
+                }
+            }
+            ' ' | '\t' => {
+                if started {
+                    args.extend(strip_field_codes(&current));
+                    current.clear();
+                    started = false;
+                }
+            }
+            '%' => {
+                started = true;
+                match chars.next() {
+                    Some('%') => current.push('%'),
+                    // A field code, marked so a lone one drops the whole argument
+                    Some(_) => current.push('\0'),
+                    None => {}
+                }
+            }
+            _ => {
+                started = true;
+                current.push(ch);
+            }
+        }
+    }
+    if started {
+        args.extend(strip_field_codes(&current));
+    }
+    Ok(args)
+}
+
+fn strip_field_codes(arg: &str) -> Option<String> {
+    if !arg.is_empty() && arg.chars().all(|c| c == '\0') {
+        return None;
+    }
+    Some(arg.replace('\0', ""))
+}
+
+/// Quote an argument so the launcher's command line splitting gives it back unchanged
+fn quote_arg(arg: &str) -> String {
+    if !arg.is_empty() && !arg.chars().any(|c| c.is_whitespace() || matches!(c, '"' | '\'' | '\\')) {
+        return arg.to_string();
+    }
+    let mut quoted = String::from("\"");
+    for ch in arg.chars() {
+        if matches!(ch, '"' | '\\') {
+            quoted.push('\\');
+        }
+        quoted.push(ch);
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture(name: &str) -> Option<DesktopEntry> {
+        let content = match name {
+            "firefox" => include_str!("fixtures/desktop_entries/firefox.desktop"),
+            "htop" => include_str!("fixtures/desktop_entries/htop.desktop"),
+            "mimeinfo-helper" => include_str!("fixtures/desktop_entries/mimeinfo-helper.desktop"),
+            "quoted" => include_str!("fixtures/desktop_entries/quoted.desktop"),
+            _ => unreachable!(),
+        };
+        parse(name, Path::new(&format!("{}.desktop", name)), content).unwrap()
+    }
+
+    #[test]
+    fn test_parse_desktop_entries() {
+        // Localized names and desktop actions are ignored, %u is stripped
+        let firefox = fixture("firefox").unwrap();
+        assert_eq!(firefox.name, "Firefox Web Browser");
+        assert_eq!(firefox.binary_path, "firefox");
+        assert_eq!(firefox.cli_params, None);
+        assert_eq!(firefox.icon.as_deref(), Some("firefox"));
+        assert!(!firefox.terminal && !firefox.hidden);
+
+        let htop = fixture("htop").unwrap();
+        assert!(htop.terminal);
+        assert_eq!(htop.binary_path, "htop");
+
+        let helper = fixture("mimeinfo-helper").unwrap();
+        assert!(helper.hidden);
+        assert_eq!(helper.cli_params.as_deref(), Some("--handle"));
+
+        // Escapes, quoting, %% and field codes inside and outside arguments
+        let quoted = fixture("quoted").unwrap();
+        assert_eq!(quoted.name, "Report Tool");
+        assert_eq!(quoted.binary_path, "/opt/Report Tool/report");
+        assert_eq!(quoted.cli_params.as_deref(), Some(r#"--title "Sales \"Q3\"" --progress=50% --"#));
+        assert_eq!(quoted.icon.as_deref(), Some("/opt/Report Tool/icon.png"));
+
+        assert_eq!(parse("link", Path::new("link.desktop"), "[Desktop Entry]\nType=Link\nName=Docs\nURL=https://example.com").unwrap(), None);
+        assert!(parse("broken", Path::new("broken.desktop"), "[Desktop Entry]\nType=Application\nName=Broken\nExec=\"unterminated").is_err());
+        assert!(parse("nameless", Path::new("nameless.desktop"), "[Desktop Entry]\nType=Application\nExec=true").is_err());
+    }
+
+    #[test]
+    fn test_scan_application_dirs() {
+        let root = std::env::temp_dir().join(format!("jvlauncher-desktop-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let user = root.join("user/applications");
+        let system = root.join("system/applications");
+        fs::create_dir_all(user.join("kde")).unwrap();
+        fs::create_dir_all(&system).unwrap();
+
+        fs::write(system.join("firefox.desktop"), include_str!("fixtures/desktop_entries/firefox.desktop")).unwrap();
+        fs::write(system.join("htop.desktop"), include_str!("fixtures/desktop_entries/htop.desktop")).unwrap();
+        fs::write(system.join("mimeinfo-helper.desktop"), include_str!("fixtures/desktop_entries/mimeinfo-helper.desktop")).unwrap();
+        fs::write(system.join("notes.txt"), "not a desktop file").unwrap();
+        // The user's copy hides the system htop entry
+        fs::write(user.join("htop.desktop"), "[Desktop Entry]\nType=Application\nName=Htop\nExec=htop\nHidden=true\n").unwrap();
+        fs::write(user.join("kde/konsole.desktop"), "[Desktop Entry]\nType=Application\nName=Konsole\nExec=konsole\n").unwrap();
+
+        let entries = scan(&[user, system, root.join("missing")]);
+        let ids: Vec<_> = entries.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, vec!["firefox", "kde-konsole"]);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
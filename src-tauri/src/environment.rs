@@ -0,0 +1,306 @@
+use crate::database::{App, DbPool};
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// An environment variable override for an app; without a value the variable is removed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EnvVar {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+}
+
+/// Working directory and environment changes to apply when starting an app
+#[derive(Debug, Default, PartialEq)]
+pub struct LaunchEnv {
+    pub working_dir: Option<PathBuf>,
+    /// Variables to set, in the order they were defined
+    pub set: Vec<(String, String)>,
+    /// Variables to remove from the inherited environment
+    pub unset: Vec<String>,
+}
+
+impl LaunchEnv {
+    /// Apply to a process about to be spawned
+    pub fn apply(&self, cmd: &mut Command) {
+        if let Some(dir) = &self.working_dir {
+            cmd.current_dir(dir);
+        }
+        for name in &self.unset {
+            cmd.env_remove(name);
+        }
+        cmd.envs(self.set.iter().map(|(k, v)| (k, v)));
+    }
+
+    fn set_var(&mut self, name: &str, value: String) {
+        self.unset.retain(|n| n != name);
+        match self.set.iter_mut().find(|(n, _)| n == name) {
+            Some(entry) => entry.1 = value,
+            None => self.set.push((name.to_string(), value)),
+        }
+    }
+
+    fn unset_var(&mut self, name: &str) {
+        self.set.retain(|(n, _)| n != name);
+        if !self.unset.iter().any(|n| n == name) {
+            self.unset.push(name.to_string());
+        }
+    }
+}
+
+/// Reject variable names a shell couldn't use
+pub fn validate(env: &[EnvVar]) -> Result<()> {
+    for var in env {
+        if !is_valid_name(&var.name) {
+            bail!("Invalid environment variable name '{}'", var.name);
+        }
+    }
+    Ok(())
+}
+
+fn is_valid_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Working directory and environment for launching an app, with `${VAR}` and `${secret:NAME}` resolved
+pub fn resolve(pool: &DbPool, app: &App) -> Result<LaunchEnv> {
+    build(
+        app.working_dir.as_deref(),
+        app.env_file.as_deref(),
+        &app.env,
+        std::env::vars().collect(),
+        |text| crate::secrets::resolve(pool, text),
+    )
+}
+
+/// The `.env` file is applied first and the app's overrides after it; each value can refer
+/// to the inherited environment and to variables defined before it
+fn build(
+    working_dir: Option<&str>,
+    env_file: Option<&str>,
+    env: &[EnvVar],
+    mut vars: HashMap<String, String>,
+    resolve_secrets: impl Fn(&str) -> Result<String>,
+) -> Result<LaunchEnv> {
+    validate(env)?;
+    let mut launch = LaunchEnv::default();
+
+    if let Some(dir) = working_dir.filter(|d| !d.trim().is_empty()) {
+        let dir = expand_path(dir.trim(), &vars);
+        if !dir.is_absolute() {
+            bail!("Working directory must be an absolute path: {}", dir.display());
+        }
+        if !dir.is_dir() {
+            bail!("Working directory does not exist: {}", dir.display());
+        }
+        launch.working_dir = Some(dir);
+    }
+
+    if let Some(file) = env_file.filter(|f| !f.trim().is_empty()) {
+        let mut path = expand_path(file.trim(), &vars);
+        if path.is_relative() {
+            let base = launch
+                .working_dir
+                .as_ref()
+                .ok_or_else(|| anyhow!("A relative env file needs a working directory: {}", path.display()))?;
+            path = base.join(path);
+        }
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read env file {}", path.display()))?;
+        for entry in parse_env_file(&content).with_context(|| format!("Invalid env file {}", path.display()))? {
+            let value = if entry.literal { entry.value } else { expand(&entry.value, &vars) };
+            let value = resolve_secrets(&value)?;
+            vars.insert(entry.name.clone(), value.clone());
+            launch.set_var(&entry.name, value);
+        }
+    }
+
+    for var in env {
+        match &var.value {
+            Some(value) => {
+                let value = resolve_secrets(&expand(value, &vars))?;
+                vars.insert(var.name.clone(), value.clone());
+                launch.set_var(&var.name, value);
+            }
+            None => {
+                vars.remove(&var.name);
+                launch.unset_var(&var.name);
+            }
+        }
+    }
+
+    Ok(launch)
+}
+
+/// Replace `${VAR}` with the variable's value, or nothing when it isn't set.
+/// `${secret:NAME}` references are left for the secrets store.
+pub fn expand(value: &str, vars: &HashMap<String, String>) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        match after.find('}') {
+            Some(end) if is_valid_name(&after[..end]) => {
+                out.push_str(vars.get(&after[..end]).map(String::as_str).unwrap_or(""));
+                rest = &after[end + 1..];
+            }
+            _ => {
+                out.push_str("${");
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn expand_path(path: &str, vars: &HashMap<String, String>) -> PathBuf {
+    let path = expand(path, vars);
+    match path.strip_prefix("~/").or(if path == "~" { Some("") } else { None }) {
+        Some(rest) => Path::new(vars.get("HOME").map(String::as_str).unwrap_or("/")).join(rest),
+        None => PathBuf::from(path),
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct EnvFileEntry {
+    name: String,
+    value: String,
+    /// Single-quoted values are taken as they are, without `${VAR}` expansion
+    literal: bool,
+}
+
+/// Parse `NAME=value` lines; `export`, comments, quotes and `\n` escapes in double quotes are understood
+fn parse_env_file(content: &str) -> Result<Vec<EnvFileEntry>> {
+    let mut entries = Vec::new();
+    for (number, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").map(str::trim_start).unwrap_or(line);
+        let (name, raw) = line
+            .split_once('=')
+            .ok_or_else(|| anyhow!("line {}: expected NAME=value", number + 1))?;
+        let name = name.trim();
+        if !is_valid_name(name) {
+            bail!("line {}: invalid variable name '{}'", number + 1, name);
+        }
+        let raw = raw.trim();
+
+        let (value, literal) = if let Some(quoted) = raw.strip_prefix('\'') {
+            let end = quoted.find('\'').ok_or_else(|| anyhow!("line {}: unterminated quote", number + 1))?;
+            (quoted[..end].to_string(), true)
+        } else if let Some(quoted) = raw.strip_prefix('"') {
+            let mut value = String::new();
+            let mut chars = quoted.chars();
+            loop {
+                match chars.next() {
+                    Some('"') => break,
+                    Some('\\') => match chars.next() {
+                        Some('n') => value.push('\n'),
+                        Some('t') => value.push('\t'),
+                        Some(c) => value.push(c),
+                        None => bail!("line {}: unterminated quote", number + 1),
+                    },
+                    Some(c) => value.push(c),
+                    None => bail!("line {}: unterminated quote", number + 1),
+                }
+            }
+            (value, false)
+        } else {
+            // An unquoted value ends at a comment
+            let value = raw.split(" #").next().unwrap_or(raw).trim_end();
+            (value.to_string(), false)
+        };
+        entries.push(EnvFileEntry { name: name.to_string(), value, literal });
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    fn var(name: &str, value: Option<&str>) -> EnvVar {
+        EnvVar { name: name.to_string(), value: value.map(str::to_string) }
+    }
+
+    #[test]
+    fn test_expand_and_env_file() {
+        let base = vars(&[("HOME", "/home/ada"), ("PATH", "/usr/bin")]);
+        assert_eq!(expand("${HOME}/bin:${PATH}", &base), "/home/ada/bin:/usr/bin");
+        assert_eq!(expand("${MISSING}x", &base), "x");
+        assert_eq!(expand("${secret:TOKEN} ${ not a var", &base), "${secret:TOKEN} ${ not a var");
+        assert_eq!(expand_path("~/work", &base), PathBuf::from("/home/ada/work"));
+
+        let entries = parse_env_file(
+            "# settings\nexport API_URL=https://example.com # comment\nGREETING=\"hello\\nworld\"\nRAW='${HOME}'\n\nEMPTY=\n",
+        )
+        .unwrap();
+        let pairs: Vec<_> = entries.iter().map(|e| (e.name.as_str(), e.value.as_str(), e.literal)).collect();
+        assert_eq!(
+            pairs,
+            vec![
+                ("API_URL", "https://example.com", false),
+                ("GREETING", "hello\nworld", false),
+                ("RAW", "${HOME}", true),
+                ("EMPTY", "", false),
+            ]
+        );
+        assert!(parse_env_file("NOT A LINE").is_err());
+        assert!(parse_env_file("1X=2").is_err());
+        assert!(parse_env_file("X=\"open").is_err());
+    }
+
+    #[test]
+    fn test_build_launch_env() {
+        let dir = std::env::temp_dir().join(format!("jvlauncher-env-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join(".env"), "DATA=${PROJECT}/data\nTOKEN=${secret:TOKEN}\nLITERAL='${PROJECT}'\n").unwrap();
+
+        let base = vars(&[("HOME", "/home/ada"), ("PROJECT", dir.to_str().unwrap()), ("EDITOR", "vi")]);
+        let secrets = |text: &str| Ok(text.replace("${secret:TOKEN}", "s3cret"));
+        let env = [
+            var("PATH", Some("${DATA}/bin")),
+            var("EDITOR", None),
+            var("LITERAL", Some("override")),
+        ];
+        let launch = build(Some("${PROJECT}"), Some(".env"), &env, base.clone(), secrets).unwrap();
+
+        assert_eq!(launch.working_dir.as_deref(), Some(dir.as_path()));
+        let data = format!("{}/data", dir.display());
+        assert_eq!(
+            launch.set,
+            vec![
+                ("DATA".to_string(), data.clone()),
+                ("TOKEN".to_string(), "s3cret".to_string()),
+                ("LITERAL".to_string(), "override".to_string()),
+                ("PATH".to_string(), format!("{}/bin", data)),
+            ]
+        );
+        assert_eq!(launch.unset, vec!["EDITOR".to_string()]);
+
+        // Nothing configured changes nothing
+        assert_eq!(build(None, None, &[], base.clone(), secrets).unwrap(), LaunchEnv::default());
+
+        // Bad settings are reported instead of launching somewhere unexpected
+        assert!(build(Some("relative/dir"), None, &[], base.clone(), secrets).is_err());
+        assert!(build(Some("/does/not/exist"), None, &[], base.clone(), secrets).is_err());
+        assert!(build(None, Some(".env"), &[], base.clone(), secrets).is_err());
+        assert!(build(None, None, &[var("BAD-NAME", Some("x"))], base, secrets).is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
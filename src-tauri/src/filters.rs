@@ -0,0 +1,156 @@
+use crate::database::{AgentApp, App, AppType, DbPool};
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+
+/// Agent tools a filter can require
+pub const AGENT_TOOLS: &[&str] = &["notification", "website_scrape", "run_command", "memory", "search_documents"];
+
+/// Which apps a saved filter shows; an empty list doesn't restrict anything
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FilterRule {
+    /// Apps need every one of these tags
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Apps need to be one of these types
+    #[serde(default)]
+    pub app_types: Vec<AppType>,
+    /// Apps need to be agents with every one of these tools turned on
+    #[serde(default)]
+    pub tools: Vec<String>,
+}
+
+impl FilterRule {
+    /// Reject tools that don't exist
+    pub fn validate(&self) -> Result<()> {
+        for tool in &self.tools {
+            if !AGENT_TOOLS.contains(&tool.as_str()) {
+                bail!("Unknown agent tool '{}', expected one of {}", tool, AGENT_TOOLS.join(", "));
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether an app passes the filter; `agent` is the app's agent configuration, if any
+    pub fn matches(&self, app: &App, agent: Option<&AgentApp>) -> bool {
+        let has_tags = self
+            .tags
+            .iter()
+            .all(|tag| app.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)));
+        let has_type = self.app_types.is_empty() || self.app_types.contains(&app.app_type);
+        let has_tools = self.tools.is_empty()
+            || agent.is_some_and(|agent| self.tools.iter().all(|tool| tool_enabled(agent, tool)));
+        has_tags && has_type && has_tools
+    }
+}
+
+fn tool_enabled(agent: &AgentApp, tool: &str) -> bool {
+    match tool {
+        "notification" => agent.tool_notification,
+        "website_scrape" => agent.tool_website_scrape,
+        "run_command" => agent.tool_run_command,
+        "memory" => agent.tool_memory,
+        "search_documents" => agent.tool_search_documents,
+        _ => false,
+    }
+}
+
+/// Ids of the apps that pass a filter, in grid order
+pub fn matching_apps(pool: &DbPool, rule: &FilterRule) -> Result<Vec<i64>> {
+    let mut ids = Vec::new();
+    for app in crate::database::get_all_apps(pool)? {
+        // Only look up agent configuration when the filter asks about tools
+        let agent = if !rule.tools.is_empty() && app.app_type == AppType::Agent {
+            crate::database::get_agent_app(pool, app.id)?
+        } else {
+            None
+        };
+        if rule.matches(&app, agent.as_ref()) {
+            ids.push(app.id);
+        }
+    }
+    Ok(ids)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{create_app, get_all_apps, save_agent_app, set_app_tags, test_pool, NewApp};
+
+    fn new_app(app_type: AppType, name: &str) -> NewApp {
+        NewApp {
+            app_type,
+            name: name.to_string(),
+            icon_path: None,
+            shortcut: None,
+            global_shortcut: None,
+            binary_path: Some("/bin/true".to_string()),
+            cli_params: None,
+            url: Some("https://example.com".to_string()),
+            show_nav_controls: None,
+            open_external_links: None,
+            enable_oauth: None,
+            auto_close_timeout: None,
+            always_on_top: None,
+            hide_on_shortcut: None,
+            single_instance: None,
+            working_dir: None,
+            env: Vec::new(),
+            env_file: None,
+            pre_launch_hook: None,
+            post_exit_hook: None,
+            hook_timeout: None,
+        }
+    }
+
+    #[test]
+    fn test_saved_filter_rules() {
+        let pool = test_pool();
+        let mail = create_app(&pool, new_app(AppType::Webapp, "Mail"), None).unwrap();
+        let deploy = create_app(&pool, new_app(AppType::Agent, "Deploy watcher"), None).unwrap();
+        let digest = create_app(&pool, new_app(AppType::Agent, "Digest"), None).unwrap();
+        set_app_tags(&pool, mail, &["work".to_string(), "email".to_string()]).unwrap();
+        set_app_tags(&pool, deploy, &["Work".to_string(), "ops".to_string()]).unwrap();
+
+        let agent = AgentApp {
+            app_id: deploy,
+            model: None,
+            prompt: "Check the deploy".to_string(),
+            tool_notification: false,
+            tool_website_scrape: false,
+            tool_run_command: true,
+            website_url: None,
+            website_scrape_mode: None,
+            command: Some("kubectl get pods".to_string()),
+            tool_memory: false,
+            memory_in_prompt: false,
+            tool_search_documents: false,
+            notification_target: "os".to_string(),
+            notification_channel_ids: Vec::new(),
+            conditions: Vec::new(),
+            conditions_default: Default::default(),
+        };
+        save_agent_app(&pool, &agent).unwrap();
+        save_agent_app(&pool, &AgentApp { app_id: digest, tool_run_command: false, ..agent.clone() }).unwrap();
+
+        let rule = |tags: &[&str], app_types: Vec<AppType>, tools: &[&str]| FilterRule {
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            app_types,
+            tools: tools.iter().map(|t| t.to_string()).collect(),
+        };
+
+        // Tags match regardless of case and all of them are needed
+        assert_eq!(matching_apps(&pool, &rule(&["work"], vec![], &[])).unwrap(), vec![mail, deploy]);
+        assert_eq!(matching_apps(&pool, &rule(&["work", "ops"], vec![], &[])).unwrap(), vec![deploy]);
+
+        // "Agents with run_command"
+        let agents = rule(&[], vec![AppType::Agent], &["run_command"]);
+        assert_eq!(matching_apps(&pool, &agents).unwrap(), vec![deploy]);
+        assert_eq!(matching_apps(&pool, &rule(&[], vec![AppType::Agent], &[])).unwrap(), vec![deploy, digest]);
+
+        // An empty rule shows everything
+        assert_eq!(matching_apps(&pool, &FilterRule::default()).unwrap().len(), get_all_apps(&pool).unwrap().len());
+
+        assert!(rule(&[], vec![], &["teleport"]).validate().is_err());
+        assert!(agents.validate().is_ok());
+    }
+}
@@ -0,0 +1,262 @@
+use crate::database::{App, DbPool};
+use crate::environment::LaunchEnv;
+use anyhow::{bail, Result};
+use std::io::Read;
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+
+/// Seconds a hook may run when the app doesn't set a timeout
+pub const DEFAULT_TIMEOUT_SECS: i32 = 30;
+
+/// Longest timeout an app can set, in seconds
+pub const MAX_TIMEOUT_SECS: i32 = 60 * 60;
+
+/// How much of a hook's output is kept for failure reports
+const OUTPUT_LIMIT: usize = 8 * 1024;
+
+/// How often a running hook is checked for having finished
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// When a hook runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hook {
+    /// Before the app starts; the app isn't started when it fails
+    PreLaunch,
+    /// After the app's process or window ended
+    PostExit,
+}
+
+impl Hook {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Hook::PreLaunch => "pre_launch",
+            Hook::PostExit => "post_exit",
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            Hook::PreLaunch => "Pre-launch hook",
+            Hook::PostExit => "Post-exit hook",
+        }
+    }
+}
+
+/// Check an app's hook timeout
+pub fn validate(hook_timeout: Option<i32>) -> Result<()> {
+    if let Some(timeout) = hook_timeout {
+        if !(1..=MAX_TIMEOUT_SECS).contains(&timeout) {
+            bail!("Hook timeout must be between 1 and {} seconds", MAX_TIMEOUT_SECS);
+        }
+    }
+    Ok(())
+}
+
+/// Run the app's pre-launch hook if it has one; fails with the hook's output when it fails or
+/// times out
+pub fn run_pre_launch(pool: &DbPool, app: &App) -> Result<()> {
+    match command(app, Hook::PreLaunch) {
+        Some(command) => run_for_app(pool, app, Hook::PreLaunch, command),
+        None => Ok(()),
+    }
+}
+
+/// Run the app's post-exit hook in the background if it has one and report a failure as a
+/// notification
+pub fn spawn_post_exit(pool: &DbPool, app_handle: &AppHandle, app_id: i64) {
+    let app = match crate::database::get_all_apps(pool) {
+        Ok(apps) => apps.into_iter().find(|a| a.id == app_id),
+        Err(e) => {
+            eprintln!("Failed to load app {} for its post-exit hook: {}", app_id, e);
+            return;
+        }
+    };
+    let Some(app) = app.filter(|app| command(app, Hook::PostExit).is_some()) else {
+        return;
+    };
+
+    let pool = pool.clone();
+    let app_handle = app_handle.clone();
+    std::thread::spawn(move || {
+        let command = command(&app, Hook::PostExit).unwrap_or_default();
+        if let Err(error) = run_for_app(&pool, &app, Hook::PostExit, command) {
+            let message = format!("{:#}", error);
+            eprintln!("{}: {}", app.name, message);
+            if let Err(e) = crate::notifications::report_hook_failure(&pool, &app_handle, app.id, &app.name, &message) {
+                eprintln!("Failed to report failed hook of {}: {}", app.name, e);
+            }
+        }
+    });
+}
+
+/// The app's command for a hook, unless it's blank
+fn command(app: &App, hook: Hook) -> Option<&str> {
+    let command = match hook {
+        Hook::PreLaunch => app.pre_launch_hook.as_deref(),
+        Hook::PostExit => app.post_exit_hook.as_deref(),
+    };
+    command.map(str::trim).filter(|c| !c.is_empty())
+}
+
+/// Run a hook with the app's working directory and environment, telling it which app and hook it's
+/// run for
+fn run_for_app(pool: &DbPool, app: &App, hook: Hook, command: &str) -> Result<()> {
+    let env = crate::environment::resolve(pool, app)?;
+    let vars = [
+        ("JVLAUNCHER_APP_ID", app.id.to_string()),
+        ("JVLAUNCHER_APP_NAME", app.name.clone()),
+        ("JVLAUNCHER_HOOK", hook.as_str().to_string()),
+    ];
+    let timeout = app.hook_timeout.unwrap_or(DEFAULT_TIMEOUT_SECS).clamp(1, MAX_TIMEOUT_SECS);
+    run(hook, command, &env, &vars, Duration::from_secs(timeout as u64))
+}
+
+/// Run a hook through the shell and wait for it; on failure the error has the hook's output
+fn run(hook: Hook, command: &str, env: &LaunchEnv, vars: &[(&str, String)], timeout: Duration) -> Result<()> {
+    #[cfg(unix)]
+    let mut cmd = {
+        use std::os::unix::process::CommandExt;
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c").arg(command);
+        // Its own process group, so a timeout also stops what the hook started
+        cmd.process_group(0);
+        cmd
+    };
+    #[cfg(windows)]
+    let mut cmd = {
+        let mut cmd = Command::new("cmd");
+        cmd.arg("/C").arg(command);
+        cmd
+    };
+    env.apply(&mut cmd);
+    cmd.envs(vars.iter().map(|(k, v)| (k, v)));
+    cmd.stdin(Stdio::null()).stdout(Stdio::piped()).stderr(Stdio::piped());
+
+    let mut child = cmd
+        .spawn()
+        .map_err(|e| anyhow::anyhow!("Failed to run {}: {}", hook.label().to_lowercase(), e))?;
+    let output = Output::new();
+    if let Some(stdout) = child.stdout.take() {
+        output.capture(stdout);
+    }
+    if let Some(stderr) = child.stderr.take() {
+        output.capture(stderr);
+    }
+
+    let started = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if started.elapsed() >= timeout {
+            kill_tree(child.id());
+            let _ = child.kill();
+            let _ = child.wait();
+            bail!(with_output(format!("{} timed out after {:?}", hook.label(), timeout), &output.text()));
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    };
+
+    if !status.success() {
+        let status = crate::processes::describe_status(status);
+        bail!(with_output(format!("{} exited with {}", hook.label(), status), &output.text()));
+    }
+    Ok(())
+}
+
+fn with_output(message: String, output: &str) -> String {
+    if output.is_empty() {
+        message
+    } else {
+        format!("{}:\n{}", message, output)
+    }
+}
+
+/// Stop a timed out hook and everything it started
+fn kill_tree(pid: u32) {
+    #[cfg(unix)]
+    let _ = Command::new("kill").arg("-KILL").arg(format!("-{}", pid)).status();
+    #[cfg(windows)]
+    let _ = Command::new("taskkill").arg("/T").arg("/F").arg("/PID").arg(pid.to_string()).status();
+}
+
+/// Combined stdout and stderr of a hook, up to `OUTPUT_LIMIT`
+struct Output {
+    text: Mutex<Vec<u8>>,
+    open_pipes: AtomicUsize,
+}
+
+impl Output {
+    fn new() -> Arc<Self> {
+        Arc::new(Output { text: Mutex::new(Vec::new()), open_pipes: AtomicUsize::new(0) })
+    }
+
+    fn capture(self: &Arc<Self>, mut pipe: impl Read + Send + 'static) {
+        self.open_pipes.fetch_add(1, Ordering::SeqCst);
+        let output = Arc::clone(self);
+        std::thread::spawn(move || {
+            let mut buffer = [0u8; 4096];
+            while let Ok(n) = pipe.read(&mut buffer) {
+                if n == 0 {
+                    break;
+                }
+                let mut text = output.text.lock().unwrap();
+                let room = OUTPUT_LIMIT.saturating_sub(text.len());
+                text.extend_from_slice(&buffer[..n.min(room)]);
+            }
+            output.open_pipes.fetch_sub(1, Ordering::SeqCst);
+        });
+    }
+
+    /// The output so far; waits briefly for the pipes to close, since something the hook left
+    /// running in the background may keep them open
+    fn text(&self) -> String {
+        let deadline = Instant::now() + Duration::from_millis(200);
+        while self.open_pipes.load(Ordering::SeqCst) > 0 && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        String::from_utf8_lossy(&self.text.lock().unwrap()).trim().to_string()
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    fn run_hook(command: &str, timeout: Duration) -> Result<()> {
+        run(Hook::PreLaunch, command, &LaunchEnv::default(), &[("JVLAUNCHER_APP_NAME", "Editor".to_string())], timeout)
+    }
+
+    #[test]
+    fn test_hook_success_and_failure() {
+        assert!(run_hook("test \"$JVLAUNCHER_APP_NAME\" = Editor", Duration::from_secs(5)).is_ok());
+
+        let error = run_hook("echo 'mounting share'; sleep 0.1; echo 'permission denied' >&2; exit 2", Duration::from_secs(5))
+            .unwrap_err()
+            .to_string();
+        assert_eq!(error, "Pre-launch hook exited with code 2:\nmounting share\npermission denied");
+
+        assert_eq!(run_hook("exit 1", Duration::from_secs(5)).unwrap_err().to_string(), "Pre-launch hook exited with code 1");
+    }
+
+    #[test]
+    fn test_hook_timeout_stops_what_it_started() {
+        let started = Instant::now();
+        let error = run_hook("echo waiting; sleep 30", Duration::from_millis(300)).unwrap_err().to_string();
+        assert_eq!(error, "Pre-launch hook timed out after 300ms:\nwaiting");
+        // The sleep was killed with the shell, so the pipes closed right away
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_validate_hook_timeout() {
+        assert!(validate(None).is_ok());
+        assert!(validate(Some(1)).is_ok());
+        assert!(validate(Some(MAX_TIMEOUT_SECS)).is_ok());
+        assert!(validate(Some(0)).is_err());
+        assert!(validate(Some(MAX_TIMEOUT_SECS + 1)).is_err());
+    }
+}
@@ -1,6 +1,8 @@
 use anyhow::{anyhow, Result};
 use image::ImageFormat;
 use std::path::Path;
+#[cfg(target_os = "linux")]
+use std::path::PathBuf;
 
 /// Extract icon from a binary and save it to the icons directory
 pub fn extract_icon_from_binary(binary_path: &str, icons_dir: &Path) -> Result<String> {
@@ -173,10 +175,11 @@ fn find_icon_on_linux(icon_name: &str) -> Option<PathBuf> {
     }
 
     // Search in common icon directories
+    let user_icons = format!("{}/.local/share/icons", std::env::var("HOME").unwrap_or_default());
     let icon_dirs = vec![
         "/usr/share/icons",
         "/usr/share/pixmaps",
-        &format!("{}/.local/share/icons", std::env::var("HOME").unwrap_or_default()),
+        user_icons.as_str(),
     ];
 
     let extensions = vec!["png", "svg", "xpm"];
@@ -212,6 +215,30 @@ fn find_icon_on_linux(icon_name: &str) -> Option<PathBuf> {
     None
 }
 
+/// Save an icon named in a .desktop file (a theme icon name or an absolute path)
+pub fn save_desktop_icon(icon: &str, icons_dir: &Path, app_name: &str) -> Result<String> {
+    #[cfg(target_os = "linux")]
+    {
+        let icon_path = find_icon_on_linux(icon)
+            .ok_or_else(|| anyhow!("Could not find icon '{}'", icon))?;
+        match icon_path.extension().and_then(|e| e.to_str()) {
+            Some("png") => save_icon_from_file(&icon_path.to_string_lossy(), icons_dir, app_name),
+            // SVG and XPM icons are copied as they are
+            ext => {
+                let output_path = icons_dir.join(format!("{}.{}", app_name, ext.unwrap_or("png")));
+                std::fs::copy(&icon_path, &output_path)?;
+                Ok(output_path.to_string_lossy().to_string())
+            }
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = (icons_dir, app_name);
+        Err(anyhow!("Desktop entry icons are only supported on Linux: {}", icon))
+    }
+}
+
 /// Save an icon from a user-provided image file
 pub fn save_icon_from_file(source_path: &str, icons_dir: &Path, app_name: &str) -> Result<String> {
 
@@ -407,7 +434,7 @@ fn save_icon_from_clipboard_linux(icons_dir: &Path, app_name: &str) -> Result<St
 
     // Try xclip first
     let output = Command::new("xclip")
-        .args(&["-selection", "clipboard", "-t", "image/png", "-o"])
+        .args(["-selection", "clipboard", "-t", "image/png", "-o"])
         .output();
 
     if let Ok(output) = output {
@@ -421,7 +448,7 @@ fn save_icon_from_clipboard_linux(icons_dir: &Path, app_name: &str) -> Result<St
 
     // Try wl-paste (Wayland)
     let output = Command::new("wl-paste")
-        .args(&["--type", "image/png"])
+        .args(["--type", "image/png"])
         .output();
 
     if let Ok(output) = output {
@@ -1,61 +1,161 @@
 use crate::database::{App, AppType, DbPool};
-use crate::terminal::create_terminal_window;
+use crate::terminal::{create_terminal_window, TerminalCommand};
 use anyhow::{anyhow, Result};
-use std::process::Command;
+use serde::{Deserialize, Serialize};
+use std::process::{Command, Stdio};
 use tauri::{AppHandle, Manager, WebviewUrl, WebviewWindowBuilder};
 
-/// Launch an application based on its type
-pub fn launch_app(app: &App, app_handle: &AppHandle, pool: &DbPool) -> Result<()> {
-    match app.app_type {
-        AppType::App => launch_application(app)?,
-        AppType::Webapp => launch_webapp(app, app_handle, pool)?,
-        AppType::Tui => launch_tui(app, app_handle)?,
-        AppType::Agent => launch_agent(app, app_handle, pool)?,
+/// What started a launch, recorded in the launch history
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LaunchTrigger {
+    /// Clicked or opened with Enter in the launcher grid
+    #[default]
+    Click,
+    /// The app's key in the launcher window
+    AppShortcut,
+    /// The app's system-wide shortcut
+    GlobalShortcut,
+    /// Started on a schedule without user interaction
+    Scheduler,
+    /// "Run again" on a notification
+    Notification,
+    /// Started as part of a workspace
+    Workspace,
+}
+
+impl LaunchTrigger {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LaunchTrigger::Click => "click",
+            LaunchTrigger::AppShortcut => "app_shortcut",
+            LaunchTrigger::GlobalShortcut => "global_shortcut",
+            LaunchTrigger::Scheduler => "scheduler",
+            LaunchTrigger::Notification => "notification",
+            LaunchTrigger::Workspace => "workspace",
+        }
     }
+}
+
+/// Launch an application based on its type, record the launch in the history and report failures
+/// as notifications
+///
+/// An app with a pre-launch hook is started in the background once the hook succeeded, so a slow
+/// hook doesn't hold up the caller; its failures are only reported as notifications.
+pub fn launch_app(app: &App, app_handle: &AppHandle, pool: &DbPool, trigger: LaunchTrigger) -> Result<()> {
+    if app.pre_launch_hook.as_deref().is_none_or(|hook| hook.trim().is_empty()) {
+        return launch_app_blocking(app, app_handle, pool, trigger);
+    }
+    let app = app.clone();
+    let app_handle = app_handle.clone();
+    let pool = pool.clone();
+    std::thread::spawn(move || {
+        let _ = launch_app_blocking(&app, &app_handle, &pool, trigger);
+    });
     Ok(())
 }
 
-/// Launch a native application
-fn launch_application(app: &App) -> Result<()> {
+/// Like `launch_app`, but waits for the pre-launch hook; for callers that are in the background
+/// already and need the app started when this returns
+pub fn launch_app_blocking(app: &App, app_handle: &AppHandle, pool: &DbPool, trigger: LaunchTrigger) -> Result<()> {
+    let result = crate::hooks::run_pre_launch(pool, app).and_then(|()| match app.app_type {
+        AppType::App => launch_application(app, app_handle, pool),
+        AppType::Webapp => launch_webapp(app, app_handle, pool),
+        AppType::Tui => launch_tui(app, app_handle, pool),
+        AppType::Agent => launch_agent(app, app_handle, pool),
+        AppType::Workspace => crate::workspaces::launch(app, app_handle, pool),
+    });
+
+    if let Err(e) = record_launch(pool, app.id, trigger, result.as_ref().err()) {
+        eprintln!("Failed to record launch of {}: {}", app.name, e);
+    }
+    if let Err(error) = &result {
+        let message = format!("{:#}", error);
+        if let Err(e) = crate::notifications::report_launch_failure(pool, app_handle, app.id, &app.name, &message) {
+            eprintln!("Failed to report failed launch of {}: {}", app.name, e);
+        }
+    }
+    result
+}
+
+/// Add a launch to the history and drop launches older than the retention setting
+fn record_launch(pool: &DbPool, app_id: i64, trigger: LaunchTrigger, error: Option<&anyhow::Error>) -> Result<()> {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)?
+        .as_secs() as i64;
+    let error = error.map(|e| format!("{:#}", e));
+    crate::database::record_launch(pool, app_id, trigger.as_str(), now, error.as_deref())?;
+
+    let retention_days = crate::database::get_settings(pool)?.launch_history_retention_days;
+    if retention_days > 0 {
+        crate::database::delete_launch_history_before(pool, now - retention_days * 24 * 60 * 60)?;
+    }
+    Ok(())
+}
+
+/// Launch a native application, or focus it when it's a running single-instance app
+fn launch_application(app: &App, app_handle: &AppHandle, pool: &DbPool) -> Result<()> {
     let binary_path = app.binary_path.as_ref()
         .ok_or_else(|| anyhow!("No binary path specified for application"))?;
 
+    if app.single_instance.unwrap_or(false) && crate::processes::is_running(app.id) {
+        // The app is running either way, so a window that can't be focused isn't a failed launch
+        if let Err(e) = crate::processes::focus(app.id) {
+            eprintln!("Failed to focus {}: {:#}", app.name, e);
+        }
+        return Ok(());
+    }
+
     // Parse CLI parameters
     let args = if let Some(params) = &app.cli_params {
         shell_words::split(params).unwrap_or_default()
     } else {
         vec![]
     };
+    let env = crate::environment::resolve(pool, app)?;
 
     // Launch the application
     #[cfg(target_os = "macos")]
     {
         // On macOS, use 'open' command for .app bundles
         if binary_path.ends_with(".app") {
+            // -W keeps `open` running until the app quits, so it can be tracked
             let mut cmd = Command::new("open");
-            cmd.arg("-a").arg(binary_path);
+            cmd.arg("-W").arg("-a").arg(binary_path);
+
+            // The bundle is started by launchd, so variables are handed over with --env
+            for (name, value) in &env.set {
+                cmd.arg("--env").arg(format!("{}={}", name, value));
+            }
+            env.apply(&mut cmd);
+            cmd.stderr(Stdio::piped());
             
             if !args.is_empty() {
                 cmd.arg("--args");
                 cmd.args(&args);
             }
             
-            cmd.spawn()
+            let child = cmd.spawn()
                 .map_err(|e| anyhow!("Failed to launch application: {}", e))?;
+            crate::processes::track(app_handle, app.id, child, Some(binary_path.clone()));
         } else {
-            Command::new(binary_path)
-                .args(&args)
-                .spawn()
+            let mut cmd = Command::new(binary_path);
+            cmd.args(&args).stderr(Stdio::piped());
+            env.apply(&mut cmd);
+            let child = cmd.spawn()
                 .map_err(|e| anyhow!("Failed to launch application: {}", e))?;
+            crate::processes::track(app_handle, app.id, child, None);
         }
     }
 
     #[cfg(not(target_os = "macos"))]
     {
-        Command::new(binary_path)
-            .args(&args)
-            .spawn()
+        let mut cmd = Command::new(binary_path);
+        cmd.args(&args).stderr(Stdio::piped());
+        env.apply(&mut cmd);
+        let child = cmd.spawn()
             .map_err(|e| anyhow!("Failed to launch application: {}", e))?;
+        crate::processes::track(app_handle, app.id, child, None);
     }
 
     Ok(())
@@ -567,6 +667,8 @@ fn launch_webapp(app: &App, app_handle: &AppHandle, pool: &DbPool) -> Result<()>
                 // Unregister from shortcut manager
                 crate::shortcut_manager::unregister_app_window(app_id);
 
+                crate::hooks::spawn_post_exit(&pool_clone, &app_handle_clone, app_id);
+
                 // Get the window's current position and size
                 if let Ok(position) = window_clone.outer_position() {
                     if let Ok(size) = window_clone.outer_size() {
@@ -600,7 +702,7 @@ fn launch_webapp(app: &App, app_handle: &AppHandle, pool: &DbPool) -> Result<()>
 }
 
 /// Launch a TUI application in a terminal window
-fn launch_tui(app: &App, app_handle: &AppHandle) -> Result<()> {
+fn launch_tui(app: &App, app_handle: &AppHandle, pool: &DbPool) -> Result<()> {
     let binary_path = app.binary_path.as_ref()
         .ok_or_else(|| anyhow!("No binary path specified for TUI application"))?;
 
@@ -633,8 +735,10 @@ fn launch_tui(app: &App, app_handle: &AppHandle) -> Result<()> {
     crate::shortcut_manager::capture_current_app();
 
     // Launch in terminal window
+    let env = crate::environment::resolve(pool, app)?;
     let always_on_top = app.always_on_top.unwrap_or(false);
-    create_terminal_window(app_handle, app.id, &window_label, &app.name, binary_path, &args, always_on_top)?;
+    let command = TerminalCommand { program: binary_path, args: &args, env: &env };
+    create_terminal_window(app_handle, app.id, &window_label, &app.name, command, always_on_top)?;
 
     Ok(())
 }
@@ -648,6 +752,7 @@ fn launch_agent(app: &App, app_handle: &AppHandle, pool: &DbPool) -> Result<()>
     // Execute agent in background (spawn a thread)
     let pool_clone = pool.clone();
     let agent_name = app.name.clone();
+    let app_id = app.id;
     let app_handle_clone = app_handle.clone();
     std::thread::spawn(move || {
         match crate::ai::agent::execute_agent(&pool_clone, &agent_config, Some(&agent_name), &app_handle_clone) {
@@ -658,6 +763,7 @@ fn launch_agent(app: &App, app_handle: &AppHandle, pool: &DbPool) -> Result<()>
                 eprintln!("Agent execution failed: {}", e);
             }
         }
+        crate::hooks::spawn_post_exit(&pool_clone, &app_handle_clone, app_id);
     });
 
     Ok(())
@@ -3,6 +3,9 @@
 
 mod commands;
 mod database;
+mod desktop_entries;
+mod environment;
+mod hooks;
 mod icon_extractor;
 mod icon_fetcher;
 mod launcher;
@@ -11,7 +14,16 @@ mod terminal;
 mod updater;
 mod webapp_auto_close;
 mod ai;
+mod backup;
+mod config;
+mod filters;
 mod scraper;
+mod search;
+mod notifications;
+mod processes;
+mod workspaces;
+mod secrets;
+mod settings;
 
 #[cfg(target_os = "macos")]
 mod macos_delegate;
@@ -36,6 +48,7 @@ fn main() {
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_os::init())
+        .plugin(tauri_plugin_notification::init())
         .plugin(tauri_plugin_updater::Builder::new().build())
         .setup(|app| {
             // Set activation policy to Accessory on macOS
@@ -55,19 +68,81 @@ fn main() {
             std::fs::create_dir_all(&app_data_dir)
                 .expect("Failed to create app data directory");
 
+            // Keep a copy of the data before a newer version migrates the database
+            match backup::backup_before_migration(&app_data_dir) {
+                Ok(Some(path)) => println!("[Backup] Saved pre-migration backup to {}", path.display()),
+                Ok(None) => {}
+                Err(e) => eprintln!("[Backup] Failed to back up before migrating: {:#}", e),
+            }
+
             // Initialize database
             let db_path = app_data_dir.join("launcher.db");
-            let pool = database::init_database(db_path)
-                .expect("Failed to initialize database");
+            let pool = match database::init_database(db_path) {
+                Ok(pool) => pool,
+                Err(e) => {
+                    // Tell the user why the launcher cannot start (e.g. the database is from a newer version)
+                    use tauri_plugin_dialog::{DialogExt, MessageDialogKind};
+                    eprintln!("Failed to initialize database: {:#}", e);
+                    app.dialog()
+                        .message(format!("{:#}", e))
+                        .title("jvlauncher cannot open its database")
+                        .kind(MessageDialogKind::Error)
+                        .blocking_show();
+                    std::process::exit(1);
+                }
+            };
 
             // Store database pool in app state
             app.manage(pool.clone());
+
+            // Unlock encrypted secrets with the OS keyring; otherwise they stay locked until the
+            // user enters their passphrase in Settings
+            if let Err(e) = secrets::init(&pool) {
+                eprintln!("[Secrets] Not unlocked at startup: {:#}", e);
+            }
             
             // Initialize AI queue manager
             let ai_settings = database::get_ai_settings(&pool)
                 .unwrap_or_default();
             ai::queue::init_queue_manager(pool.clone(), ai_settings.max_concurrent_agents);
 
+            // Keep the document search index up to date in the background
+            ai::documents::start_background_indexer(pool.clone());
+
+            // Rotating automatic backups
+            backup::start_scheduler(pool.clone(), app_data_dir.clone());
+
+            // Apply the watched configuration file when it is edited
+            let config_handle = app.handle().clone();
+            let config_pool = pool.clone();
+            config::start_watcher(pool.clone(), app_data_dir.clone(), move |result| match result {
+                Ok(result) => {
+                    let plan = commands::apply_config_side_effects(&config_handle, result);
+                    println!("[Config] Applied {} change(s)", plan.changes.len());
+                    for warning in plan.warnings {
+                        eprintln!("[Config] {}", warning);
+                    }
+                }
+                Err(e) => {
+                    // Show the problem in the notification center, since the file was edited elsewhere
+                    eprintln!("[Config] Not applied: {:#}", e);
+                    let _ = database::create_notification(&config_pool, &database::NewNotification {
+                        text: format!("{:#}", e),
+                        title: Some("Configuration file not applied".to_string()),
+                        url: None,
+                        severity: "normal".to_string(),
+                        app_id: None,
+                        queue_id: None,
+                    });
+                }
+            });
+
+            // Re-surface snoozed notifications and apply notification retention
+            notifications::start_maintenance(pool.clone(), app.handle().clone());
+
+            // Watch the processes of launched native apps
+            processes::start_reaper(app.handle().clone(), pool.clone());
+
             // Initialize terminal state
             app.manage(terminal::TerminalState {
                 windows: std::sync::Arc::new(std::sync::Mutex::new(std::collections::HashMap::new())),
@@ -121,7 +196,10 @@ fn main() {
                 if let Some(app_id) = event.payload().parse::<i64>().ok() {
                     if let Ok(apps) = database::get_all_apps(&pool_clone) {
                         if let Some(app_to_launch) = apps.iter().find(|a| a.id == app_id) {
-                            let _ = launcher::launch_app(app_to_launch, &app_handle_clone, &pool_clone);
+                            // Failures are reported as notifications by the launcher
+                            if let Err(e) = launcher::launch_app(app_to_launch, &app_handle_clone, &pool_clone, launcher::LaunchTrigger::GlobalShortcut) {
+                                eprintln!("Failed to launch {} by shortcut: {:#}", app_to_launch.name, e);
+                            }
                         }
                     }
                 }
@@ -234,7 +312,39 @@ fn main() {
             commands::update_app,
             commands::delete_app,
             commands::reorder_apps,
+            commands::get_pages,
+            commands::create_page,
+            commands::update_page,
+            commands::delete_page,
+            commands::reorder_pages,
+            commands::get_groups,
+            commands::create_group,
+            commands::rename_group,
+            commands::delete_group,
+            commands::move_app,
+            commands::move_group,
+            commands::get_tags,
+            commands::add_app_tag,
+            commands::remove_app_tag,
+            commands::set_app_tags,
+            commands::rename_tag,
+            commands::delete_tag,
+            commands::get_saved_filters,
+            commands::save_saved_filter,
+            commands::delete_saved_filter,
+            commands::get_filter_apps,
             commands::launch,
+            commands::get_running_apps,
+            commands::stop_app,
+            commands::get_workspace_items,
+            commands::set_workspace_items,
+            commands::save_workspace_layout,
+            commands::close_workspace,
+            commands::get_launch_history,
+            commands::get_launch_errors,
+            commands::get_launch_stats,
+            commands::get_launch_histogram,
+            commands::search,
             commands::extract_icon_from_binary,
             commands::save_icon_from_file,
             commands::paste_icon_from_clipboard,
@@ -242,9 +352,12 @@ fn main() {
             commands::paste_icon_from_clipboard_temp,
             commands::finalize_temp_icon,
             commands::cleanup_temp_icon,
+            commands::scan_desktop_entries,
+            commands::import_desktop_entries,
             commands::fetch_web_icon,
             commands::get_settings,
             commands::update_setting,
+            commands::update_settings,
             commands::update_global_shortcut,
             commands::check_shortcut_conflict,
             commands::check_global_shortcut_conflict,
@@ -263,6 +376,21 @@ fn main() {
             commands::webapp_navigate_home,
             commands::get_ai_settings,
             commands::update_ai_setting,
+            commands::create_backup,
+            commands::validate_backup,
+            commands::restore_backup,
+            commands::list_backups,
+            commands::export_config,
+            commands::preview_config_import,
+            commands::apply_config_import,
+            commands::get_config_watch,
+            commands::set_config_watch,
+            commands::get_secrets_status,
+            commands::setup_secrets_passphrase,
+            commands::unlock_secrets,
+            commands::list_secrets,
+            commands::set_secret,
+            commands::delete_secret,
             commands::fetch_models,
             commands::fetch_models_with_endpoint,
             commands::get_models,
@@ -274,11 +402,32 @@ fn main() {
             commands::get_notifications,
             commands::dismiss_notification,
             commands::dismiss_all_notifications,
+            commands::get_notification_sources,
+            commands::snooze_notification,
+            commands::get_notification_deliveries,
+            commands::get_notification_channels,
+            commands::save_notification_channel,
+            commands::delete_notification_channel,
+            commands::test_notification_channel,
             commands::open_ai_queue_window,
             commands::open_queue_detail_window,
             commands::open_notifications_window,
             commands::save_agent_app,
             commands::get_agent_app,
+            commands::dry_run_agent,
+            commands::replay_queue_item,
+            commands::export_agent,
+            commands::import_agent,
+            commands::get_agent_memory,
+            commands::set_agent_memory,
+            commands::delete_agent_memory,
+            commands::clear_agent_memory,
+            commands::get_document_sources,
+            commands::add_document_source,
+            commands::remove_document_source,
+            commands::set_document_source_enabled,
+            commands::reindex_documents,
+            commands::search_documents,
             updater::check_for_updates,
             updater::download_and_install_update,
         ])
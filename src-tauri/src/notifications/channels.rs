@@ -0,0 +1,575 @@
+use super::{AgentNotification, Urgency};
+use crate::database::{DbPool, NotificationChannel};
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Channel kinds
+pub const KIND_WEBHOOK: &str = "webhook";
+pub const KIND_SMTP: &str = "smtp";
+pub const KIND_NTFY: &str = "ntfy";
+pub const KIND_GOTIFY: &str = "gotify";
+
+/// Delays between delivery attempts (one more attempt than there are delays)
+const RETRY_DELAYS: [Duration; 2] = [Duration::from_secs(10), Duration::from_secs(60)];
+
+/// Timeout for a single HTTP or SMTP delivery attempt
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Generic webhook: JSON POST with an optional `{{placeholder}}` body template
+#[derive(Debug, Deserialize)]
+struct WebhookConfig {
+    url: String,
+    #[serde(default)]
+    body_template: Option<String>,
+    #[serde(default)]
+    headers: HashMap<String, String>,
+}
+
+/// SMTP email
+#[derive(Debug, Deserialize)]
+struct SmtpConfig {
+    host: String,
+    #[serde(default)]
+    port: Option<u16>,
+    #[serde(default = "default_smtp_security")]
+    security: String, // "tls", "starttls" or "none"
+    #[serde(default)]
+    username: Option<String>,
+    #[serde(default)]
+    password: Option<String>,
+    from: String,
+    to: String, // comma-separated list of recipients
+    #[serde(default)]
+    subject_template: Option<String>,
+}
+
+fn default_smtp_security() -> String {
+    "starttls".to_string()
+}
+
+/// ntfy push (JSON publish to the server root)
+#[derive(Debug, Deserialize)]
+struct NtfyConfig {
+    server_url: String,
+    topic: String,
+    #[serde(default)]
+    token: Option<String>,
+}
+
+/// Gotify push
+#[derive(Debug, Deserialize)]
+struct GotifyConfig {
+    server_url: String,
+    token: String,
+}
+
+/// Check that a channel's kind is known and its config has the required fields
+pub fn validate(channel: &NotificationChannel) -> Result<()> {
+    if channel.name.trim().is_empty() {
+        return Err(anyhow!("Channel name is required"));
+    }
+
+    match channel.kind.as_str() {
+        KIND_WEBHOOK => parse_config::<WebhookConfig>(channel).map(|_| ()),
+        KIND_SMTP => parse_config::<SmtpConfig>(channel).map(|_| ()),
+        KIND_NTFY => parse_config::<NtfyConfig>(channel).map(|_| ()),
+        KIND_GOTIFY => parse_config::<GotifyConfig>(channel).map(|_| ()),
+        other => Err(anyhow!("Unknown channel kind: {}", other)),
+    }
+}
+
+fn parse_config<T: serde::de::DeserializeOwned>(channel: &NotificationChannel) -> Result<T> {
+    serde_json::from_value(channel.config.clone())
+        .map_err(|e| anyhow!("Invalid {} channel config: {}", channel.kind, e))
+}
+
+/// Deliver a notification to the given channels in the background, retrying failures
+pub fn deliver_to_channels(pool: &DbPool, channel_ids: &[i64], notification: &AgentNotification) {
+    for &channel_id in channel_ids {
+        let channel = match crate::database::get_notification_channel(pool, channel_id) {
+            Ok(Some(channel)) if channel.enabled => channel,
+            Ok(_) => continue,
+            Err(e) => {
+                eprintln!("Failed to load notification channel {}: {}", channel_id, e);
+                continue;
+            }
+        };
+
+        let pool = pool.clone();
+        let notification = notification.clone();
+        std::thread::spawn(move || {
+            deliver_with_retries(&pool, &channel, &notification, &RETRY_DELAYS);
+        });
+    }
+}
+
+/// Try to deliver a notification, logging every attempt; returns whether it succeeded
+fn deliver_with_retries(
+    pool: &DbPool,
+    channel: &NotificationChannel,
+    notification: &AgentNotification,
+    delays: &[Duration],
+) -> bool {
+    let channel = &match with_resolved_secrets(pool, channel) {
+        Ok(resolved) => resolved,
+        Err(e) => {
+            eprintln!("Delivery to channel '{}' failed: {}", channel.name, e);
+            if let Err(e) = crate::database::log_notification_delivery(
+                pool,
+                notification.notification_id,
+                channel,
+                1,
+                Some(&e.to_string()),
+            ) {
+                eprintln!("Failed to log notification delivery: {}", e);
+            }
+            return false;
+        }
+    };
+
+    for attempt in 1..=delays.len() + 1 {
+        let result = send(channel, notification);
+        let error = result
+            .as_ref()
+            .err()
+            .map(|e| crate::secrets::redact(pool, &e.to_string()));
+
+        if let Err(e) = crate::database::log_notification_delivery(
+            pool,
+            notification.notification_id,
+            channel,
+            attempt as i64,
+            error.as_deref(),
+        ) {
+            eprintln!("Failed to log notification delivery: {}", e);
+        }
+
+        match error {
+            None => return true,
+            Some(e) => {
+                eprintln!("Delivery to channel '{}' failed (attempt {}): {}", channel.name, attempt, e);
+                if let Some(delay) = delays.get(attempt - 1) {
+                    std::thread::sleep(*delay);
+                }
+            }
+        }
+    }
+
+    false
+}
+
+/// Copy of a channel with `${secret:NAME}` references in its config replaced by their values
+pub fn with_resolved_secrets(pool: &DbPool, channel: &NotificationChannel) -> Result<NotificationChannel> {
+    let mut resolved = channel.clone();
+    resolved.config = crate::secrets::resolve_json(pool, &channel.config)?;
+    Ok(resolved)
+}
+
+/// Send a notification through a channel once
+pub fn send(channel: &NotificationChannel, notification: &AgentNotification) -> Result<()> {
+    match channel.kind.as_str() {
+        KIND_WEBHOOK => send_webhook(&parse_config(channel)?, notification),
+        KIND_SMTP => send_smtp(&parse_config(channel)?, notification),
+        KIND_NTFY => send_ntfy(&parse_config(channel)?, notification),
+        KIND_GOTIFY => send_gotify(&parse_config(channel)?, notification),
+        other => Err(anyhow!("Unknown channel kind: {}", other)),
+    }
+}
+
+fn http_client() -> Result<reqwest::blocking::Client> {
+    Ok(reqwest::blocking::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()?)
+}
+
+/// Turn a non-success HTTP status into an error that includes the response body
+fn check_response(response: reqwest::blocking::Response) -> Result<()> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(());
+    }
+
+    let body = response.text().unwrap_or_default();
+    Err(anyhow!("HTTP {}: {}", status, body.chars().take(200).collect::<String>()))
+}
+
+fn send_webhook(config: &WebhookConfig, notification: &AgentNotification) -> Result<()> {
+    let body = match &config.body_template {
+        Some(template) if !template.trim().is_empty() => render_template(template, notification, true),
+        _ => serde_json::json!({
+            "title": notification.title,
+            "message": notification.body,
+            "urgency": notification.urgency.as_str(),
+            "url": notification.url,
+            "app_id": notification.app_id,
+            "queue_id": notification.queue_id,
+            "notification_id": notification.notification_id,
+        })
+        .to_string(),
+    };
+
+    let mut request = http_client()?
+        .post(&config.url)
+        .header("Content-Type", "application/json")
+        .body(body);
+    for (name, value) in &config.headers {
+        request = request.header(name.as_str(), value.as_str());
+    }
+
+    check_response(request.send()?)
+}
+
+fn send_ntfy(config: &NtfyConfig, notification: &AgentNotification) -> Result<()> {
+    let priority = match notification.urgency {
+        Urgency::Low => 2,
+        Urgency::Normal => 3,
+        Urgency::Critical => 5,
+    };
+
+    let mut payload = serde_json::json!({
+        "topic": config.topic,
+        "title": notification.title,
+        "message": notification.body,
+        "priority": priority,
+    });
+    if let Some(url) = &notification.url {
+        payload["click"] = serde_json::json!(url);
+    }
+
+    let mut request = http_client()?
+        .post(config.server_url.trim_end_matches('/'))
+        .json(&payload);
+    if let Some(token) = config.token.as_deref().filter(|t| !t.is_empty()) {
+        request = request.bearer_auth(token);
+    }
+
+    check_response(request.send()?)
+}
+
+fn send_gotify(config: &GotifyConfig, notification: &AgentNotification) -> Result<()> {
+    let priority = match notification.urgency {
+        Urgency::Low => 2,
+        Urgency::Normal => 5,
+        Urgency::Critical => 8,
+    };
+
+    let url = format!("{}/message", config.server_url.trim_end_matches('/'));
+    let response = http_client()?
+        .post(url)
+        .header("X-Gotify-Key", &config.token)
+        .json(&serde_json::json!({
+            "title": notification.title,
+            "message": notification.body,
+            "priority": priority,
+        }))
+        .send()?;
+
+    check_response(response)
+}
+
+fn send_smtp(config: &SmtpConfig, notification: &AgentNotification) -> Result<()> {
+    use lettre::message::header::ContentType;
+    use lettre::transport::smtp::authentication::Credentials;
+    use lettre::{Message, SmtpTransport, Transport};
+
+    let subject = match &config.subject_template {
+        Some(template) if !template.trim().is_empty() => render_template(template, notification, false),
+        _ => notification.title.clone(),
+    };
+
+    let mut builder = Message::builder()
+        .from(config.from.parse()?)
+        .subject(subject)
+        .header(ContentType::TEXT_PLAIN);
+    let mut has_recipient = false;
+    for recipient in config.to.split(',').map(str::trim).filter(|r| !r.is_empty()) {
+        builder = builder.to(recipient.parse()?);
+        has_recipient = true;
+    }
+    if !has_recipient {
+        return Err(anyhow!("No recipients configured"));
+    }
+    let email = builder.body(notification.body.clone())?;
+
+    let (mut transport, default_port) = match config.security.as_str() {
+        "tls" => (SmtpTransport::relay(&config.host)?, 465),
+        "none" => (SmtpTransport::builder_dangerous(&config.host), 25),
+        _ => (SmtpTransport::starttls_relay(&config.host)?, 587),
+    };
+    transport = transport
+        .port(config.port.unwrap_or(default_port))
+        .timeout(Some(REQUEST_TIMEOUT));
+    if let Some(username) = config.username.as_deref().filter(|u| !u.is_empty()) {
+        transport = transport.credentials(Credentials::new(
+            username.to_string(),
+            config.password.clone().unwrap_or_default(),
+        ));
+    }
+
+    transport.build().send(&email)?;
+    Ok(())
+}
+
+/// Replace `{{title}}`, `{{message}}`, `{{url}}`, `{{urgency}}`, `{{app_id}}`, `{{queue_id}}`,
+/// `{{notification_id}}` and `{{timestamp}}` placeholders (missing values become empty). With `json_escape` the values
+/// are escaped so they can sit inside JSON string literals.
+fn render_template(template: &str, notification: &AgentNotification, json_escape: bool) -> String {
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+
+    let values = [
+        ("title", notification.title.clone()),
+        ("message", notification.body.clone()),
+        ("url", notification.url.clone().unwrap_or_default()),
+        ("urgency", notification.urgency.as_str().to_string()),
+        ("app_id", notification.app_id.map(|id| id.to_string()).unwrap_or_default()),
+        ("queue_id", notification.queue_id.map(|id| id.to_string()).unwrap_or_default()),
+        ("notification_id", notification.notification_id.to_string()),
+        ("timestamp", timestamp.to_string()),
+    ];
+
+    let mut rendered = template.to_string();
+    for (name, value) in values {
+        let value = if json_escape {
+            let quoted = serde_json::to_string(&value).unwrap_or_default();
+            quoted[1..quoted.len() - 1].to_string()
+        } else {
+            value
+        };
+        rendered = rendered.replace(&format!("{{{{{}}}}}", name), &value);
+    }
+
+    rendered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+
+    fn test_notification() -> AgentNotification {
+        AgentNotification {
+            notification_id: 1,
+            app_id: Some(7),
+            queue_id: Some(42),
+            title: "Price watch".to_string(),
+            body: "Now \"19.99\"\nin stock".to_string(),
+            url: Some("https://shop.example.com/item".to_string()),
+            urgency: Urgency::Critical,
+        }
+    }
+
+    fn test_channel(kind: &str, config: serde_json::Value) -> NotificationChannel {
+        NotificationChannel {
+            id: 0,
+            name: format!("test {}", kind),
+            kind: kind.to_string(),
+            config,
+            enabled: true,
+        }
+    }
+
+    /// Minimal HTTP server answering one request per status, sending back each raw request
+    fn http_stand_in(statuses: Vec<u16>) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let (tx, rx) = mpsc::channel();
+
+        std::thread::spawn(move || {
+            for status in statuses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+
+                let mut request = String::new();
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(value) = line.to_lowercase().strip_prefix("content-length:") {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                    request.push_str(&line);
+                    if line == "\r\n" {
+                        break;
+                    }
+                }
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).unwrap();
+                request.push_str(&String::from_utf8_lossy(&body));
+
+                let mut stream = reader.into_inner();
+                write!(stream, "HTTP/1.1 {} Test\r\nContent-Length: 0\r\nConnection: close\r\n\r\n", status).unwrap();
+                tx.send(request).unwrap();
+            }
+        });
+
+        (url, rx)
+    }
+
+    /// Minimal SMTP server accepting one message and sending back its DATA section
+    fn smtp_stand_in() -> (u16, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let (tx, rx) = mpsc::channel();
+
+        std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut writer = stream.try_clone().unwrap();
+            let mut reader = BufReader::new(stream);
+            writer.write_all(b"220 localhost ESMTP test\r\n").unwrap();
+
+            let mut data = String::new();
+            let mut in_data = false;
+            loop {
+                let mut line = String::new();
+                if reader.read_line(&mut line).unwrap() == 0 {
+                    break;
+                }
+
+                if in_data {
+                    if line == ".\r\n" {
+                        in_data = false;
+                        writer.write_all(b"250 queued\r\n").unwrap();
+                    } else {
+                        data.push_str(&line);
+                    }
+                    continue;
+                }
+
+                let command = line.to_uppercase();
+                if command.starts_with("DATA") {
+                    in_data = true;
+                    writer.write_all(b"354 go ahead\r\n").unwrap();
+                } else if command.starts_with("QUIT") {
+                    writer.write_all(b"221 bye\r\n").unwrap();
+                    break;
+                } else {
+                    writer.write_all(b"250 ok\r\n").unwrap();
+                }
+            }
+
+            tx.send(data).unwrap();
+        });
+
+        (port, rx)
+    }
+
+    #[test]
+    fn test_render_template_escapes_json() {
+        let rendered = render_template(
+            r#"{"text": "{{title}}: {{message}}", "queue": {{queue_id}}}"#,
+            &test_notification(),
+            true,
+        );
+
+        let value: serde_json::Value = serde_json::from_str(&rendered).unwrap();
+        assert_eq!(value["text"], "Price watch: Now \"19.99\"\nin stock");
+        assert_eq!(value["queue"], 42);
+    }
+
+    #[test]
+    fn test_webhook_posts_templated_body() {
+        let (url, rx) = http_stand_in(vec![200]);
+        let channel = test_channel(KIND_WEBHOOK, serde_json::json!({
+            "url": format!("{}/hook", url),
+            "body_template": "{\"content\": \"{{message}}\"}",
+            "headers": { "X-Token": "secret" }
+        }));
+
+        send(&channel, &test_notification()).unwrap();
+
+        let request = rx.recv().unwrap();
+        assert!(request.starts_with("POST /hook HTTP/1.1"));
+        assert!(request.to_lowercase().contains("x-token: secret"));
+        assert!(request.ends_with(r#"{"content": "Now \"19.99\"\nin stock"}"#));
+    }
+
+    #[test]
+    fn test_ntfy_publishes_json_with_priority() {
+        let (url, rx) = http_stand_in(vec![200]);
+        let channel = test_channel(KIND_NTFY, serde_json::json!({
+            "server_url": url,
+            "topic": "alerts",
+            "token": "tk_123"
+        }));
+
+        send(&channel, &test_notification()).unwrap();
+
+        let request = rx.recv().unwrap();
+        assert!(request.to_lowercase().contains("authorization: bearer tk_123"));
+        let body: serde_json::Value = serde_json::from_str(request.split("\r\n\r\n").nth(1).unwrap()).unwrap();
+        assert_eq!(body["topic"], "alerts");
+        assert_eq!(body["title"], "Price watch");
+        assert_eq!(body["priority"], 5);
+        assert_eq!(body["click"], "https://shop.example.com/item");
+    }
+
+    #[test]
+    fn test_smtp_sends_message() {
+        let (port, rx) = smtp_stand_in();
+        let channel = test_channel(KIND_SMTP, serde_json::json!({
+            "host": "127.0.0.1",
+            "port": port,
+            "security": "none",
+            "from": "launcher@example.com",
+            "to": "me@example.com, you@example.com",
+            "subject_template": "[{{urgency}}] {{title}}"
+        }));
+
+        send(&channel, &test_notification()).unwrap();
+
+        let data = rx.recv().unwrap();
+        assert!(data.contains("Subject: [critical] Price watch"));
+        assert!(data.contains("To: me@example.com, you@example.com"));
+        assert!(data.contains("in stock"));
+    }
+
+    #[test]
+    fn test_failed_delivery_is_retried_and_logged() {
+        let pool = crate::database::test_pool();
+        let notification_id = crate::database::create_notification(
+            &pool,
+            &crate::database::NewNotification {
+                text: "Now in stock".to_string(),
+                title: None,
+                url: None,
+                severity: "normal".to_string(),
+                app_id: None,
+                queue_id: None,
+            },
+        )
+        .unwrap();
+
+        let (url, _rx) = http_stand_in(vec![500, 200]);
+        let mut channel = test_channel(KIND_WEBHOOK, serde_json::json!({ "url": url }));
+        channel.id = crate::database::save_notification_channel(&pool, &channel).unwrap();
+
+        let notification = AgentNotification {
+            notification_id,
+            ..test_notification()
+        };
+        assert!(deliver_with_retries(&pool, &channel, &notification, &[Duration::ZERO]));
+
+        let deliveries = crate::database::get_notification_deliveries(&pool, notification_id).unwrap();
+        assert_eq!(deliveries.len(), 2);
+        assert!(!deliveries[0].success);
+        assert!(deliveries[0].error.as_deref().unwrap().contains("500"));
+        assert!(deliveries[1].success);
+        assert_eq!(deliveries[1].attempt, 2);
+    }
+
+    #[test]
+    fn test_validate_rejects_incomplete_config() {
+        let channel = test_channel(KIND_GOTIFY, serde_json::json!({ "server_url": "http://localhost" }));
+        assert!(validate(&channel).is_err());
+
+        let channel = test_channel("pager", serde_json::json!({}));
+        assert!(validate(&channel).is_err());
+    }
+}
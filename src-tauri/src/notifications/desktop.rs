@@ -0,0 +1,120 @@
+use super::AgentNotification;
+use crate::database::DbPool;
+use anyhow::Result;
+use tauri::AppHandle;
+
+/// Action id sent when the notification body is clicked
+#[cfg(target_os = "linux")]
+const ACTION_DEFAULT: &str = "default";
+
+/// Action ids for the notification buttons
+#[cfg(target_os = "linux")]
+const ACTION_OPEN: &str = "open";
+#[cfg(target_os = "linux")]
+const ACTION_RERUN: &str = "rerun";
+#[cfg(target_os = "linux")]
+const ACTION_DISMISS: &str = "dismiss";
+
+/// Show a native desktop notification for an agent result
+///
+/// On Linux this talks to the freedesktop notification daemon over D-Bus, which supports
+/// urgency and action buttons. Other platforms use the Tauri notification plugin, which
+/// shows title and body only.
+#[cfg(target_os = "linux")]
+pub fn show(pool: &DbPool, app_handle: &AppHandle, notification: &AgentNotification) -> Result<()> {
+    use notify_rust::{Notification, Urgency};
+
+    let urgency = match notification.urgency {
+        super::Urgency::Low => Urgency::Low,
+        super::Urgency::Normal => Urgency::Normal,
+        super::Urgency::Critical => Urgency::Critical,
+    };
+
+    let mut builder = Notification::new();
+    builder
+        .appname("jvlauncher")
+        .summary(&notification.title)
+        .body(&notification.body)
+        .urgency(urgency)
+        .action(ACTION_DEFAULT, "Open result")
+        .action(ACTION_OPEN, "Open result");
+    if notification.app_id.is_some() {
+        builder.action(ACTION_RERUN, "Run again");
+    }
+    let handle = builder.action(ACTION_DISMISS, "Dismiss").show()?;
+
+    // Waiting for an action blocks until the notification is closed, so do it off-thread
+    let pool = pool.clone();
+    let app_handle = app_handle.clone();
+    let notification = notification.clone();
+    std::thread::spawn(move || {
+        handle.wait_for_action(|action| {
+            let action = if action == ACTION_DEFAULT { ACTION_OPEN } else { action };
+            handle_action(&pool, &app_handle, &notification, action);
+        });
+    });
+
+    Ok(())
+}
+
+/// Show a native desktop notification for an agent result
+#[cfg(not(target_os = "linux"))]
+pub fn show(_pool: &DbPool, app_handle: &AppHandle, notification: &AgentNotification) -> Result<()> {
+    use tauri_plugin_notification::NotificationExt;
+
+    app_handle
+        .notification()
+        .builder()
+        .title(&notification.title)
+        .body(&notification.body)
+        .show()?;
+
+    Ok(())
+}
+
+/// Run the action chosen on a desktop notification
+#[cfg(target_os = "linux")]
+fn handle_action(pool: &DbPool, app_handle: &AppHandle, notification: &AgentNotification, action: &str) {
+    match action {
+        ACTION_OPEN => match notification.queue_id {
+            Some(queue_id) => {
+                if let Err(e) = crate::commands::open_queue_detail_window(app_handle.clone(), queue_id) {
+                    eprintln!("Failed to open queue item {}: {}", queue_id, e);
+                }
+            }
+            None => {
+                if let Err(e) = crate::commands::open_notifications_window(app_handle.clone()) {
+                    eprintln!("Failed to open notifications window: {}", e);
+                }
+            }
+        },
+        ACTION_RERUN => {
+            let Some(app_id) = notification.app_id else {
+                return;
+            };
+            let apps = match crate::database::get_all_apps(pool) {
+                Ok(apps) => apps,
+                Err(e) => {
+                    eprintln!("Failed to get apps: {}", e);
+                    return;
+                }
+            };
+
+            match apps.iter().find(|a| a.id == app_id) {
+                Some(app) => {
+                    if let Err(e) = crate::launcher::launch_app(app, app_handle, pool, crate::launcher::LaunchTrigger::Notification) {
+                        eprintln!("Failed to run {} again: {}", app.name, e);
+                    }
+                }
+                None => eprintln!("App {} no longer exists", app_id),
+            }
+        }
+        ACTION_DISMISS => {
+            if let Err(e) = crate::database::dismiss_notification(pool, notification.notification_id) {
+                eprintln!("Failed to dismiss notification {}: {}", notification.notification_id, e);
+            }
+        }
+        // Closed or expired without an action
+        _ => {}
+    }
+}
@@ -0,0 +1,223 @@
+pub mod channels;
+pub mod desktop;
+
+use crate::database::{AgentApp, DbPool, Notification};
+use anyhow::Result;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+
+/// Show agent notifications as native OS notifications
+pub const TARGET_OS: &str = "os";
+
+/// Show agent notifications in the notifications window
+pub const TARGET_WINDOW: &str = "window";
+
+/// Show agent notifications both natively and in the notifications window
+pub const TARGET_BOTH: &str = "both";
+
+/// Notification urgency, mapped to the platform's urgency levels where supported
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Urgency {
+    Low,
+    Normal,
+    Critical,
+}
+
+impl Urgency {
+    /// Parse an urgency name, falling back to `Normal` for unknown values
+    pub fn parse(value: &str) -> Self {
+        match value.trim().to_lowercase().as_str() {
+            "low" => Urgency::Low,
+            "critical" | "high" => Urgency::Critical,
+            _ => Urgency::Normal,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Urgency::Low => "low",
+            Urgency::Normal => "normal",
+            Urgency::Critical => "critical",
+        }
+    }
+}
+
+/// How often snoozed notifications are checked
+const SNOOZE_POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// How often old dismissed notifications are cleaned up
+const CLEANUP_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Launch failure output beyond this many characters is only shown in the notifications window
+const LAUNCH_FAILURE_BODY_CHARS: usize = 300;
+
+/// A notification as shown on the desktop and sent to outbound channels
+#[derive(Debug, Clone)]
+pub struct AgentNotification {
+    pub notification_id: i64,
+    pub app_id: Option<i64>,
+    pub queue_id: Option<i64>,
+    pub title: String,
+    pub body: String,
+    pub url: Option<String>,
+    pub urgency: Urgency,
+}
+
+impl AgentNotification {
+    /// Build from a stored notification
+    pub fn from_notification(notification: &Notification) -> Self {
+        AgentNotification {
+            notification_id: notification.id,
+            app_id: notification.app_id,
+            queue_id: notification.queue_id,
+            title: notification
+                .title
+                .clone()
+                .or_else(|| notification.source_name.clone())
+                .unwrap_or_else(|| "jvlauncher".to_string()),
+            body: notification.text.clone(),
+            url: notification.url.clone(),
+            urgency: Urgency::parse(&notification.severity),
+        }
+    }
+}
+
+/// Deliver an agent notification to the targets configured for the agent
+pub fn deliver(pool: &DbPool, app_handle: &AppHandle, agent: &AgentApp, notification: &AgentNotification) -> Result<()> {
+    show_locally(pool, app_handle, &agent.notification_target, notification);
+
+    if !agent.notification_channel_ids.is_empty() {
+        channels::deliver_to_channels(pool, &agent.notification_channel_ids, notification);
+    }
+
+    Ok(())
+}
+
+/// Report an app that failed to launch or exited right after starting, with the error and captured
+/// output kept in the notifications window and a shortened version shown on the desktop
+pub fn report_launch_failure(pool: &DbPool, app_handle: &AppHandle, app_id: i64, app_name: &str, error: &str) -> Result<()> {
+    report_app_failure(pool, app_handle, app_id, format!("{} failed to start", app_name), error)
+}
+
+/// Report a post-exit hook that failed or timed out, the same way as a failed launch
+pub fn report_hook_failure(pool: &DbPool, app_handle: &AppHandle, app_id: i64, app_name: &str, error: &str) -> Result<()> {
+    report_app_failure(pool, app_handle, app_id, format!("Post-exit hook of {} failed", app_name), error)
+}
+
+fn report_app_failure(pool: &DbPool, app_handle: &AppHandle, app_id: i64, title: String, error: &str) -> Result<()> {
+    let notification_id = crate::database::create_notification(
+        pool,
+        &crate::database::NewNotification {
+            text: error.to_string(),
+            title: Some(title.clone()),
+            url: None,
+            severity: Urgency::Critical.as_str().to_string(),
+            app_id: Some(app_id),
+            queue_id: None,
+        },
+    )?;
+
+    let mut body: String = error.chars().take(LAUNCH_FAILURE_BODY_CHARS).collect();
+    if body.len() < error.len() {
+        body.push('…');
+    }
+    let notification = AgentNotification {
+        notification_id,
+        app_id: Some(app_id),
+        queue_id: None,
+        title,
+        body,
+        url: None,
+        urgency: Urgency::Critical,
+    };
+    show_locally(pool, app_handle, TARGET_OS, &notification);
+    Ok(())
+}
+
+/// Show a notification on the desktop and/or in the notifications window
+fn show_locally(pool: &DbPool, app_handle: &AppHandle, target: &str, notification: &AgentNotification) {
+    let _ = app_handle.emit("notifications-changed", notification.notification_id);
+
+    if target == TARGET_OS || target == TARGET_BOTH {
+        if let Err(e) = desktop::show(pool, app_handle, notification) {
+            eprintln!("Failed to show desktop notification: {}", e);
+            // Make sure the notification is not lost when the OS notification fails
+            if target == TARGET_OS {
+                open_window(app_handle);
+            }
+        }
+    }
+
+    if target == TARGET_WINDOW || target == TARGET_BOTH {
+        open_window(app_handle);
+    }
+}
+
+/// Open the notifications window
+fn open_window(app_handle: &AppHandle) {
+    if let Err(e) = crate::commands::open_notifications_window(app_handle.clone()) {
+        eprintln!("Failed to open notifications window: {}", e);
+    }
+}
+
+/// Start the background thread that re-surfaces snoozed notifications and
+/// removes dismissed notifications older than the retention setting
+pub fn start_maintenance(pool: DbPool, app_handle: AppHandle) {
+    std::thread::spawn(move || {
+        let mut last_cleanup: Option<Instant> = None;
+
+        loop {
+            if last_cleanup.is_none_or(|t| t.elapsed() >= CLEANUP_INTERVAL) {
+                if let Err(e) = cleanup_dismissed(&pool) {
+                    eprintln!("Failed to clean up notifications: {}", e);
+                }
+                last_cleanup = Some(Instant::now());
+            }
+
+            if let Err(e) = resurface_snoozed(&pool, &app_handle) {
+                eprintln!("Failed to re-surface snoozed notifications: {}", e);
+            }
+
+            std::thread::sleep(SNOOZE_POLL_INTERVAL);
+        }
+    });
+}
+
+/// Show notifications whose snooze has expired again
+fn resurface_snoozed(pool: &DbPool, app_handle: &AppHandle) -> Result<()> {
+    for notification in crate::database::take_due_snoozed_notifications(pool, now())? {
+        let target = match notification.app_id {
+            Some(app_id) => crate::database::get_agent_app(pool, app_id)?
+                .map(|agent| agent.notification_target)
+                .unwrap_or_else(|| TARGET_WINDOW.to_string()),
+            None => TARGET_WINDOW.to_string(),
+        };
+
+        show_locally(pool, app_handle, &target, &AgentNotification::from_notification(&notification));
+    }
+
+    Ok(())
+}
+
+/// Delete dismissed notifications older than the configured retention
+fn cleanup_dismissed(pool: &DbPool) -> Result<()> {
+    let retention_days = crate::database::get_settings(pool)?.notification_retention_days;
+    if retention_days <= 0 {
+        return Ok(());
+    }
+
+    let cutoff = now() - retention_days * 24 * 60 * 60;
+    let deleted = crate::database::delete_dismissed_notifications_before(pool, cutoff)?;
+    if deleted > 0 {
+        println!("[Notifications] Removed {} dismissed notifications older than {} days", deleted, retention_days);
+    }
+
+    Ok(())
+}
+
+fn now() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64
+}
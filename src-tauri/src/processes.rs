@@ -0,0 +1,645 @@
+use crate::database::{DbPool, WindowState};
+use anyhow::{anyhow, bail, Result};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+use std::process::{Child, Command, ExitStatus};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+
+/// How often exited processes are collected
+const REAP_INTERVAL: Duration = Duration::from_millis(500);
+
+/// A process that fails within this long after starting counts as a failed launch
+const EARLY_EXIT_WINDOW: Duration = Duration::from_secs(5);
+
+/// How much of a process's stderr is kept for failure reports
+const STDERR_LIMIT: usize = 8 * 1024;
+
+/// Native app processes started by the launcher
+static PROCESSES: Mutex<ProcessTable> = Mutex::new(ProcessTable::new());
+
+/// Sent as `app-running-changed` when an app's first process starts or its last one exits
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RunningChanged {
+    pub app_id: i64,
+    pub running: bool,
+}
+
+/// A process that exited with an error shortly after it was started
+#[derive(Debug, Clone, PartialEq)]
+pub struct EarlyExit {
+    pub app_id: i64,
+    /// e.g. `code 1` or `signal 11`
+    pub status: String,
+    pub after: Duration,
+    /// The start of the process's stderr
+    pub stderr: String,
+}
+
+impl EarlyExit {
+    /// Text for the launch history and the failure notification
+    pub fn message(&self) -> String {
+        let mut message = format!("Exited with {} after {:.1}s", self.status, self.after.as_secs_f64());
+        if !self.stderr.is_empty() {
+            message.push_str(":\n");
+            message.push_str(&self.stderr);
+        }
+        message
+    }
+}
+
+/// The start of a process's stderr, collected by a reader thread
+#[derive(Default)]
+struct StderrCapture {
+    output: Mutex<Vec<u8>>,
+    closed: AtomicBool,
+}
+
+impl StderrCapture {
+    /// Read until the pipe closes, keeping the first `STDERR_LIMIT` bytes and passing everything
+    /// through to our own stderr so the output isn't lost
+    fn start(mut stderr: impl Read + Send + 'static) -> Arc<Self> {
+        let capture = Arc::new(StderrCapture::default());
+        let reader = capture.clone();
+        std::thread::spawn(move || {
+            let mut buf = [0u8; 4096];
+            while let Ok(n) = stderr.read(&mut buf) {
+                if n == 0 {
+                    break;
+                }
+                let _ = std::io::stderr().write_all(&buf[..n]);
+                let mut output = reader.output.lock().unwrap();
+                let keep = n.min(STDERR_LIMIT.saturating_sub(output.len()));
+                output.extend_from_slice(&buf[..keep]);
+            }
+            reader.closed.store(true, Ordering::SeqCst);
+        });
+        capture
+    }
+
+    /// The captured text, waiting up to `wait` for the rest of the output of an exited process
+    fn text(&self, wait: Duration) -> String {
+        let deadline = Instant::now() + wait;
+        while !self.closed.load(Ordering::SeqCst) && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        String::from_utf8_lossy(&self.output.lock().unwrap()).trim().to_string()
+    }
+}
+
+/// A process started for an app
+struct Tracked {
+    child: Child,
+    /// macOS .app bundle started with `open -W`; the tracked child is `open`, not the app itself
+    bundle: Option<String>,
+    started_at: Instant,
+    stderr: Option<Arc<StderrCapture>>,
+    /// Asked to quit, so exiting with an error isn't a failed launch
+    stopping: bool,
+}
+
+/// What a reaper pass found
+#[derive(Debug, Default, PartialEq)]
+struct Reaped {
+    /// Apps that have no running process left
+    stopped: Vec<i64>,
+    /// Processes that failed right after starting
+    failed: Vec<EarlyExit>,
+}
+
+/// Processes by app id
+struct ProcessTable {
+    apps: BTreeMap<i64, Vec<Tracked>>,
+}
+
+impl ProcessTable {
+    const fn new() -> Self {
+        ProcessTable { apps: BTreeMap::new() }
+    }
+
+    /// Add a process and start capturing its stderr when it's piped; returns true when it's the app's
+    /// first running one
+    fn insert(&mut self, app_id: i64, mut child: Child, bundle: Option<String>) -> bool {
+        let stderr = child.stderr.take().map(StderrCapture::start);
+        let processes = self.apps.entry(app_id).or_default();
+        processes.push(Tracked { child, bundle, started_at: Instant::now(), stderr, stopping: false });
+        processes.len() == 1
+    }
+
+    fn pids(&self, app_id: i64) -> Vec<u32> {
+        self.apps.get(&app_id).map_or_else(Vec::new, |p| p.iter().map(|t| t.child.id()).collect())
+    }
+
+    fn running_apps(&self) -> Vec<i64> {
+        self.apps.keys().copied().collect()
+    }
+
+    /// Drop the processes that have exited and note the ones that failed right after starting
+    fn reap(&mut self) -> Reaped {
+        let mut reaped = Reaped::default();
+        self.apps.retain(|app_id, processes| {
+            processes.retain_mut(|tracked| {
+                let status = match tracked.child.try_wait() {
+                    Ok(None) => return true,
+                    Ok(Some(status)) => status,
+                    Err(_) => return false,
+                };
+                let after = tracked.started_at.elapsed();
+                if !status.success() && !tracked.stopping && after < EARLY_EXIT_WINDOW {
+                    reaped.failed.push(EarlyExit {
+                        app_id: *app_id,
+                        status: describe_status(status),
+                        after,
+                        stderr: tracked.stderr.as_ref().map_or_else(String::new, |s| s.text(Duration::from_millis(200))),
+                    });
+                }
+                false
+            });
+            if processes.is_empty() {
+                reaped.stopped.push(*app_id);
+            }
+            !processes.is_empty()
+        });
+        reaped
+    }
+
+    /// Ask an app's processes to quit, or kill them when `force` is set
+    fn stop(&mut self, app_id: i64, force: bool) -> Result<()> {
+        let processes = self
+            .apps
+            .get_mut(&app_id)
+            .ok_or_else(|| anyhow!("App {} is not running", app_id))?;
+        for tracked in processes.iter_mut() {
+            tracked.stopping = true;
+            match (&tracked.bundle, force) {
+                (Some(bundle), _) => quit_bundle(bundle, force)?,
+                (None, true) => tracked.child.kill()?,
+                (None, false) => terminate(tracked.child.id())?,
+            }
+        }
+        Ok(())
+    }
+
+    fn bundle(&self, app_id: i64) -> Option<String> {
+        self.apps.get(&app_id)?.iter().find_map(|t| t.bundle.clone())
+    }
+}
+
+/// Keep track of a process started for an app; spawn it with a piped stderr to have early failures
+/// reported with their output
+pub fn track(app_handle: &AppHandle, app_id: i64, child: Child, bundle: Option<String>) {
+    let first = PROCESSES.lock().unwrap().insert(app_id, child, bundle);
+    if first {
+        let _ = app_handle.emit("app-running-changed", RunningChanged { app_id, running: true });
+    }
+}
+
+pub fn is_running(app_id: i64) -> bool {
+    !PROCESSES.lock().unwrap().pids(app_id).is_empty()
+}
+
+/// Ids of the apps with a running process
+pub fn running_apps() -> Vec<i64> {
+    PROCESSES.lock().unwrap().running_apps()
+}
+
+/// Quit an app's processes, or kill them when `force` is set
+pub fn stop(app_id: i64, force: bool) -> Result<()> {
+    PROCESSES.lock().unwrap().stop(app_id, force)
+}
+
+/// Bring the window of an app's running process to the front
+pub fn focus(app_id: i64) -> Result<()> {
+    let (pids, bundle) = snapshot(app_id)?;
+    if let Some(bundle) = bundle {
+        // Opening a running bundle again activates it
+        Command::new("open").arg("-a").arg(&bundle).status()?;
+        return Ok(());
+    }
+    focus_pids(&pids)
+}
+
+/// Position and size of the window of an app's running process
+pub fn window_geometry(app_id: i64) -> Result<WindowState> {
+    match snapshot(app_id)? {
+        (_, Some(bundle)) => bundle_window_geometry(&bundle),
+        (pids, None) => window_geometry_pids(&pids),
+    }
+}
+
+/// Move and resize the window of an app's running process
+pub fn place(app_id: i64, state: &WindowState) -> Result<()> {
+    match snapshot(app_id)? {
+        (_, Some(bundle)) => place_bundle_window(&bundle, state),
+        (pids, None) => place_pids(&pids, state),
+    }
+}
+
+/// Pids and bundle of an app's running processes
+fn snapshot(app_id: i64) -> Result<(Vec<u32>, Option<String>)> {
+    let table = PROCESSES.lock().unwrap();
+    let pids = table.pids(app_id);
+    if pids.is_empty() {
+        bail!("App {} is not running", app_id);
+    }
+    Ok((pids, table.bundle(app_id)))
+}
+
+/// Collect exited processes in the background, announce apps that stopped running, run their
+/// post-exit hooks and report processes that failed right after starting
+pub fn start_reaper(app_handle: AppHandle, pool: DbPool) {
+    std::thread::spawn(move || loop {
+        let reaped = PROCESSES.lock().unwrap().reap();
+        for exit in reaped.failed {
+            if let Err(e) = report_early_exit(&pool, &app_handle, &exit) {
+                eprintln!("Failed to report exit of app {}: {}", exit.app_id, e);
+            }
+        }
+        for app_id in reaped.stopped {
+            let _ = app_handle.emit("app-running-changed", RunningChanged { app_id, running: false });
+            crate::hooks::spawn_post_exit(&pool, &app_handle, app_id);
+        }
+        std::thread::sleep(REAP_INTERVAL);
+    });
+}
+
+/// Mark the app's launch as failed and tell the user
+fn report_early_exit(pool: &DbPool, app_handle: &AppHandle, exit: &EarlyExit) -> Result<()> {
+    let message = exit.message();
+    crate::database::set_last_launch_error(pool, exit.app_id, &message)?;
+    let apps = crate::database::get_all_apps(pool)?;
+    let name = apps.iter().find(|a| a.id == exit.app_id).map_or("App", |a| a.name.as_str());
+    crate::notifications::report_launch_failure(pool, app_handle, exit.app_id, name, &message)
+}
+
+/// "code N", or "signal N" for a process killed by a signal
+pub fn describe_status(status: ExitStatus) -> String {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+        if let Some(signal) = status.signal() {
+            return format!("signal {}", signal);
+        }
+    }
+    match status.code() {
+        Some(code) => format!("code {}", code),
+        None => status.to_string(),
+    }
+}
+
+/// Ask a process to quit the way a window manager would
+fn terminate(pid: u32) -> Result<()> {
+    #[cfg(unix)]
+    let status = Command::new("kill").arg("-TERM").arg(pid.to_string()).status()?;
+    // Without /F, taskkill asks the process's windows to close
+    #[cfg(windows)]
+    let status = Command::new("taskkill").arg("/PID").arg(pid.to_string()).status()?;
+
+    if !status.success() {
+        bail!("Failed to stop process {}", pid);
+    }
+    Ok(())
+}
+
+fn quit_bundle(bundle: &str, force: bool) -> Result<()> {
+    let status = if force {
+        Command::new("pkill").arg("-9").arg("-f").arg(format!("{}/Contents/MacOS/", bundle)).status()?
+    } else {
+        let script = format!("tell application (POSIX file \"{}\" as text) to quit", bundle);
+        Command::new("osascript").arg("-e").arg(script).status()?
+    };
+    if !status.success() {
+        bail!("Failed to quit {}", bundle);
+    }
+    Ok(())
+}
+
+/// Run a script against the front window of a process through System Events, returning its output
+fn system_events(process: &str, script: &str) -> Result<String> {
+    let script = format!(
+        "tell application \"System Events\"\ntell ({})\n{}\nend tell\nend tell",
+        process, script
+    );
+    let output = Command::new("osascript").arg("-e").arg(script).output()?;
+    if !output.status.success() {
+        bail!("{}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// The System Events process of a bundle, which runs under the bundle's name
+fn bundle_process(bundle: &str) -> String {
+    let name = std::path::Path::new(bundle).file_stem().map_or_else(|| bundle.into(), |n| n.to_string_lossy());
+    format!("first process whose name is \"{}\"", name)
+}
+
+fn bundle_window_geometry(bundle: &str) -> Result<WindowState> {
+    parse_geometry(&system_events(&bundle_process(bundle), "get {position, size} of window 1")?)
+}
+
+fn place_bundle_window(bundle: &str, state: &WindowState) -> Result<()> {
+    system_events(&bundle_process(bundle), &place_script(state))?;
+    Ok(())
+}
+
+fn place_script(state: &WindowState) -> String {
+    format!(
+        "set position of window 1 to {{{}, {}}}\nset size of window 1 to {{{}, {}}}",
+        state.x, state.y, state.width, state.height
+    )
+}
+
+/// Parse the `x, y, width, height` list System Events returns for `{position, size}`
+fn parse_geometry(output: &str) -> Result<WindowState> {
+    let values = output
+        .split(',')
+        .map(|v| v.trim().parse::<i32>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| anyhow!("Unexpected window geometry '{}'", output))?;
+    match values[..] {
+        [x, y, width, height] => Ok(WindowState { x, y, width, height }),
+        _ => bail!("Unexpected window geometry '{}'", output),
+    }
+}
+
+/// An X11 connection and a top-level window of one of the processes (or their children)
+#[cfg(target_os = "linux")]
+struct X11Window {
+    conn: x11rb::rust_connection::RustConnection,
+    root: u32,
+    window: u32,
+}
+
+#[cfg(target_os = "linux")]
+impl X11Window {
+    fn find(pids: &[u32]) -> Result<Self> {
+        use x11rb::connection::Connection;
+        use x11rb::protocol::xproto::{AtomEnum, ConnectionExt};
+
+        let (conn, screen) = x11rb::connect(None).map_err(|e| anyhow!("Managing windows needs X11: {}", e))?;
+        let root = conn.setup().roots[screen].root;
+        let client_list = atom(&conn, "_NET_CLIENT_LIST")?;
+        let wm_pid = atom(&conn, "_NET_WM_PID")?;
+
+        // Launchers and wrapper scripts often leave the window to a child process
+        let pids = with_descendants(pids);
+        let windows = conn.get_property(false, root, client_list, AtomEnum::WINDOW, 0, u32::MAX)?.reply()?;
+        let window = windows
+            .value32()
+            .into_iter()
+            .flatten()
+            .find(|&window| {
+                conn.get_property(false, window, wm_pid, AtomEnum::CARDINAL, 0, 1)
+                    .ok()
+                    .and_then(|cookie| cookie.reply().ok())
+                    .and_then(|reply| reply.value32().and_then(|mut values| values.next()))
+                    .is_some_and(|pid| pids.contains(&pid))
+            })
+            .ok_or_else(|| anyhow!("No window found for the running app"))?;
+        Ok(X11Window { conn, root, window })
+    }
+
+    /// Ask the window manager to act on the window, the way a pager would
+    fn send(&self, message_type: &str, data: [u32; 5]) -> Result<()> {
+        use x11rb::connection::Connection;
+        use x11rb::protocol::xproto::{ClientMessageEvent, ConnectionExt, EventMask};
+
+        let event = ClientMessageEvent::new(32, self.window, atom(&self.conn, message_type)?, data);
+        let mask = EventMask::SUBSTRUCTURE_REDIRECT | EventMask::SUBSTRUCTURE_NOTIFY;
+        self.conn.send_event(false, self.root, mask, event)?;
+        self.conn.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn atom(conn: &impl x11rb::protocol::xproto::ConnectionExt, name: &str) -> Result<u32> {
+    Ok(conn.intern_atom(false, name.as_bytes())?.reply()?.atom)
+}
+
+/// Activate the window through `_NET_ACTIVE_WINDOW`
+#[cfg(target_os = "linux")]
+fn focus_pids(pids: &[u32]) -> Result<()> {
+    // Source 2 marks the request as coming from a pager, which window managers honour
+    X11Window::find(pids)?.send("_NET_ACTIVE_WINDOW", [2, 0, 0, 0, 0])
+}
+
+#[cfg(target_os = "linux")]
+fn window_geometry_pids(pids: &[u32]) -> Result<WindowState> {
+    use x11rb::protocol::xproto::ConnectionExt;
+
+    let found = X11Window::find(pids)?;
+    let geometry = found.conn.get_geometry(found.window)?.reply()?;
+    let origin = found.conn.translate_coordinates(found.window, found.root, 0, 0)?.reply()?;
+    Ok(WindowState {
+        x: origin.dst_x as i32,
+        y: origin.dst_y as i32,
+        width: geometry.width as i32,
+        height: geometry.height as i32,
+    })
+}
+
+/// Move and resize through `_NET_MOVERESIZE_WINDOW`; static gravity places the client area itself,
+/// matching the geometry read back above
+#[cfg(target_os = "linux")]
+fn place_pids(pids: &[u32], state: &WindowState) -> Result<()> {
+    const STATIC_GRAVITY: u32 = 10;
+    let flags = STATIC_GRAVITY | 0b1111 << 8 | 2 << 12;
+    X11Window::find(pids)?.send(
+        "_NET_MOVERESIZE_WINDOW",
+        [flags, state.x as u32, state.y as u32, state.width as u32, state.height as u32],
+    )
+}
+
+/// The processes and all their descendants, from /proc
+#[cfg(target_os = "linux")]
+fn with_descendants(pids: &[u32]) -> Vec<u32> {
+    let parents: Vec<(u32, u32)> = std::fs::read_dir("/proc")
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let pid = entry.file_name().to_str()?.parse().ok()?;
+            let stat = std::fs::read_to_string(entry.path().join("stat")).ok()?;
+            // The command name in parentheses may contain spaces; the parent pid is the second field after it
+            let ppid = stat.rsplit_once(')')?.1.split_whitespace().nth(1)?.parse().ok()?;
+            Some((pid, ppid))
+        })
+        .collect();
+
+    let mut all = pids.to_vec();
+    let mut i = 0;
+    while i < all.len() {
+        let parent = all[i];
+        all.extend(parents.iter().filter(|(_, ppid)| *ppid == parent).map(|(pid, _)| *pid));
+        i += 1;
+    }
+    all
+}
+
+#[cfg(target_os = "macos")]
+fn focus_pids(pids: &[u32]) -> Result<()> {
+    system_events(&pid_process(pids), "set frontmost to true")?;
+    Ok(())
+}
+
+#[cfg(target_os = "macos")]
+fn window_geometry_pids(pids: &[u32]) -> Result<WindowState> {
+    parse_geometry(&system_events(&pid_process(pids), "get {position, size} of window 1")?)
+}
+
+#[cfg(target_os = "macos")]
+fn place_pids(pids: &[u32], state: &WindowState) -> Result<()> {
+    system_events(&pid_process(pids), &place_script(state))?;
+    Ok(())
+}
+
+#[cfg(target_os = "macos")]
+fn pid_process(pids: &[u32]) -> String {
+    format!("first process whose unix id is {}", pids[0])
+}
+
+/// A visible top-level window of one of the processes
+#[cfg(target_os = "windows")]
+fn find_window(pids: &[u32]) -> Result<winapi::shared::windef::HWND> {
+    use winapi::shared::minwindef::{BOOL, DWORD, FALSE, LPARAM, TRUE};
+    use winapi::shared::windef::HWND;
+    use winapi::um::winuser::{EnumWindows, GetWindowThreadProcessId, IsWindowVisible};
+
+    struct Search<'a> {
+        pids: &'a [u32],
+        found: HWND,
+    }
+
+    unsafe extern "system" fn visit(hwnd: HWND, lparam: LPARAM) -> BOOL {
+        let search = &mut *(lparam as *mut Search);
+        let mut pid: DWORD = 0;
+        GetWindowThreadProcessId(hwnd, &mut pid);
+        if IsWindowVisible(hwnd) != 0 && search.pids.contains(&pid) {
+            search.found = hwnd;
+            return FALSE;
+        }
+        TRUE
+    }
+
+    let mut search = Search { pids, found: std::ptr::null_mut() };
+    unsafe {
+        EnumWindows(Some(visit), &mut search as *mut Search as LPARAM);
+    }
+    if search.found.is_null() {
+        bail!("No window found for the running app");
+    }
+    Ok(search.found)
+}
+
+#[cfg(target_os = "windows")]
+fn focus_pids(pids: &[u32]) -> Result<()> {
+    use winapi::um::winuser::{IsIconic, SetForegroundWindow, ShowWindow, SW_RESTORE};
+
+    let window = find_window(pids)?;
+    unsafe {
+        if IsIconic(window) != 0 {
+            ShowWindow(window, SW_RESTORE);
+        }
+        SetForegroundWindow(window);
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "windows")]
+fn window_geometry_pids(pids: &[u32]) -> Result<WindowState> {
+    use winapi::shared::windef::RECT;
+    use winapi::um::winuser::GetWindowRect;
+
+    let window = find_window(pids)?;
+    let mut rect = RECT { left: 0, top: 0, right: 0, bottom: 0 };
+    if unsafe { GetWindowRect(window, &mut rect) } == 0 {
+        bail!("Failed to get the window position");
+    }
+    Ok(WindowState {
+        x: rect.left,
+        y: rect.top,
+        width: rect.right - rect.left,
+        height: rect.bottom - rect.top,
+    })
+}
+
+#[cfg(target_os = "windows")]
+fn place_pids(pids: &[u32], state: &WindowState) -> Result<()> {
+    use winapi::shared::minwindef::TRUE;
+    use winapi::um::winuser::{IsIconic, MoveWindow, ShowWindow, SW_RESTORE};
+
+    let window = find_window(pids)?;
+    unsafe {
+        if IsIconic(window) != 0 {
+            ShowWindow(window, SW_RESTORE);
+        }
+        if MoveWindow(window, state.x, state.y, state.width, state.height, TRUE) == 0 {
+            bail!("Failed to move the window");
+        }
+    }
+    Ok(())
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    use std::process::Stdio;
+
+    #[test]
+    fn test_process_table_tracks_and_reaps() {
+        let mut table = ProcessTable::new();
+        let sleeper = Command::new("sleep").arg("30").spawn().unwrap();
+        let quick = Command::new("true").spawn().unwrap();
+
+        assert!(table.insert(1, sleeper, None));
+        assert!(!table.insert(1, Command::new("true").spawn().unwrap(), None));
+        assert!(table.insert(2, quick, None));
+        assert_eq!(table.running_apps(), vec![1, 2]);
+
+        // Exited processes are dropped; app 1 still has its sleeper
+        std::thread::sleep(Duration::from_millis(200));
+        assert_eq!(table.reap(), Reaped { stopped: vec![2], failed: vec![] });
+        assert_eq!(table.running_apps(), vec![1]);
+        assert_eq!(table.pids(1).len(), 1);
+
+        // Killing an app on request isn't a failed launch
+        assert!(table.stop(2, false).is_err());
+        table.stop(1, true).unwrap();
+        std::thread::sleep(Duration::from_millis(200));
+        assert_eq!(table.reap(), Reaped { stopped: vec![1], failed: vec![] });
+        assert!(table.running_apps().is_empty());
+    }
+
+    #[test]
+    fn test_parse_geometry() {
+        assert_eq!(parse_geometry("10, -20, 800, 600\n").unwrap(), WindowState { x: 10, y: -20, width: 800, height: 600 });
+        assert!(parse_geometry("10, 20").is_err());
+        assert!(parse_geometry("missing value").is_err());
+    }
+
+    #[test]
+    fn test_early_exit_is_reported_with_stderr() {
+        let mut table = ProcessTable::new();
+        let crash = Command::new("sh")
+            .arg("-c")
+            .arg("echo 'missing library' >&2; exit 3")
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+        table.insert(1, crash, None);
+
+        std::thread::sleep(Duration::from_millis(200));
+        let reaped = table.reap();
+        assert_eq!(reaped.stopped, vec![1]);
+        assert_eq!(reaped.failed.len(), 1);
+        let exit = &reaped.failed[0];
+        assert_eq!((exit.app_id, exit.status.as_str(), exit.stderr.as_str()), (1, "code 3", "missing library"));
+        assert!(exit.message().starts_with("Exited with code 3 after "));
+        assert!(exit.message().ends_with(":\nmissing library"));
+    }
+}
@@ -0,0 +1,315 @@
+use crate::database::{App, AppType, DbPool};
+use anyhow::Result;
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Built-in actions that can be searched alongside apps, as (id, name, keywords)
+pub const ACTIONS: &[(&str, &str, &str)] = &[
+    ("settings", "Settings", "preferences options configure"),
+    ("add_app", "Add Application", "new create app webapp agent"),
+    ("notifications", "Notifications", "alerts messages inbox"),
+    ("ai_queue", "AI Queue", "agents runs jobs history"),
+    ("quit", "Quit jvlauncher", "exit close"),
+];
+
+/// How much a match in each field counts, relative to a match in the name
+const NAME_WEIGHT: f64 = 1.0;
+const TAG_WEIGHT: f64 = 0.8;
+const URL_WEIGHT: f64 = 0.6;
+const PATH_WEIGHT: f64 = 0.5;
+const PROMPT_WEIGHT: f64 = 0.4;
+const KEYWORD_WEIGHT: f64 = 0.5;
+
+/// Points added per unit of log frecency, so often used apps win between similar matches
+const FRECENCY_WEIGHT: f64 = 6.0;
+
+/// A search hit
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchResult {
+    pub kind: String, // "app" or "action"
+    pub app_id: Option<i64>,
+    pub action: Option<String>,
+    pub name: String,
+    /// Field that matched: "name", "tag", "url", "binary_path", "prompt" or "keywords"
+    pub matched_field: String,
+    /// Character positions in `name` that matched, for highlighting
+    pub positions: Vec<usize>,
+    pub score: f64,
+}
+
+/// Score how well `query` fuzzy-matches `text`
+///
+/// Every query character has to appear in order. Consecutive characters, matches at the start
+/// of words and a match at the very start score higher; gaps cost a little. Returns the score
+/// and the matched character positions, or `None` if the text doesn't match.
+pub fn fuzzy_match(query: &str, text: &str) -> Option<(f64, Vec<usize>)> {
+    let query: Vec<char> = query.chars().filter(|c| !c.is_whitespace()).flat_map(char::to_lowercase).collect();
+    if query.is_empty() {
+        return None;
+    }
+    let chars: Vec<char> = text.chars().collect();
+    let lower: Vec<char> = chars.iter().map(|c| c.to_lowercase().next().unwrap_or(*c)).collect();
+
+    // Greedy matching can miss a better later alignment, so try every start of the first character
+    let mut best: Option<(f64, Vec<usize>)> = None;
+    for start in (0..lower.len()).filter(|&i| lower[i] == query[0]) {
+        let Some(positions) = match_from(&query, &lower, start) else {
+            break;
+        };
+        let score = score_positions(&chars, &positions, query.len());
+        if best.as_ref().is_none_or(|(s, _)| score > *s) {
+            best = Some((score, positions));
+        }
+    }
+    best
+}
+
+fn match_from(query: &[char], lower: &[char], start: usize) -> Option<Vec<usize>> {
+    let mut positions = Vec::with_capacity(query.len());
+    let mut next = start;
+    for &c in query {
+        let found = (next..lower.len()).find(|&i| lower[i] == c)?;
+        positions.push(found);
+        next = found + 1;
+    }
+    Some(positions)
+}
+
+fn score_positions(chars: &[char], positions: &[usize], query_len: usize) -> f64 {
+    let mut score = 0.0;
+    for (i, &pos) in positions.iter().enumerate() {
+        score += 1.0;
+        let word_start = pos == 0 || !chars[pos - 1].is_alphanumeric() || (chars[pos].is_uppercase() && chars[pos - 1].is_lowercase());
+        if word_start {
+            score += 2.0;
+        }
+        if i > 0 {
+            let gap = pos - positions[i - 1] - 1;
+            if gap == 0 {
+                score += 1.5;
+            } else {
+                score -= (gap as f64).min(5.0) * 0.2;
+            }
+        }
+    }
+    if positions.first() == Some(&0) {
+        score += 3.0;
+    }
+    if query_len == chars.len() {
+        score += 5.0;
+    }
+    // Normalize so long queries don't dwarf the frecency boost
+    score / query_len as f64 * 10.0
+}
+
+/// Search apps and built-in actions, best matches first
+pub fn search(pool: &DbPool, query: &str, limit: usize, now: i64) -> Result<Vec<SearchResult>> {
+    let apps = crate::database::get_all_apps(pool)?;
+    let prompts = crate::database::get_agent_prompts(pool)?;
+    let frecency = crate::database::get_launch_frecency(pool, now)?;
+    Ok(rank(query, &apps, &prompts, &frecency, limit))
+}
+
+/// Rank apps and actions against a query
+pub fn rank(
+    query: &str,
+    apps: &[App],
+    prompts: &HashMap<i64, String>,
+    frecency: &HashMap<i64, f64>,
+    limit: usize,
+) -> Vec<SearchResult> {
+    let mut results = Vec::new();
+
+    for app in apps {
+        let mut fields: Vec<(&str, f64, &str)> = vec![("name", NAME_WEIGHT, app.name.as_str())];
+        for tag in &app.tags {
+            fields.push(("tag", TAG_WEIGHT, tag.as_str()));
+        }
+        if let Some(url) = app.url.as_deref().filter(|_| app.app_type == AppType::Webapp) {
+            fields.push(("url", URL_WEIGHT, url.split("://").nth(1).unwrap_or(url)));
+        }
+        if let Some(path) = app.binary_path.as_deref() {
+            fields.push(("binary_path", PATH_WEIGHT, path.rsplit(['/', '\\']).next().unwrap_or(path)));
+        }
+        if let Some(prompt) = prompts.get(&app.id) {
+            fields.push(("prompt", PROMPT_WEIGHT, prompt.as_str()));
+        }
+
+        if let Some(mut result) = best_field(query, &app.name, &fields) {
+            let launches = frecency.get(&app.id).copied().unwrap_or(0.0);
+            result.score += (1.0 + launches).ln() * FRECENCY_WEIGHT;
+            result.kind = "app".to_string();
+            result.app_id = Some(app.id);
+            results.push(result);
+        }
+    }
+
+    for (id, name, keywords) in ACTIONS {
+        let fields = [("name", NAME_WEIGHT, *name), ("keywords", KEYWORD_WEIGHT, *keywords)];
+        if let Some(mut result) = best_field(query, name, &fields) {
+            result.kind = "action".to_string();
+            result.action = Some(id.to_string());
+            results.push(result);
+        }
+    }
+
+    results.sort_by(|a, b| b.score.total_cmp(&a.score).then_with(|| a.name.cmp(&b.name)));
+    results.truncate(limit);
+    results
+}
+
+/// The best-scoring field of an entry, as a result without kind or id
+fn best_field(query: &str, name: &str, fields: &[(&str, f64, &str)]) -> Option<SearchResult> {
+    fields
+        .iter()
+        .filter_map(|(field, weight, text)| {
+            fuzzy_match(query, text).map(|(score, positions)| (*field, score * weight, positions))
+        })
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(field, score, positions)| SearchResult {
+            kind: String::new(),
+            app_id: None,
+            action: None,
+            name: name.to_string(),
+            matched_field: field.to_string(),
+            positions: if field == "name" { positions } else { Vec::new() },
+            score,
+        })
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn app(id: i64, app_type: AppType, name: &str) -> App {
+        App {
+            id,
+            app_type,
+            name: name.to_string(),
+            icon_path: None,
+            position: id as i32,
+            shortcut: None,
+            global_shortcut: None,
+            binary_path: None,
+            cli_params: None,
+            url: None,
+            session_data_path: None,
+            show_nav_controls: None,
+            open_external_links: None,
+            enable_oauth: None,
+            auto_close_timeout: None,
+            always_on_top: None,
+            hide_on_shortcut: None,
+            single_instance: None,
+            working_dir: None,
+            env: Vec::new(),
+            env_file: None,
+            pre_launch_hook: None,
+            post_exit_hook: None,
+            hook_timeout: None,
+            page_id: 1,
+            group_id: None,
+            tags: Vec::new(),
+        }
+    }
+
+    fn names(results: &[SearchResult]) -> Vec<&str> {
+        results.iter().map(|r| r.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_fuzzy_match() {
+        assert!(fuzzy_match("fx", "Firefox").is_some());
+        assert!(fuzzy_match("xf", "Firefox").is_none());
+        assert!(fuzzy_match("", "Firefox").is_none());
+        assert_eq!(fuzzy_match("ff", "Firefox").unwrap().1, vec![0, 4]);
+
+        // Prefixes and word starts beat scattered matches
+        let prefix = fuzzy_match("term", "Terminal").unwrap().0;
+        let scattered = fuzzy_match("term", "The Energy Report Manager").unwrap().0;
+        assert!(prefix > scattered);
+        let word_start = fuzzy_match("vc", "Visual Code").unwrap().0;
+        let inner = fuzzy_match("vc", "Devices").unwrap().0;
+        assert!(word_start > inner);
+
+        // The best alignment is found even when the first occurrence is a poor one
+        assert_eq!(fuzzy_match("code", "decode Code").unwrap().1, vec![7, 8, 9, 10]);
+    }
+
+    #[test]
+    fn test_rank_fields_and_frecency() {
+        let mut mail = app(1, AppType::Webapp, "Mail");
+        mail.url = Some("https://fastmail.com".to_string());
+        let mut editor = app(2, AppType::App, "Editor");
+        editor.binary_path = Some("/usr/bin/gedit".to_string());
+        let mut watcher = app(3, AppType::Agent, "Price watcher");
+        watcher.tags = vec!["shopping".to_string()];
+        let mailer = app(4, AppType::App, "Mailspring");
+        let apps = vec![mail, editor, watcher, mailer];
+        let prompts = HashMap::from([(3, "Check the shop for a new graphics card".to_string())]);
+
+        let results = rank("gedit", &apps, &prompts, &HashMap::new(), 10);
+        assert_eq!(names(&results), vec!["Editor"]);
+        assert_eq!(results[0].matched_field, "binary_path");
+
+        let results = rank("graphics", &apps, &prompts, &HashMap::new(), 10);
+        assert_eq!((results[0].app_id, results[0].matched_field.as_str()), (Some(3), "prompt"));
+
+        assert_eq!(rank("fastmail", &apps, &prompts, &HashMap::new(), 10)[0].app_id, Some(1));
+
+        let results = rank("shopping", &apps, &prompts, &HashMap::new(), 10);
+        assert_eq!((results[0].app_id, results[0].matched_field.as_str()), (Some(3), "tag"));
+
+        // Without usage the closer match wins; heavy use of a similar match reorders them
+        let results = rank("mail", &apps, &prompts, &HashMap::new(), 10);
+        assert_eq!(names(&results)[..2], ["Mail", "Mailspring"]);
+        let frecency = HashMap::from([(4, 800.0)]);
+        let results = rank("mail", &apps, &prompts, &frecency, 10);
+        assert_eq!(names(&results)[..2], ["Mailspring", "Mail"]);
+
+        // Actions are searchable by name and keywords
+        let results = rank("prefs", &apps, &prompts, &HashMap::new(), 10);
+        assert_eq!(results[0].action.as_deref(), Some("settings"));
+
+        assert_eq!(rank("m", &apps, &prompts, &HashMap::new(), 2).len(), 2);
+    }
+
+    #[test]
+    fn test_search_uses_launch_history() {
+        let pool = crate::database::test_pool();
+        let new_app = |name: &str| crate::database::NewApp {
+            app_type: AppType::App,
+            name: name.to_string(),
+            icon_path: None,
+            shortcut: None,
+            global_shortcut: None,
+            binary_path: Some("/bin/true".to_string()),
+            cli_params: None,
+            url: None,
+            show_nav_controls: None,
+            open_external_links: None,
+            enable_oauth: None,
+            auto_close_timeout: None,
+            always_on_top: None,
+            hide_on_shortcut: None,
+            single_instance: None,
+            working_dir: None,
+            env: Vec::new(),
+            env_file: None,
+            pre_launch_hook: None,
+            post_exit_hook: None,
+            hook_timeout: None,
+        };
+        crate::database::create_app(&pool, new_app("Notes"), None).unwrap();
+        let notion = crate::database::create_app(&pool, new_app("Notion"), None).unwrap();
+
+        let now = 1_000_000_000;
+        for i in 0..20 {
+            crate::database::record_launch(&pool, notion, "click", now - i * 60, None).unwrap();
+        }
+
+        let results = search(&pool, "no", 10, now).unwrap();
+        assert_eq!(results[0].app_id, Some(notion));
+    }
+}
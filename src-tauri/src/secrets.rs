@@ -0,0 +1,395 @@
+use crate::database::DbPool;
+use anyhow::{anyhow, Context, Result};
+use base64::Engine;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use regex::Regex;
+use serde::Serialize;
+use std::sync::Mutex;
+
+/// The master key lives in the OS secret service (Keychain, Credential Manager, Secret Service)
+pub const BACKEND_KEYRING: &str = "keyring";
+
+/// The master key is derived from a passphrase the user enters each session
+pub const BACKEND_PASSPHRASE: &str = "passphrase";
+
+/// Settings that may hold credentials and are moved into secrets when stored in plain text
+const SECRET_SETTINGS: &[&str] = &["ai_api_key"];
+
+/// OS keyring entry holding the master key
+const KEYRING_SERVICE: &str = "jvlauncher";
+const KEYRING_USER: &str = "secrets-master-key";
+
+/// Known plaintext encrypted with a passphrase-derived key to check the passphrase
+const VERIFIER_PLAINTEXT: &str = "jvlauncher-secrets";
+
+/// Secrets shorter than this are not redacted, as they would match ordinary text
+const MIN_REDACT_LEN: usize = 4;
+
+pub type MasterKey = [u8; 32];
+
+/// The unlocked master key for this session
+static MASTER_KEY: Mutex<Option<MasterKey>> = Mutex::new(None);
+
+/// Whether secrets are set up and available
+#[derive(Debug, Clone, Serialize)]
+pub struct SecretsStatus {
+    /// `None` until secrets have been set up
+    pub backend: Option<String>,
+    pub unlocked: bool,
+}
+
+/// A stored secret, without its value
+#[derive(Debug, Clone, Serialize)]
+pub struct SecretInfo {
+    pub name: String,
+    pub updated_at: i64,
+}
+
+/// Unlock secrets at startup using the OS keyring when possible
+///
+/// When no backend is configured yet the keyring is tried first; if it is unavailable the
+/// user has to choose a passphrase before secrets can be used.
+pub fn init(pool: &DbPool) -> Result<()> {
+    let backend = crate::database::get_setting(pool, "secrets_backend")?;
+    if backend.as_deref().is_some_and(|b| b != BACKEND_KEYRING) {
+        return Ok(());
+    }
+
+    let key = load_or_create_keyring_key().context("OS keyring unavailable")?;
+    if backend.is_none() {
+        crate::database::update_setting(pool, "secrets_backend", BACKEND_KEYRING)?;
+    }
+    unlock_with_key(pool, key)
+}
+
+/// Set up passphrase-based secrets (used when the OS keyring is unavailable)
+pub fn setup_passphrase(pool: &DbPool, passphrase: &str) -> Result<()> {
+    if crate::database::get_setting(pool, "secrets_backend")?.is_some() {
+        return Err(anyhow!("Secrets are already set up"));
+    }
+    if passphrase.chars().count() < 8 {
+        return Err(anyhow!("The passphrase must be at least 8 characters"));
+    }
+
+    let salt = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let key = derive_key(passphrase, &salt)?;
+    let (nonce, ciphertext) = encrypt(&key, VERIFIER_PLAINTEXT)?;
+
+    let encoder = &base64::engine::general_purpose::STANDARD;
+    crate::database::update_setting(pool, "secrets_salt", &encoder.encode(salt))?;
+    crate::database::update_setting(pool, "secrets_verifier", &encoder.encode([nonce, ciphertext].concat()))?;
+    crate::database::update_setting(pool, "secrets_backend", BACKEND_PASSPHRASE)?;
+
+    unlock_with_key(pool, key)
+}
+
+/// Unlock passphrase-based secrets for this session
+pub fn unlock(pool: &DbPool, passphrase: &str) -> Result<()> {
+    let decoder = &base64::engine::general_purpose::STANDARD;
+    let salt = crate::database::get_setting(pool, "secrets_salt")?
+        .ok_or_else(|| anyhow!("No secrets passphrase has been set up"))?;
+    let verifier = crate::database::get_setting(pool, "secrets_verifier")?
+        .ok_or_else(|| anyhow!("No secrets passphrase has been set up"))?;
+
+    let key = derive_key(passphrase, &decoder.decode(salt)?)?;
+    let verifier = decoder.decode(verifier)?;
+    if verifier.len() < 24 {
+        return Err(anyhow!("Stored passphrase check is corrupt"));
+    }
+    let (nonce, ciphertext) = verifier.split_at(24);
+    match decrypt(&key, nonce, ciphertext) {
+        Ok(text) if text == VERIFIER_PLAINTEXT => unlock_with_key(pool, key),
+        _ => Err(anyhow!("Wrong passphrase")),
+    }
+}
+
+/// Get the current secrets status
+pub fn status(pool: &DbPool) -> Result<SecretsStatus> {
+    Ok(SecretsStatus {
+        backend: crate::database::get_setting(pool, "secrets_backend")?,
+        unlocked: current_key().is_some(),
+    })
+}
+
+/// List stored secrets without their values
+pub fn list(pool: &DbPool) -> Result<Vec<SecretInfo>> {
+    Ok(crate::database::get_secrets(pool)?
+        .into_iter()
+        .map(|s| SecretInfo { name: s.name, updated_at: s.updated_at })
+        .collect())
+}
+
+/// Encrypt and store a secret
+pub fn set(pool: &DbPool, name: &str, value: &str) -> Result<()> {
+    store(pool, &require_key()?, name, value)
+}
+
+/// Delete a secret
+pub fn delete(pool: &DbPool, name: &str) -> Result<()> {
+    crate::database::delete_secret(pool, name)
+}
+
+/// The reference to a named secret, as written in settings and agent configs
+pub fn reference(name: &str) -> String {
+    format!("${{secret:{}}}", name)
+}
+
+/// Whether a value contains secret references
+pub fn has_references(text: &str) -> bool {
+    text.contains("${secret:")
+}
+
+/// Replace `${secret:NAME}` references with the secret values
+pub fn resolve(pool: &DbPool, text: &str) -> Result<String> {
+    if !has_references(text) {
+        return Ok(text.to_string());
+    }
+    resolve_with(pool, &require_key()?, text)
+}
+
+/// Resolve secret references in every string of a JSON value, such as a channel config
+pub fn resolve_json(pool: &DbPool, value: &serde_json::Value) -> Result<serde_json::Value> {
+    use serde_json::Value;
+
+    Ok(match value {
+        Value::String(text) => Value::String(resolve(pool, text)?),
+        Value::Array(items) => Value::Array(items.iter().map(|v| resolve_json(pool, v)).collect::<Result<_>>()?),
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(k, v)| Ok((k.clone(), resolve_json(pool, v)?)))
+                .collect::<Result<_>>()?,
+        ),
+        other => other.clone(),
+    })
+}
+
+/// Replace any secret values in text with their references, for transcripts, logs and exports
+///
+/// Text is returned unchanged while secrets are locked, as no secret can have been resolved.
+pub fn redact(pool: &DbPool, text: &str) -> String {
+    let Some(key) = current_key() else {
+        return text.to_string();
+    };
+
+    match redact_with(pool, &key, text) {
+        Ok(redacted) => redacted,
+        Err(e) => {
+            eprintln!("[Secrets] Failed to redact text: {}", e);
+            text.to_string()
+        }
+    }
+}
+
+/// Value to store for a setting: credentials are saved as a secret and replaced by a reference
+///
+/// While secrets are locked the value is stored as given and moved into secrets on unlock.
+pub fn protect_setting(pool: &DbPool, setting: &str, value: &str) -> Result<String> {
+    let Some(key) = current_key() else {
+        return Ok(value.to_string());
+    };
+    if !SECRET_SETTINGS.contains(&setting) || value.is_empty() || has_references(value) {
+        return Ok(value.to_string());
+    }
+
+    store(pool, &key, setting, value)?;
+    Ok(reference(setting))
+}
+
+/// Move credentials stored in plain text settings into secrets
+fn migrate_plaintext_settings(pool: &DbPool, key: &MasterKey) -> Result<()> {
+    for setting in SECRET_SETTINGS {
+        let Some(value) = crate::database::get_setting(pool, setting)? else {
+            continue;
+        };
+        if value.is_empty() || has_references(&value) {
+            continue;
+        }
+
+        store(pool, key, setting, &value)?;
+        crate::database::update_setting(pool, setting, &reference(setting))?;
+        println!("[Secrets] Moved setting '{}' into encrypted secrets", setting);
+    }
+
+    Ok(())
+}
+
+fn unlock_with_key(pool: &DbPool, key: MasterKey) -> Result<()> {
+    *MASTER_KEY.lock().unwrap() = Some(key);
+    migrate_plaintext_settings(pool, &key)
+}
+
+fn current_key() -> Option<MasterKey> {
+    *MASTER_KEY.lock().unwrap()
+}
+
+fn require_key() -> Result<MasterKey> {
+    current_key().ok_or_else(|| anyhow!("Secrets are locked; unlock them in Settings"))
+}
+
+fn validate_name(name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && name.len() <= 64
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'));
+    if valid {
+        Ok(())
+    } else {
+        Err(anyhow!("Secret names may only contain letters, digits, '_', '-' and '.'"))
+    }
+}
+
+fn store(pool: &DbPool, key: &MasterKey, name: &str, value: &str) -> Result<()> {
+    validate_name(name)?;
+    let (nonce, ciphertext) = encrypt(key, value)?;
+    crate::database::save_secret(pool, name, &nonce, &ciphertext)
+}
+
+fn resolve_with(pool: &DbPool, key: &MasterKey, text: &str) -> Result<String> {
+    let pattern = Regex::new(r"\$\{secret:([A-Za-z0-9_.-]+)\}").unwrap();
+    let mut resolved = String::with_capacity(text.len());
+    let mut last = 0;
+
+    for caps in pattern.captures_iter(text) {
+        let whole = caps.get(0).unwrap();
+        let name = &caps[1];
+        let secret = crate::database::get_secret(pool, name)?
+            .ok_or_else(|| anyhow!("Unknown secret '{}'", name))?;
+
+        resolved.push_str(&text[last..whole.start()]);
+        resolved.push_str(&decrypt(key, &secret.nonce, &secret.ciphertext)?);
+        last = whole.end();
+    }
+
+    resolved.push_str(&text[last..]);
+    Ok(resolved)
+}
+
+fn redact_with(pool: &DbPool, key: &MasterKey, text: &str) -> Result<String> {
+    let mut values = Vec::new();
+    for secret in crate::database::get_secrets(pool)? {
+        let value = decrypt(key, &secret.nonce, &secret.ciphertext)?;
+        if value.chars().count() >= MIN_REDACT_LEN {
+            values.push((value, reference(&secret.name)));
+        }
+    }
+
+    // Longest first, so a secret containing another is replaced whole
+    values.sort_by_key(|(value, _)| std::cmp::Reverse(value.len()));
+
+    let mut redacted = text.to_string();
+    for (value, reference) in values {
+        if redacted.contains(&value) {
+            redacted = redacted.replace(&value, &reference);
+        }
+    }
+    Ok(redacted)
+}
+
+fn encrypt(key: &MasterKey, plaintext: &str) -> Result<(Vec<u8>, Vec<u8>)> {
+    let cipher = XChaCha20Poly1305::new(key.into());
+    let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext.as_bytes())
+        .map_err(|_| anyhow!("Failed to encrypt secret"))?;
+    Ok((nonce.to_vec(), ciphertext))
+}
+
+fn decrypt(key: &MasterKey, nonce: &[u8], ciphertext: &[u8]) -> Result<String> {
+    if nonce.len() != 24 {
+        return Err(anyhow!("Invalid secret nonce"));
+    }
+    let cipher = XChaCha20Poly1305::new(key.into());
+    let plaintext = cipher
+        .decrypt(XNonce::from_slice(nonce), ciphertext)
+        .map_err(|_| anyhow!("Failed to decrypt secret (wrong key or corrupted data)"))?;
+    Ok(String::from_utf8(plaintext)?)
+}
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> Result<MasterKey> {
+    let mut key = [0u8; 32];
+    argon2::Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| anyhow!("Failed to derive key from passphrase: {}", e))?;
+    Ok(key)
+}
+
+fn load_or_create_keyring_key() -> Result<MasterKey> {
+    let entry = keyring::Entry::new(KEYRING_SERVICE, KEYRING_USER)?;
+    let engine = &base64::engine::general_purpose::STANDARD;
+
+    match entry.get_password() {
+        Ok(encoded) => {
+            let bytes = engine.decode(encoded)?;
+            bytes
+                .try_into()
+                .map_err(|_| anyhow!("Master key in the OS keyring has the wrong length"))
+        }
+        Err(keyring::Error::NoEntry) => {
+            let key: MasterKey = XChaCha20Poly1305::generate_key(&mut OsRng).into();
+            entry.set_password(&engine.encode(key))?;
+            Ok(key)
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_pool;
+
+    const KEY: MasterKey = [7u8; 32];
+
+    #[test]
+    fn test_encrypt_roundtrip_and_wrong_key() {
+        let (nonce, ciphertext) = encrypt(&KEY, "hunter22").unwrap();
+        assert_ne!(ciphertext, b"hunter22");
+        assert_eq!(decrypt(&KEY, &nonce, &ciphertext).unwrap(), "hunter22");
+        assert!(decrypt(&[8u8; 32], &nonce, &ciphertext).is_err());
+    }
+
+    #[test]
+    fn test_resolve_and_redact() {
+        let pool = test_pool();
+        store(&pool, &KEY, "shop_token", "tk-12345").unwrap();
+        store(&pool, &KEY, "pin", "42").unwrap();
+
+        let resolved = resolve_with(&pool, &KEY, "curl -H 'X-Token: ${secret:shop_token}' ${secret:pin}").unwrap();
+        assert_eq!(resolved, "curl -H 'X-Token: tk-12345' 42");
+        assert!(resolve_with(&pool, &KEY, "${secret:missing}").unwrap_err().to_string().contains("Unknown secret"));
+
+        // Short values are left alone so ordinary text is not mangled
+        let redacted = redact_with(&pool, &KEY, "Response used tk-12345 and 42").unwrap();
+        assert_eq!(redacted, "Response used ${secret:shop_token} and 42");
+    }
+
+    #[test]
+    fn test_passphrase_key_derivation() {
+        let salt = [1u8; 24];
+        let key = derive_key("correct horse", &salt).unwrap();
+        assert_eq!(key, derive_key("correct horse", &salt).unwrap());
+        assert_ne!(key, derive_key("wrong horse", &salt).unwrap());
+    }
+
+    #[test]
+    fn test_migrate_plaintext_settings() {
+        let pool = test_pool();
+        crate::database::update_setting(&pool, "ai_api_key", "sk-live-abc").unwrap();
+
+        migrate_plaintext_settings(&pool, &KEY).unwrap();
+        assert_eq!(crate::database::get_setting(&pool, "ai_api_key").unwrap().as_deref(), Some("${secret:ai_api_key}"));
+        assert_eq!(resolve_with(&pool, &KEY, "${secret:ai_api_key}").unwrap(), "sk-live-abc");
+
+        // Already migrated settings are left alone
+        migrate_plaintext_settings(&pool, &KEY).unwrap();
+        assert_eq!(crate::database::get_secrets(&pool).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_secret_names() {
+        assert!(validate_name("smtp.password").is_ok());
+        assert!(validate_name("").is_err());
+        assert!(validate_name("has space").is_err());
+        assert!(validate_name("${x}").is_err());
+    }
+}
@@ -0,0 +1,299 @@
+use crate::database::{AISettings, DbPool, Settings};
+use anyhow::{anyhow, bail, Result};
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Type and allowed values of a setting
+#[derive(Debug, Clone, Copy)]
+pub enum SettingKind {
+    Bool,
+    Integer { min: i64, max: i64 },
+    Text,
+    Choice(&'static [&'static str]),
+    /// Global shortcut syntax, e.g. `CommandOrControl+Shift+Space`
+    Shortcut,
+    /// http(s) URL
+    Url,
+}
+
+/// What has to happen outside the database when a setting changes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SideEffect {
+    /// Re-register the launcher's global shortcut
+    GlobalShortcut,
+    /// Resize the main window to the grid
+    GridSize,
+    /// Turn starting at login on or off
+    Autostart,
+    /// Change how many agents the queue runs at once
+    MaxConcurrentAgents,
+}
+
+/// A setting that can be changed from the UI or a configuration file
+#[derive(Debug, Clone, Copy)]
+pub struct SettingDef {
+    pub key: &'static str,
+    pub kind: SettingKind,
+    pub side_effect: Option<SideEffect>,
+    /// Stored as an encrypted secret when secrets are unlocked
+    pub secret: bool,
+}
+
+const fn setting(key: &'static str, kind: SettingKind) -> SettingDef {
+    SettingDef {
+        key,
+        kind,
+        side_effect: None,
+        secret: false,
+    }
+}
+
+const fn with_effect(key: &'static str, kind: SettingKind, side_effect: SideEffect) -> SettingDef {
+    SettingDef {
+        key,
+        kind,
+        side_effect: Some(side_effect),
+        secret: false,
+    }
+}
+
+/// Every user-facing setting. Internal keys (secrets, config watch) are not listed and
+/// can't be written through the settings commands.
+pub const REGISTRY: &[SettingDef] = &[
+    with_effect("global_shortcut", SettingKind::Shortcut, SideEffect::GlobalShortcut),
+    setting("theme", SettingKind::Choice(&["system", "light", "dark"])),
+    with_effect("grid_cols", SettingKind::Integer { min: 1, max: 20 }, SideEffect::GridSize),
+    with_effect("grid_rows", SettingKind::Integer { min: 1, max: 20 }, SideEffect::GridSize),
+    with_effect("start_at_login", SettingKind::Bool, SideEffect::Autostart),
+    setting("terminal_command", SettingKind::Text),
+    setting("hide_app_names", SettingKind::Bool),
+    // The agent tab bar changes the window height
+    with_effect("separate_agent_apps", SettingKind::Bool, SideEffect::GridSize),
+    setting("notification_retention_days", SettingKind::Integer { min: 0, max: 3650 }),
+    setting("launch_history_retention_days", SettingKind::Integer { min: 0, max: 3650 }),
+    setting("backup_interval_hours", SettingKind::Integer { min: 0, max: 8760 }),
+    setting("backup_keep", SettingKind::Integer { min: 1, max: 1000 }),
+    setting("backup_include_sessions", SettingKind::Bool),
+    setting("ai_enabled", SettingKind::Bool),
+    setting("ai_endpoint_url", SettingKind::Url),
+    SettingDef {
+        key: "ai_api_key",
+        kind: SettingKind::Text,
+        side_effect: None,
+        secret: true,
+    },
+    setting("ai_default_model", SettingKind::Text),
+    setting("ai_embedding_model", SettingKind::Text),
+    with_effect(
+        "ai_max_concurrent_agents",
+        SettingKind::Integer { min: 1, max: 32 },
+        SideEffect::MaxConcurrentAgents,
+    ),
+];
+
+/// A setting that was written, for running side effects and notifying windows
+#[derive(Debug, Clone, Serialize)]
+pub struct SettingChange {
+    pub key: String,
+    /// Previous stored value, used to undo a change whose side effect failed
+    #[serde(skip)]
+    pub old: Option<String>,
+    pub side_effect: Option<SideEffect>,
+}
+
+/// Look up a setting, rejecting unknown keys
+pub fn find(key: &str) -> Result<&'static SettingDef> {
+    REGISTRY
+        .iter()
+        .find(|def| def.key == key)
+        .ok_or_else(|| anyhow!("Unknown setting '{}'", key))
+}
+
+/// Default value of a setting as stored, taken from `Settings::default()` and `AISettings::default()`
+pub fn default_value(key: &str) -> Option<String> {
+    let (defaults, field) = match key.strip_prefix("ai_") {
+        Some(field) => (serde_json::to_value(AISettings::default()).ok()?, field),
+        None => (serde_json::to_value(Settings::default()).ok()?, key),
+    };
+
+    match defaults.get(field)? {
+        serde_json::Value::Null => None,
+        serde_json::Value::String(s) => Some(s.clone()),
+        other => Some(other.to_string()),
+    }
+}
+
+/// Check a value for a setting, returning it in its stored form
+///
+/// Errors describe the problem without the key so callers can say where the value came from.
+pub fn validate(key: &str, value: &str) -> Result<String> {
+    let def = find(key)?;
+    let value = value.trim();
+
+    match def.kind {
+        SettingKind::Bool => match value {
+            "true" | "false" => Ok(value.to_string()),
+            _ => bail!("must be true or false"),
+        },
+        SettingKind::Integer { min, max } => match value.parse::<i64>() {
+            Ok(n) if (min..=max).contains(&n) => Ok(n.to_string()),
+            _ => bail!("must be a whole number between {} and {}", min, max),
+        },
+        SettingKind::Text => Ok(value.to_string()),
+        SettingKind::Choice(choices) if choices.contains(&value) => Ok(value.to_string()),
+        SettingKind::Choice(choices) => bail!("must be one of {}", choices.join(", ")),
+        SettingKind::Shortcut => {
+            validate_shortcut(value)?;
+            Ok(value.to_string())
+        }
+        SettingKind::Url => match url::Url::parse(value) {
+            Ok(url) if matches!(url.scheme(), "http" | "https") && url.has_host() => Ok(value.to_string()),
+            _ => bail!("must be an http:// or https:// URL"),
+        },
+    }
+}
+
+/// Check global shortcut syntax the same way the shortcut plugin parses it
+pub fn validate_shortcut(shortcut: &str) -> Result<()> {
+    if shortcut.is_empty() {
+        bail!("must not be empty");
+    }
+    shortcut
+        .parse::<tauri_plugin_global_shortcut::Shortcut>()
+        .map(|_| ())
+        .map_err(|e| anyhow!("is not a valid shortcut ({})", e))
+}
+
+/// Validate and store several settings at once
+///
+/// Nothing is written unless every value is valid. Values equal to the stored ones are
+/// skipped, so the returned changes only list settings that actually changed.
+pub fn update(pool: &DbPool, values: &[(String, String)]) -> Result<Vec<SettingChange>> {
+    let mut errors = Vec::new();
+    let mut validated = Vec::new();
+    for (key, value) in values {
+        match validate(key, value) {
+            Ok(value) => validated.push((find(key)?, value)),
+            Err(e) if REGISTRY.iter().any(|def| def.key == key) => errors.push(format!("{}: {}", key, e)),
+            Err(e) => errors.push(e.to_string()),
+        }
+    }
+    if !errors.is_empty() {
+        bail!("Invalid settings:\n{}", errors.join("\n"));
+    }
+
+    let mut changes = Vec::new();
+    for (def, value) in validated {
+        let old = crate::database::get_setting(pool, def.key)?;
+        let value = if def.secret {
+            crate::secrets::protect_setting(pool, def.key, &value)?
+        } else {
+            value
+        };
+
+        // A secret's stored reference stays the same when the secret itself changes
+        if !def.secret && old.as_deref() == Some(value.as_str()) {
+            continue;
+        }
+
+        crate::database::update_setting(pool, def.key, &value)?;
+        changes.push(SettingChange {
+            key: def.key.to_string(),
+            old,
+            side_effect: def.side_effect,
+        });
+    }
+    Ok(changes)
+}
+
+/// Validate and store a single setting
+pub fn set(pool: &DbPool, key: &str, value: &str) -> Result<Vec<SettingChange>> {
+    update(pool, &[(key.to_string(), value.to_string())])
+}
+
+/// Parse a stored value, using the default (with a warning) when it doesn't parse
+pub fn parse_or<T: std::str::FromStr>(values: &HashMap<String, String>, key: &str, default: T) -> T {
+    match values.get(key) {
+        Some(raw) => raw.parse().unwrap_or_else(|_| {
+            eprintln!("[Settings] Ignoring invalid stored value {:?} for '{}', using the default", raw, key);
+            default
+        }),
+        None => default,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_pool;
+
+    #[test]
+    fn test_defaults_match_settings_structs() {
+        for def in REGISTRY {
+            if let Some(default) = default_value(def.key) {
+                assert!(validate(def.key, &default).is_ok(), "default of {} is invalid", def.key);
+            }
+        }
+        assert_eq!(default_value("grid_cols").as_deref(), Some("4"));
+        assert_eq!(default_value("ai_max_concurrent_agents").as_deref(), Some("1"));
+        assert_eq!(default_value("terminal_command"), None);
+    }
+
+    #[test]
+    fn test_validation() {
+        assert_eq!(validate("grid_cols", " 6 ").unwrap(), "6");
+        assert!(validate("grid_cols", "0").unwrap_err().to_string().contains("between 1 and 20"));
+        assert!(validate("grid_cols", "four").is_err());
+        assert!(validate("start_at_login", "yes").is_err());
+        assert!(validate("theme", "solarized").unwrap_err().to_string().contains("system, light, dark"));
+        assert!(validate("ai_endpoint_url", "http://localhost:1234").is_ok());
+        assert!(validate("ai_endpoint_url", "localhost:1234").is_err());
+        assert!(validate("ai_endpoint_url", "ftp://example.com").is_err());
+        assert!(validate("global_shortcut", "CommandOrControl+Shift+Space").is_ok());
+        assert!(validate("global_shortcut", "Shift+Banana").is_err());
+        assert!(validate("global_shortcut", "").is_err());
+        assert_eq!(validate("secrets_salt", "x").unwrap_err().to_string(), "Unknown setting 'secrets_salt'");
+    }
+
+    #[test]
+    fn test_update_is_all_or_nothing() {
+        let pool = test_pool();
+
+        let error = update(
+            &pool,
+            &[
+                ("grid_cols".to_string(), "6".to_string()),
+                ("grid_rows".to_string(), "99".to_string()),
+                ("colour".to_string(), "red".to_string()),
+            ],
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(error.contains("grid_rows: must be a whole number between 1 and 20"), "{}", error);
+        assert!(error.contains("Unknown setting 'colour'"), "{}", error);
+        assert_eq!(crate::database::get_settings(&pool).unwrap().grid_cols, 4);
+
+        let changes = update(
+            &pool,
+            &[
+                ("grid_cols".to_string(), "6".to_string()),
+                ("theme".to_string(), "system".to_string()),
+            ],
+        )
+        .unwrap();
+        // The theme was already "system", so only the grid changed
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].key, "grid_cols");
+        assert_eq!(changes[0].old.as_deref(), Some("4"));
+        assert_eq!(changes[0].side_effect, Some(SideEffect::GridSize));
+        assert_eq!(crate::database::get_settings(&pool).unwrap().grid_cols, 6);
+    }
+
+    #[test]
+    fn test_invalid_stored_value_falls_back_to_default() {
+        let pool = test_pool();
+        crate::database::update_setting(&pool, "grid_rows", "lots").unwrap();
+        assert_eq!(crate::database::get_settings(&pool).unwrap().grid_rows, 3);
+    }
+}
@@ -283,6 +283,23 @@ pub fn unregister_app_shortcut(
     Ok(())
 }
 
+/// Replace the launcher's global shortcut, keeping the old one if the new one can't be registered
+pub fn replace_global_shortcut(
+    app_handle: &AppHandle,
+    old_shortcut: &str,
+    new_shortcut: &str,
+) -> Result<()> {
+    if let Ok(old) = old_shortcut.parse::<Shortcut>() {
+        let _ = app_handle.global_shortcut().unregister(old);
+    }
+
+    if let Err(e) = register_global_shortcut(app_handle, new_shortcut) {
+        let _ = register_global_shortcut(app_handle, old_shortcut);
+        return Err(e);
+    }
+    Ok(())
+}
+
 /// Update the global shortcut
 pub fn update_global_shortcut(
     app_handle: &AppHandle,
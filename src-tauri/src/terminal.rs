@@ -1,3 +1,4 @@
+use crate::environment::LaunchEnv;
 use anyhow::Result;
 use portable_pty::{CommandBuilder, PtySize, native_pty_system};
 use std::io::Read;
@@ -5,14 +6,21 @@ use std::sync::{Arc, Mutex};
 use std::thread;
 use tauri::{AppHandle, Manager, Emitter};
 
+/// What to run in a terminal window
+pub struct TerminalCommand<'a> {
+    pub program: &'a str,
+    pub args: &'a [String],
+    /// The app's working directory and environment overrides
+    pub env: &'a LaunchEnv,
+}
+
 /// Create a terminal window and run a command in it
 pub fn create_terminal_window(
     app_handle: &AppHandle,
     app_id: i64,
     window_label: &str,
     title: &str,
-    command: &str,
-    args: &[String],
+    command: TerminalCommand,
     always_on_top: bool,
 ) -> Result<()> {
     let window_label = window_label.to_string();
@@ -27,8 +35,8 @@ pub fn create_terminal_window(
     })?;
 
     // Build command with essential environment variables
-    let mut cmd = CommandBuilder::new(command);
-    for arg in args {
+    let mut cmd = CommandBuilder::new(command.program);
+    for arg in command.args {
         cmd.arg(arg);
     }
 
@@ -51,6 +59,17 @@ pub fn create_terminal_window(
         cmd.env("SHELL", shell);
     }
 
+    // The app's own settings come last so they can override any of the above
+    if let Some(dir) = &command.env.working_dir {
+        cmd.cwd(dir);
+    }
+    for name in &command.env.unset {
+        cmd.env_remove(name);
+    }
+    for (name, value) in &command.env.set {
+        cmd.env(name, value);
+    }
+
     // Spawn command in PTY
     let child = pair.slave.spawn_command(cmd)?;
 
@@ -142,6 +161,10 @@ pub fn create_terminal_window(
             // Unregister from shortcut manager
             crate::shortcut_manager::unregister_app_window(app_id);
 
+            if let Some(pool) = app_handle_for_cleanup.try_state::<crate::database::DbPool>() {
+                crate::hooks::spawn_post_exit(&pool, &app_handle_for_cleanup, app_id);
+            }
+
             // Remove from terminal state
             if let Some(state) = app_handle_for_cleanup.try_state::<TerminalState>() {
                 if let Ok(mut windows) = state.windows.lock() {
@@ -0,0 +1,228 @@
+use crate::database::{App, AppType, DbPool, WindowState, WorkspaceItem};
+use crate::launcher::LaunchTrigger;
+use anyhow::{anyhow, bail, Result};
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager, PhysicalPosition, PhysicalSize};
+
+/// Longest wait allowed before starting an app of a workspace
+pub const MAX_DELAY_MS: i64 = 10 * 60 * 1000;
+
+/// How long a native app gets to show its window before placing it is given up
+const WINDOW_WAIT: Duration = Duration::from_secs(15);
+
+/// How often a native app's window is looked for while it starts
+const WINDOW_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Check the apps of a workspace: each must exist, not be a workspace itself and appear only once
+pub fn validate(apps: &[App], workspace_id: i64, items: &[WorkspaceItem]) -> Result<()> {
+    let mut seen = HashSet::new();
+    for item in items {
+        let app = apps
+            .iter()
+            .find(|a| a.id == item.app_id)
+            .ok_or_else(|| anyhow!("App {} does not exist", item.app_id))?;
+        if app.app_type == AppType::Workspace || app.id == workspace_id {
+            bail!("A workspace can't contain another workspace ('{}')", app.name);
+        }
+        if !seen.insert(app.id) {
+            bail!("'{}' is in the workspace more than once", app.name);
+        }
+        if !(0..=MAX_DELAY_MS).contains(&item.delay_ms) {
+            bail!("The delay before '{}' must be between 0 and {} seconds", app.name, MAX_DELAY_MS / 1000);
+        }
+    }
+    Ok(())
+}
+
+/// Start the apps of a workspace in order in the background and restore their saved layout
+pub fn launch(workspace: &App, app_handle: &AppHandle, pool: &DbPool) -> Result<()> {
+    let items = crate::database::get_workspace_items(pool, workspace.id)?;
+    if items.is_empty() {
+        bail!("Workspace '{}' has no apps", workspace.name);
+    }
+    let apps = crate::database::get_all_apps(pool)?;
+
+    let app_handle = app_handle.clone();
+    let pool = pool.clone();
+    std::thread::spawn(move || {
+        for item in items {
+            std::thread::sleep(Duration::from_millis(item.delay_ms as u64));
+            let Some(app) = apps.iter().find(|a| a.id == item.app_id) else {
+                continue;
+            };
+            // A failed app is reported by the launcher and doesn't hold up the rest
+            if crate::launcher::launch_app_blocking(app, &app_handle, &pool, LaunchTrigger::Workspace).is_err() {
+                continue;
+            }
+            if let Some(geometry) = item.geometry {
+                restore_geometry(&app_handle, app, geometry);
+            }
+        }
+    });
+    Ok(())
+}
+
+/// Remember where the workspace's open windows are; returns how many were saved
+pub fn save_layout(app_handle: &AppHandle, pool: &DbPool, workspace_id: i64) -> Result<usize> {
+    let apps = crate::database::get_all_apps(pool)?;
+    let mut saved = 0;
+    for item in crate::database::get_workspace_items(pool, workspace_id)? {
+        let Some(app) = apps.iter().find(|a| a.id == item.app_id) else {
+            continue;
+        };
+        if let Some(geometry) = current_geometry(app_handle, app) {
+            crate::database::save_workspace_geometry(pool, workspace_id, app.id, &geometry)?;
+            saved += 1;
+        }
+    }
+    Ok(saved)
+}
+
+/// Save the layout, then close the workspace's windows and ask its native apps to quit
+pub fn close(app_handle: &AppHandle, pool: &DbPool, workspace_id: i64) -> Result<()> {
+    save_layout(app_handle, pool, workspace_id)?;
+
+    let apps = crate::database::get_all_apps(pool)?;
+    let mut failed = Vec::new();
+    for item in crate::database::get_workspace_items(pool, workspace_id)? {
+        let Some(app) = apps.iter().find(|a| a.id == item.app_id) else {
+            continue;
+        };
+        let result = match window_label(app) {
+            Some(label) => match app_handle.get_webview_window(&label) {
+                Some(window) => window.close().map_err(anyhow::Error::from),
+                None => Ok(()),
+            },
+            None if app.app_type == AppType::App && crate::processes::is_running(app.id) => {
+                crate::processes::stop(app.id, false)
+            }
+            None => Ok(()),
+        };
+        if let Err(e) = result {
+            eprintln!("Failed to close {}: {}", app.name, e);
+            failed.push(app.name.clone());
+        }
+    }
+
+    if !failed.is_empty() {
+        bail!("Failed to close {}", failed.join(", "));
+    }
+    // A workspace has no process or window of its own; closing it is when it ends
+    crate::hooks::spawn_post_exit(pool, app_handle, workspace_id);
+    Ok(())
+}
+
+/// Label of the launcher window an app runs in, for webapps and terminal apps
+fn window_label(app: &App) -> Option<String> {
+    match app.app_type {
+        AppType::Webapp => Some(format!("webapp_{}", app.id)),
+        AppType::Tui => Some(format!("tui_{}", app.id)),
+        _ => None,
+    }
+}
+
+fn current_geometry(app_handle: &AppHandle, app: &App) -> Option<WindowState> {
+    match window_label(app) {
+        Some(label) => {
+            let window = app_handle.get_webview_window(&label)?;
+            let position = window.outer_position().ok()?;
+            let size = window.inner_size().ok()?;
+            Some(WindowState {
+                x: position.x,
+                y: position.y,
+                width: size.width as i32,
+                height: size.height as i32,
+            })
+        }
+        None if app.app_type == AppType::App => crate::processes::window_geometry(app.id).ok(),
+        None => None,
+    }
+}
+
+/// Put an app's window where the workspace last had it
+fn restore_geometry(app_handle: &AppHandle, app: &App, geometry: WindowState) {
+    match window_label(app) {
+        Some(label) => {
+            if let Some(window) = app_handle.get_webview_window(&label) {
+                let _ = window.set_position(PhysicalPosition::new(geometry.x, geometry.y));
+                let _ = window.set_size(PhysicalSize::new(geometry.width.max(1) as u32, geometry.height.max(1) as u32));
+            }
+        }
+        None if app.app_type == AppType::App => {
+            // Native windows show up some time after the process starts
+            let app_id = app.id;
+            let name = app.name.clone();
+            std::thread::spawn(move || {
+                let started = Instant::now();
+                while let Err(e) = crate::processes::place(app_id, &geometry) {
+                    if !crate::processes::is_running(app_id) {
+                        return;
+                    }
+                    if started.elapsed() >= WINDOW_WAIT {
+                        eprintln!("Failed to place the window of {}: {}", name, e);
+                        return;
+                    }
+                    std::thread::sleep(WINDOW_POLL_INTERVAL);
+                }
+            });
+        }
+        None => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{create_app, get_all_apps, test_pool, NewApp};
+
+    fn new_app(app_type: AppType, name: &str) -> NewApp {
+        NewApp {
+            app_type,
+            name: name.to_string(),
+            icon_path: None,
+            shortcut: None,
+            global_shortcut: None,
+            binary_path: Some("/bin/true".to_string()),
+            cli_params: None,
+            url: Some("https://example.com".to_string()),
+            show_nav_controls: None,
+            open_external_links: None,
+            enable_oauth: None,
+            auto_close_timeout: None,
+            always_on_top: None,
+            hide_on_shortcut: None,
+            single_instance: None,
+            working_dir: None,
+            env: Vec::new(),
+            env_file: None,
+            pre_launch_hook: None,
+            post_exit_hook: None,
+            hook_timeout: None,
+        }
+    }
+
+    fn item(app_id: i64, delay_ms: i64) -> WorkspaceItem {
+        WorkspaceItem { app_id, delay_ms, geometry: None }
+    }
+
+    #[test]
+    fn test_validate_workspace_items() {
+        let pool = test_pool();
+        let morning = create_app(&pool, new_app(AppType::Workspace, "Morning"), None).unwrap();
+        let evening = create_app(&pool, new_app(AppType::Workspace, "Evening"), None).unwrap();
+        let mail = create_app(&pool, new_app(AppType::Webapp, "Mail"), None).unwrap();
+        let htop = create_app(&pool, new_app(AppType::Tui, "htop"), None).unwrap();
+        let apps = get_all_apps(&pool).unwrap();
+
+        assert!(validate(&apps, morning, &[]).is_ok());
+        assert!(validate(&apps, morning, &[item(mail, 0), item(htop, MAX_DELAY_MS)]).is_ok());
+
+        let error = |items: &[WorkspaceItem]| validate(&apps, morning, items).unwrap_err().to_string();
+        assert_eq!(error(&[item(99, 0)]), "App 99 does not exist");
+        assert_eq!(error(&[item(evening, 0)]), "A workspace can't contain another workspace ('Evening')");
+        assert_eq!(error(&[item(morning, 0)]), "A workspace can't contain another workspace ('Morning')");
+        assert_eq!(error(&[item(mail, 0), item(mail, 500)]), "'Mail' is in the workspace more than once");
+        assert_eq!(error(&[item(htop, -1)]), "The delay before 'htop' must be between 0 and 600 seconds");
+    }
+}